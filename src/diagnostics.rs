@@ -0,0 +1,11 @@
+mod diagnostic;
+mod renderer;
+
+pub use diagnostic::*;
+pub use renderer::*;
+
+/// Conversion of compiler errors into renderable diagnostics.
+pub trait ToDiagnostic
+{
+  fn to_diagnostic(&self) -> Diagnostic;
+}
@@ -0,0 +1,144 @@
+use crate::syntax::surface::Span;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Severity
+{
+  Error,
+  Warning,
+}
+
+impl std::fmt::Display for Severity
+{
+  fn fmt(
+    &self,
+    f: &mut std::fmt::Formatter<'_>,
+  ) -> std::fmt::Result
+  {
+    match self {
+      | Severity::Error => write!(f, "error"),
+      | Severity::Warning => write!(f, "warning"),
+    }
+  }
+}
+
+/// The part of the source a label points at.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Anchor
+{
+  Span(Span),
+  EndOfInput,
+}
+
+impl From<Span> for Anchor
+{
+  fn from(span: Span) -> Self
+  {
+    Self::Span(span)
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LabelStyle
+{
+  Primary,
+  Secondary,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Label
+{
+  pub style: LabelStyle,
+  pub anchor: Anchor,
+  pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic
+{
+  pub severity: Severity,
+  pub message: String,
+  pub labels: Vec<Label>,
+  pub notes: Vec<String>,
+}
+
+impl Diagnostic
+{
+  pub fn error<IntoString>(message: IntoString) -> Self
+  where
+    IntoString: Into<String>,
+  {
+    Self::new(Severity::Error, message)
+  }
+
+  pub fn warning<IntoString>(message: IntoString) -> Self
+  where
+    IntoString: Into<String>,
+  {
+    Self::new(Severity::Warning, message)
+  }
+
+  fn new<IntoString>(
+    severity: Severity,
+    message: IntoString,
+  ) -> Self
+  where
+    IntoString: Into<String>,
+  {
+    Self {
+      severity,
+      message: message.into(),
+      labels: vec![],
+      notes: vec![],
+    }
+  }
+
+  pub fn with_primary<IntoAnchor, IntoString>(
+    self,
+    anchor: IntoAnchor,
+    message: IntoString,
+  ) -> Self
+  where
+    IntoAnchor: Into<Anchor>,
+    IntoString: Into<String>,
+  {
+    self.with_label(LabelStyle::Primary, anchor.into(), message.into())
+  }
+
+  pub fn with_secondary<IntoAnchor, IntoString>(
+    self,
+    anchor: IntoAnchor,
+    message: IntoString,
+  ) -> Self
+  where
+    IntoAnchor: Into<Anchor>,
+    IntoString: Into<String>,
+  {
+    self.with_label(LabelStyle::Secondary, anchor.into(), message.into())
+  }
+
+  pub fn with_note<IntoString>(
+    mut self,
+    note: IntoString,
+  ) -> Self
+  where
+    IntoString: Into<String>,
+  {
+    self.notes.push(note.into());
+    self
+  }
+
+  fn with_label(
+    mut self,
+    style: LabelStyle,
+    anchor: Anchor,
+    message: String,
+  ) -> Self
+  {
+    self.labels.push(Label {
+      style,
+      anchor,
+      message,
+    });
+    self
+  }
+}
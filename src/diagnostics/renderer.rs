@@ -0,0 +1,387 @@
+use super::{
+  Anchor,
+  Diagnostic,
+  Label,
+  LabelStyle,
+  Severity,
+};
+use crate::syntax::surface::Location;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutputMode
+{
+  Plain,
+  Ansi,
+}
+
+impl OutputMode
+{
+  /// Colours output written to a terminal unless `NO_COLOR` is set.
+  pub fn detect() -> Self
+  {
+    use std::io::IsTerminal;
+    match std::env::var_os("NO_COLOR") {
+      | None if std::io::stderr().is_terminal() => OutputMode::Ansi,
+      | _ => OutputMode::Plain,
+    }
+  }
+}
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const RED: &str = "\x1b[1;31m";
+const YELLOW: &str = "\x1b[1;33m";
+const BLUE: &str = "\x1b[1;34m";
+
+/// Renders diagnostics against the source they were produced from.
+pub struct Renderer<'a>
+{
+  name: &'a str,
+  source: &'a str,
+  mode: OutputMode,
+}
+
+/// A label resolved to a single line of the source.
+struct Underline<'a>
+{
+  label: &'a Label,
+  line: usize,
+  column: usize,
+  width: usize,
+}
+
+impl<'a> Renderer<'a>
+{
+  pub fn new(
+    name: &'a str,
+    source: &'a str,
+    mode: OutputMode,
+  ) -> Self
+  {
+    Self {
+      name,
+      source,
+      mode,
+    }
+  }
+
+  pub fn render(
+    &self,
+    diagnostic: &Diagnostic,
+  ) -> String
+  {
+    let severity_style = match diagnostic.severity {
+      | Severity::Error => RED,
+      | Severity::Warning => YELLOW,
+    };
+    let mut output = format!(
+      "{}{}\n",
+      self.paint(severity_style, &diagnostic.severity.to_string()),
+      self.paint(BOLD, &format!(": {}", diagnostic.message)),
+    );
+
+    let mut underlines = diagnostic
+      .labels
+      .iter()
+      .map(|label| self.underline(label))
+      .collect::<Vec<_>>();
+    let gutter = underlines
+      .iter()
+      .map(|underline| underline.line.to_string().len())
+      .max()
+      .unwrap_or(0);
+    let padding = " ".repeat(gutter);
+
+    if let Some(primary) = underlines
+      .iter()
+      .find(|underline| underline.label.style == LabelStyle::Primary)
+      .or(underlines.first())
+    {
+      output += &format!(
+        "{}{} {}:{}:{}\n",
+        padding,
+        self.paint(BLUE, "-->"),
+        self.name,
+        primary.line,
+        primary.column,
+      );
+    }
+
+    underlines.sort_by_key(|underline| (underline.line, underline.column));
+    let mut previous_line = None;
+    for underline in underlines.iter() {
+      if previous_line.is_none() {
+        output += &format!("{} {}\n", padding, self.paint(BLUE, "|"));
+      }
+      if previous_line != Some(underline.line) {
+        if matches!(previous_line, Some(previous) if underline.line > previous + 1)
+        {
+          output += &format!("{}\n", self.paint(BLUE, "..."));
+        }
+        output += &format!(
+          "{} {} {}\n",
+          self.paint(BLUE, &format!("{:>gutter$}", underline.line)),
+          self.paint(BLUE, "|"),
+          self.line(underline.line),
+        );
+        previous_line = Some(underline.line);
+      }
+      let (marker, style) = match underline.label.style {
+        | LabelStyle::Primary => ("^", severity_style),
+        | LabelStyle::Secondary => ("-", BLUE),
+      };
+      let indent = self
+        .line(underline.line)
+        .chars()
+        .take(underline.column - 1)
+        .map(|char| match char {
+          | '\t' => '\t',
+          | _ => ' ',
+        })
+        .collect::<String>();
+      let marked = format!(
+        "{} {}",
+        marker.repeat(underline.width),
+        underline.label.message
+      );
+      output += &format!(
+        "{} {} {}{}\n",
+        padding,
+        self.paint(BLUE, "|"),
+        indent,
+        self.paint(style, marked.trim_end()),
+      );
+    }
+    if previous_line.is_some() {
+      output += &format!("{} {}\n", padding, self.paint(BLUE, "|"));
+    }
+
+    for note in diagnostic.notes.iter() {
+      output += &format!(
+        "{} {} {}\n",
+        padding,
+        self.paint(BLUE, "="),
+        self.paint(BOLD, &format!("note: {}", note)),
+      );
+    }
+    output
+  }
+
+  fn underline<'b>(
+    &self,
+    label: &'b Label,
+  ) -> Underline<'b>
+  {
+    let (start, end) = match label.anchor {
+      | Anchor::Span(span) => (span.start, span.end),
+      | Anchor::EndOfInput => {
+        let end = self.end_of_input();
+        (end, end)
+      },
+    };
+    let line_length = self.line(start.line).chars().count();
+    let width = match () {
+      | _ if end.line == start.line => end.column.saturating_sub(start.column),
+      | _ => (line_length + 1).saturating_sub(start.column),
+    };
+    Underline {
+      label,
+      line: start.line,
+      column: start.column,
+      width: std::cmp::max(width, 1),
+    }
+  }
+
+  /// Location just past the last character that is not whitespace.
+  fn end_of_input(&self) -> Location
+  {
+    let mut location = Location::default();
+    for char in self.source.trim_end().chars() {
+      location.advance(char);
+    }
+    location
+  }
+
+  fn line(
+    &self,
+    line: usize,
+  ) -> &str
+  {
+    self
+      .source
+      .lines()
+      .nth(line.saturating_sub(1))
+      .unwrap_or("")
+  }
+
+  fn paint(
+    &self,
+    style: &str,
+    text: &str,
+  ) -> String
+  {
+    match self.mode {
+      | OutputMode::Plain => text.to_string(),
+      | OutputMode::Ansi => format!("{}{}{}", style, text, RESET),
+    }
+  }
+}
+
+#[cfg(test)]
+mod spec
+{
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+  use crate::syntax::surface::Span;
+
+  fn span(
+    start: (usize, usize, usize),
+    end: (usize, usize, usize),
+  ) -> Span
+  {
+    Span::new(
+      Location::new(start.0, start.1, start.2),
+      Location::new(end.0, end.1, end.2),
+    )
+  }
+
+  #[test]
+  fn header_only()
+  {
+    let renderer = Renderer::new("main.ml", "", OutputMode::Plain);
+    assert_eq!(
+      renderer.render(&Diagnostic::error("something went wrong")),
+      "error: something went wrong\n"
+    );
+  }
+
+  #[test]
+  fn primary_label()
+  {
+    let source = "val foo bar ;";
+    let renderer = Renderer::new("main.ml", source, OutputMode::Plain);
+    let diagnostic = Diagnostic::error("unexpected token")
+      .with_primary(span((8, 1, 9), (11, 1, 12)), "expected `=`");
+    assert_eq!(
+      renderer.render(&diagnostic),
+      [
+        "error: unexpected token",
+        " --> main.ml:1:9",
+        "  |",
+        "1 | val foo bar ;",
+        "  |         ^^^ expected `=`",
+        "  |",
+        "",
+      ]
+      .join("\n")
+    );
+  }
+
+  #[test]
+  fn secondary_labels_and_notes()
+  {
+    let source = "val f = fun x -> x ;\n\n\nval y = f f 10 ;";
+    let renderer = Renderer::new("main.ml", source, OutputMode::Plain);
+    let diagnostic = Diagnostic::error("mismatched types")
+      .with_primary(span((33, 4, 11), (34, 4, 12)), "expected `Int`")
+      .with_secondary(span((4, 1, 5), (5, 1, 6)), "defined here")
+      .with_note("functions are not numbers");
+    assert_eq!(
+      renderer.render(&diagnostic),
+      [
+        "error: mismatched types",
+        " --> main.ml:4:11",
+        "  |",
+        "1 | val f = fun x -> x ;",
+        "  |     - defined here",
+        "...",
+        "4 | val y = f f 10 ;",
+        "  |           ^ expected `Int`",
+        "  |",
+        "  = note: functions are not numbers",
+        "",
+      ]
+      .join("\n")
+    );
+  }
+
+  #[test]
+  fn end_of_input()
+  {
+    let source = "val foo =\n";
+    let renderer = Renderer::new("main.ml", source, OutputMode::Plain);
+    let diagnostic = Diagnostic::error("unexpected end of input")
+      .with_primary(Anchor::EndOfInput, "expected expression");
+    assert_eq!(
+      renderer.render(&diagnostic),
+      [
+        "error: unexpected end of input",
+        " --> main.ml:1:10",
+        "  |",
+        "1 | val foo =",
+        "  |          ^ expected expression",
+        "  |",
+        "",
+      ]
+      .join("\n")
+    );
+  }
+
+  #[test]
+  fn multiline_span_is_underlined_to_end_of_line()
+  {
+    let source = "fun x ->\n  x";
+    let renderer = Renderer::new("main.ml", source, OutputMode::Plain);
+    let diagnostic =
+      Diagnostic::error("unused").with_primary(span((0, 1, 1), (12, 2, 4)), "");
+    assert_eq!(
+      renderer.render(&diagnostic),
+      [
+        "error: unused",
+        " --> main.ml:1:1",
+        "  |",
+        "1 | fun x ->",
+        "  | ^^^^^^^^",
+        "  |",
+        "",
+      ]
+      .join("\n")
+    );
+  }
+
+  #[test]
+  fn tabs_are_preserved_in_indentation()
+  {
+    let source = "\tfoo";
+    let renderer = Renderer::new("main.ml", source, OutputMode::Plain);
+    let diagnostic = Diagnostic::error("unbound")
+      .with_primary(span((1, 1, 2), (4, 1, 5)), "here");
+    assert!(renderer
+      .render(&diagnostic)
+      .contains("  | \t^^^ here\n"));
+  }
+
+  #[test]
+  fn ansi_mode_colours_output()
+  {
+    let source = "foo";
+    let renderer = Renderer::new("main.ml", source, OutputMode::Ansi);
+    let diagnostic = Diagnostic::error("unbound")
+      .with_primary(span((0, 1, 1), (3, 1, 4)), "here");
+    let rendered = renderer.render(&diagnostic);
+    assert!(
+      rendered.starts_with("\x1b[1;31merror\x1b[0m\x1b[1m: unbound\x1b[0m\n")
+    );
+    assert!(rendered.contains("\x1b[1;31m^^^ here\x1b[0m"));
+  }
+
+  #[test]
+  fn warnings_are_yellow()
+  {
+    let renderer = Renderer::new("main.ml", "", OutputMode::Ansi);
+    assert!(renderer
+      .render(&Diagnostic::warning("unused"))
+      .starts_with("\x1b[1;33mwarning\x1b[0m"));
+  }
+}
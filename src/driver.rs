@@ -0,0 +1,483 @@
+use std::io::Write;
+
+use crate::diagnostics::{
+  OutputMode,
+  Renderer,
+  ToDiagnostic,
+};
+use crate::frontend::{
+  DeclarationParser,
+  Lexer,
+  WithBacktracking,
+};
+use crate::session::{
+  CompilationError,
+  Session,
+};
+use crate::syntax::surface;
+
+pub const USAGE: &str = "\
+usage:
+  rusty-ml [options] <file.ml>   run every declaration in a file
+  rusty-ml repl                  start an interactive session
+  rusty-ml --help                show this message
+
+options:
+  --dump-tokens     print the lexemes of the file
+  --dump-surface    print the surface syntax tree of every declaration
+  --dump-debrujin   print the de Bruijn encoding of every declaration
+  --dump-types      print the inferred type of every declaration
+
+exit codes:
+  0  success
+  1  usage or io error
+  2  parse error
+  3  encoding error
+  4  type error
+  5  runtime error
+";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command
+{
+  Run(Options),
+  Repl,
+  Help,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Options
+{
+  pub path: String,
+  pub dump_tokens: bool,
+  pub dump_surface: bool,
+  pub dump_debrujin: bool,
+  pub dump_types: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitCode
+{
+  Success = 0,
+  Usage = 1,
+  Parse = 2,
+  Encoding = 3,
+  Type = 4,
+  Runtime = 5,
+}
+
+impl From<&CompilationError> for ExitCode
+{
+  fn from(error: &CompilationError) -> Self
+  {
+    match error {
+      | CompilationError::Parse(_) => ExitCode::Parse,
+      | CompilationError::Encoding(_) => ExitCode::Encoding,
+      | CompilationError::Type(_) => ExitCode::Type,
+      | CompilationError::Runtime(_) => ExitCode::Runtime,
+    }
+  }
+}
+
+pub fn parse_args<Args>(args: Args) -> Result<Command, String>
+where
+  Args: IntoIterator<Item = String>,
+{
+  let mut options = Options::default();
+  let mut path = None;
+  for arg in args {
+    match arg.as_str() {
+      | "--help" | "-h" => return Ok(Command::Help),
+      | "repl" if path.is_none() => return Ok(Command::Repl),
+      | "--dump-tokens" => options.dump_tokens = true,
+      | "--dump-surface" => options.dump_surface = true,
+      | "--dump-debrujin" => options.dump_debrujin = true,
+      | "--dump-types" => options.dump_types = true,
+      | flag if flag.starts_with("--") =>
+        return Err(format!("unknown option `{}`", flag)),
+      | _ if path.is_some() =>
+        return Err(format!("unexpected argument `{}`", arg)),
+      | _ => path = Some(arg),
+    }
+  }
+  match path {
+    | Some(path) => Ok(Command::Run(Options {
+      path,
+      ..options
+    })),
+    | None => Err("missing source file".to_string()),
+  }
+}
+
+/// Reads the file named in `options` and runs it, see `run_source`.
+pub fn run(
+  options: &Options,
+  mode: OutputMode,
+  out: &mut dyn Write,
+  err: &mut dyn Write,
+) -> std::io::Result<ExitCode>
+{
+  match std::fs::read_to_string(&options.path) {
+    | Ok(source) => run_source(options, &source, mode, out, err),
+    | Err(error) => {
+      writeln!(err, "error: could not read `{}`: {}", options.path, error)?;
+      Ok(ExitCode::Usage)
+    },
+  }
+}
+
+/// Parses, encodes, type checks and evaluates every declaration of
+/// `source` after the prelude, one stage at a time, so that a failing stage
+/// reports before any later stage runs.
+pub fn run_source(
+  options: &Options,
+  source: &str,
+  mode: OutputMode,
+  out: &mut dyn Write,
+  err: &mut dyn Write,
+) -> std::io::Result<ExitCode>
+{
+  let renderer = Renderer::new(&options.path, source, mode);
+  match run_stages(options, source, &renderer, out, err)? {
+    | Ok(()) => Ok(ExitCode::Success),
+    | Err(error) => {
+      write!(err, "{}", renderer.render(&error.to_diagnostic()))?;
+      Ok(ExitCode::from(&error))
+    },
+  }
+}
+
+fn run_stages(
+  options: &Options,
+  source: &str,
+  renderer: &Renderer,
+  out: &mut dyn Write,
+  err: &mut dyn Write,
+) -> std::io::Result<Result<(), CompilationError>>
+{
+  if options.dump_tokens {
+    for lexeme in Lexer::from_str(source) {
+      let start = lexeme.span().start;
+      writeln!(
+        out,
+        "{}:{} {:?} {:?}",
+        start.line,
+        start.column,
+        lexeme.token(),
+        lexeme.value()
+      )?;
+    }
+  }
+
+  let program = match Lexer::from_str(source)
+    .with_backtracking()
+    .expect_program()
+  {
+    | Ok(program) => program,
+    | Err(error) => return Ok(Err(error.into())),
+  };
+  let declarations = &program.declarations;
+  if options.dump_surface {
+    for declaration in declarations {
+      writeln!(out, "{:#?}", declaration)?;
+    }
+  }
+
+  let mut session = Session::with_prelude();
+  let encoded = match session.encode_program(&program) {
+    | Ok(encoded) => encoded.declarations,
+    | Err(error) => return Ok(Err(error)),
+  };
+  if options.dump_debrujin {
+    for (declaration, encoded) in declarations.iter().zip(&encoded) {
+      let (keyword, names, separator) = match declaration {
+        | surface::TopLevel::ValBinding(_) =>
+          ("val", declaration.names(), " and "),
+        | surface::TopLevel::DefBinding(_) =>
+          ("def", declaration.names(), " and "),
+        | surface::TopLevel::TypeDeclaration(_) =>
+          ("type", declaration.names(), " and "),
+        | surface::TopLevel::Structure(structure) =>
+          ("structure", vec![structure.name.clone()], ""),
+        | surface::TopLevel::Open(open) => ("open", open.path.clone(), "."),
+      };
+      let names = names
+        .iter()
+        .map(|name| name.name.as_str())
+        .collect::<Vec<_>>();
+      writeln!(out, "{} {} = {:#?}", keyword, names.join(separator), encoded)?;
+    }
+  }
+
+  let types = match session.infer_program(&program) {
+    | Ok(types) => types,
+    | Err(error) => return Ok(Err(error)),
+  };
+  for warning in session.take_warnings() {
+    write!(err, "{}", renderer.render(&warning.to_diagnostic()))?;
+  }
+  if options.dump_types {
+    for (name, scheme) in &types {
+      writeln!(out, "val {} : {}", name.name, scheme)?;
+    }
+  }
+
+  let mut types = types.iter();
+  for encoded in encoded.iter() {
+    let values = match session.evaluate_declaration(encoded) {
+      | Ok(values) => values,
+      | Err(error) => return Ok(Err(error)),
+    };
+    for (value, (name, scheme)) in values.iter().zip(&mut types) {
+      writeln!(out, "val {} = {} : {}", name.name, value, scheme)?;
+    }
+  }
+  Ok(Ok(()))
+}
+
+#[cfg(test)]
+mod spec
+{
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  fn args(args: &[&str]) -> Result<Command, String>
+  {
+    parse_args(args.iter().map(|arg| arg.to_string()))
+  }
+
+  fn execute(
+    options: Options,
+    source: &str,
+  ) -> (ExitCode, String, String)
+  {
+    let mut out = vec![];
+    let mut err = vec![];
+    let options = Options {
+      path: "test.ml".to_string(),
+      ..options
+    };
+    let code =
+      run_source(&options, source, OutputMode::Plain, &mut out, &mut err)
+        .unwrap();
+    (code, String::from_utf8(out).unwrap(), String::from_utf8(err).unwrap())
+  }
+
+  #[test]
+  fn parses_commands()
+  {
+    assert_eq!(args(&["repl"]), Ok(Command::Repl));
+    assert_eq!(args(&["--help"]), Ok(Command::Help));
+    assert_eq!(
+      args(&["--dump-types", "main.ml", "--dump-tokens"]),
+      Ok(Command::Run(Options {
+        path: "main.ml".to_string(),
+        dump_tokens: true,
+        dump_types: true,
+        ..Options::default()
+      }))
+    );
+    assert!(args(&[]).is_err());
+    assert!(args(&["--frobnicate", "main.ml"]).is_err());
+    assert!(args(&["main.ml", "other.ml"]).is_err());
+  }
+
+  #[test]
+  fn runs_declarations_in_order()
+  {
+    assert_eq!(
+      execute(
+        Options::default(),
+        "val x = 10 ;\nval id = fun y -> y ;\nval z = id x ;\n"
+      ),
+      (
+        ExitCode::Success,
+        "val x = 10 : Int\nval id = <fun> : 'a -> 'a\nval z = 10 : Int\n"
+          .to_string(),
+        String::new()
+      )
+    );
+  }
+
+  #[test]
+  fn runs_recursive_declarations()
+  {
+    assert_eq!(
+      execute(
+        Options::default(),
+        "def loop x = loop x ;\nval id = fun y -> y ;\nval z = id 10 ;\n"
+      ),
+      (
+        ExitCode::Success,
+        "val loop = <fun> : 'a -> 'b\nval id = <fun> : 'a -> 'a\nval z = 10 : \
+         Int\n"
+          .to_string(),
+        String::new()
+      )
+    );
+  }
+
+  #[test]
+  fn programs_can_use_the_prelude()
+  {
+    assert_eq!(
+      execute(Options::default(), "val xs = map succ [1, 2] ;\n"),
+      (
+        ExitCode::Success,
+        "val xs = [2, 3] : List Int\n".to_string(),
+        String::new()
+      )
+    );
+  }
+
+  #[test]
+  fn structure_members_are_printed_qualified()
+  {
+    assert_eq!(
+      execute(
+        Options::default(),
+        "structure A = struct val x = 1 ; end ;\nval y = A.x ;\n"
+      ),
+      (
+        ExitCode::Success,
+        "val A.x = 1 : Int\nval y = 1 : Int\n".to_string(),
+        String::new()
+      )
+    );
+  }
+
+  #[test]
+  fn dumps_tokens_and_types()
+  {
+    let (code, out, _) = execute(
+      Options {
+        dump_tokens: true,
+        dump_types: true,
+        ..Options::default()
+      },
+      "val x = true ;",
+    );
+    assert_eq!(code, ExitCode::Success);
+    assert_eq!(
+      out,
+      [
+        "1:1 Keyword(\"val\") \"val\"",
+        "1:5 Identifier \"x\"",
+        "1:7 Keyword(\"=\") \"=\"",
+        "1:9 Keyword(\"true\") \"true\"",
+        "1:14 Keyword(\";\") \";\"",
+        "val x : Boolean",
+        "val x = true : Boolean",
+        "",
+      ]
+      .join("\n")
+    );
+  }
+
+  #[test]
+  fn parse_errors_stop_before_evaluation()
+  {
+    let (code, out, err) = execute(Options::default(), "val x = 10 ;\nval");
+    assert_eq!(code, ExitCode::Parse);
+    assert_eq!(out, "");
+    assert!(err.starts_with("error: "));
+  }
+
+  #[test]
+  fn encoding_errors_report_the_unbound_name()
+  {
+    let (code, out, err) =
+      execute(Options::default(), "val x = 10 ;\nval y = z ;\n");
+    assert_eq!(code, ExitCode::Encoding);
+    assert_eq!(out, "");
+    assert!(err.contains("unbound variable `z`"));
+    assert!(err.contains(" --> test.ml:2:9"));
+  }
+
+  #[test]
+  fn type_errors_stop_before_evaluation()
+  {
+    let (code, out, err) =
+      execute(Options::default(), "val x = 10 ;\nval y = x x ;\n");
+    assert_eq!(code, ExitCode::Type);
+    assert_eq!(out, "");
+    assert_eq!(
+      err,
+      [
+        "error: `Int` is not a function",
+        " --> test.ml:2:9",
+        "  |",
+        "2 | val y = x x ;",
+        "  |         ^^^ cannot be applied to an argument",
+        "  |",
+        "",
+      ]
+      .join("\n")
+    );
+  }
+
+  #[test]
+  fn warnings_do_not_stop_evaluation()
+  {
+    let (code, out, err) = execute(
+      Options::default(),
+      "val x = match 1 with 1 -> true | _ -> false | 2 -> true ;\n",
+    );
+    assert_eq!(code, ExitCode::Success);
+    assert_eq!(out, "val x = true : Boolean\n");
+    assert_eq!(
+      err,
+      [
+        "warning: unreachable pattern",
+        " --> test.ml:1:47",
+        "  |",
+        "1 | val x = match 1 with 1 -> true | _ -> false | 2 -> true ;",
+        "  |                                               ^ every value it \
+         matches is matched above",
+        "  |",
+        "",
+      ]
+      .join("\n")
+    );
+  }
+
+  #[test]
+  fn runtime_errors_stop_evaluation()
+  {
+    let (code, out, err) = execute(
+      Options::default(),
+      "val x = 10 ;\nval y = x / 0 ;\nval z = x ;\n",
+    );
+    assert_eq!(code, ExitCode::Runtime);
+    assert_eq!(out, "val x = 10 : Int\n");
+    assert_eq!(
+      err,
+      [
+        "error: division by zero",
+        " --> test.ml:2:9",
+        "  |",
+        "2 | val y = x / 0 ;",
+        "  |         ^^^^^ the divisor is zero",
+        "  |",
+        "",
+      ]
+      .join("\n")
+    );
+  }
+
+  #[test]
+  fn exit_codes_follow_the_failing_stage()
+  {
+    use crate::syntax::debrujin::transformations::{
+      RuntimeError,
+      Value,
+    };
+    assert_eq!(
+      ExitCode::from(&CompilationError::Runtime(RuntimeError::NotAFunction(
+        Value::Bool(true)
+      ))),
+      ExitCode::Runtime
+    );
+  }
+}
@@ -5,3 +5,4 @@ mod tokens;
 
 pub use lexer::Lexer;
 pub use parser::*;
+pub use tokens::Token;
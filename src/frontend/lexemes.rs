@@ -1,10 +1,12 @@
 use super::tokens::Token;
+use crate::syntax::surface::Span;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Lexeme
 {
   token: Token,
   value: String,
+  span: Span,
 }
 
 impl Lexeme
@@ -14,6 +16,7 @@ impl Lexeme
     Self {
       token: Token::UnclosedComment,
       value: "".to_string(),
+      span: Span::default(),
     }
   }
 
@@ -22,6 +25,7 @@ impl Lexeme
     Self {
       token: Token::UnclosedString,
       value: "".to_string(),
+      span: Span::default(),
     }
   }
 
@@ -30,6 +34,7 @@ impl Lexeme
     Self {
       token: Token::Symbol(value),
       value: value.to_string(),
+      span: Span::default(),
     }
   }
 
@@ -40,6 +45,18 @@ impl Lexeme
     Self {
       token: Token::Identifier,
       value: value.into(),
+      span: Span::default(),
+    }
+  }
+
+  pub fn operator<IntoString>(value: IntoString) -> Self
+  where
+    IntoString: Into<String>,
+  {
+    Self {
+      token: Token::Operator,
+      value: value.into(),
+      span: Span::default(),
     }
   }
 
@@ -50,16 +67,29 @@ impl Lexeme
     Self {
       token: Token::StringLiteral,
       value: value.into(),
+      span: Span::default(),
+    }
+  }
+
+  pub fn integer<IntoString>(value: IntoString) -> Self
+  where
+    IntoString: Into<String>,
+  {
+    Self {
+      token: Token::IntegerLiteral,
+      value: value.into(),
+      span: Span::default(),
     }
   }
 
-  pub fn numeric<IntoString>(value: IntoString) -> Self
+  pub fn float<IntoString>(value: IntoString) -> Self
   where
     IntoString: Into<String>,
   {
     Self {
-      token: Token::NumericLiteral,
+      token: Token::FloatLiteral,
       value: value.into(),
+      span: Span::default(),
     }
   }
 
@@ -70,6 +100,7 @@ impl Lexeme
     Self {
       token: Token::MalformedNumericLiteral,
       value: value.into(),
+      span: Span::default(),
     }
   }
 
@@ -78,6 +109,18 @@ impl Lexeme
     Self {
       token: Token::Keyword(value),
       value: value.into(),
+      span: Span::default(),
+    }
+  }
+
+  pub fn with_span(
+    self,
+    span: Span,
+  ) -> Self
+  {
+    Self {
+      span,
+      ..self
     }
   }
 
@@ -90,4 +133,28 @@ impl Lexeme
   {
     &self.value
   }
+
+  pub fn span(&self) -> Span
+  {
+    self.span
+  }
+}
+
+impl std::fmt::Display for Lexeme
+{
+  fn fmt(
+    &self,
+    f: &mut std::fmt::Formatter<'_>,
+  ) -> std::fmt::Result
+  {
+    match self.token {
+      | Token::Identifier
+      | Token::Operator
+      | Token::IntegerLiteral
+      | Token::FloatLiteral
+      | Token::MalformedNumericLiteral =>
+        write!(f, "{} `{}`", self.token, self.value),
+      | _ => write!(f, "{}", self.token),
+    }
+  }
 }
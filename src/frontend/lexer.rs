@@ -4,14 +4,21 @@ mod feedable_result;
 mod state;
 
 use self::feedable_result::FeedableResult;
+pub use self::state::parse_numeric;
 use self::state::State;
 use super::lexemes::Lexeme;
+use crate::syntax::surface::{
+  Location,
+  Span,
+};
 
 pub struct Lexer<'a>
 {
   source: std::str::Chars<'a>,
   state: State,
   buffer: Option<char>,
+  location: Location,
+  lexeme_start: Location,
 }
 
 impl<'a> Lexer<'a>
@@ -22,6 +29,8 @@ impl<'a> Lexer<'a>
       source: str.chars(),
       state: State::empty(),
       buffer: None,
+      location: Location::default(),
+      lexeme_start: Location::default(),
     }
   }
 
@@ -46,10 +55,15 @@ impl<'a> Iterator for Lexer<'a>
   {
     loop {
       let current = self.consume_buffer_or_next();
+      if let State::Empty(_) = self.state {
+        self.lexeme_start = self.location;
+      }
       let result = self.state.feed(current);
       let consumed_input = result.consumed_input();
-      if !consumed_input {
-        self.buffer = current;
+      match current {
+        | _ if !consumed_input => self.buffer = current,
+        | Some(char) => self.location.advance(char),
+        | None => (),
       }
       match result {
         | FeedableResult::Eof => return None,
@@ -59,7 +73,9 @@ impl<'a> Iterator for Lexer<'a>
           ..
         } => {
           self.state = state;
-          return Some(token)
+          return Some(
+            token.with_span(Span::new(self.lexeme_start, self.location)),
+          )
         },
         | FeedableResult::Transition {
           state,
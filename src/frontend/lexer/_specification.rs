@@ -345,30 +345,62 @@ mod numeric
   fn numerics()
   {
     let mut lexer = Lexer::from_str(
-      "0 1 2 3 4 5 6 7 8 9 1234567890.123456890 1,234,567,890.123,456,789,0 \
+      "0 1 2 3 4 5 6 7 8 9 1234567890.123456890 1_234_567_890.123_456_789_0 \
        1.1.1 10foo foo10 kmh2mph",
     );
-    assert_eq!(lexer.next(), Some(Lexeme::numeric("0")));
-    assert_eq!(lexer.next(), Some(Lexeme::numeric("1")));
-    assert_eq!(lexer.next(), Some(Lexeme::numeric("2")));
-    assert_eq!(lexer.next(), Some(Lexeme::numeric("3")));
-    assert_eq!(lexer.next(), Some(Lexeme::numeric("4")));
-    assert_eq!(lexer.next(), Some(Lexeme::numeric("5")));
-    assert_eq!(lexer.next(), Some(Lexeme::numeric("6")));
-    assert_eq!(lexer.next(), Some(Lexeme::numeric("7")));
-    assert_eq!(lexer.next(), Some(Lexeme::numeric("8")));
-    assert_eq!(lexer.next(), Some(Lexeme::numeric("9")));
-    assert_eq!(lexer.next(), Some(Lexeme::numeric("1234567890.123456890")));
+    assert_eq!(lexer.next(), Some(Lexeme::integer("0")));
+    assert_eq!(lexer.next(), Some(Lexeme::integer("1")));
+    assert_eq!(lexer.next(), Some(Lexeme::integer("2")));
+    assert_eq!(lexer.next(), Some(Lexeme::integer("3")));
+    assert_eq!(lexer.next(), Some(Lexeme::integer("4")));
+    assert_eq!(lexer.next(), Some(Lexeme::integer("5")));
+    assert_eq!(lexer.next(), Some(Lexeme::integer("6")));
+    assert_eq!(lexer.next(), Some(Lexeme::integer("7")));
+    assert_eq!(lexer.next(), Some(Lexeme::integer("8")));
+    assert_eq!(lexer.next(), Some(Lexeme::integer("9")));
+    assert_eq!(lexer.next(), Some(Lexeme::float("1234567890.123456890")));
     assert_eq!(
       lexer.next(),
-      Some(Lexeme::numeric("1,234,567,890.123,456,789,0"))
+      Some(Lexeme::float("1_234_567_890.123_456_789_0"))
     );
     assert_eq!(lexer.next(), Some(Lexeme::malformed_numeric("1.1.1")));
-    assert_eq!(lexer.next(), Some(Lexeme::identifier("10foo")));
+    assert_eq!(lexer.next(), Some(Lexeme::malformed_numeric("10foo")));
     assert_eq!(lexer.next(), Some(Lexeme::identifier("foo10")));
     assert_eq!(lexer.next(), Some(Lexeme::identifier("kmh2mph")));
     assert_eq!(lexer.next(), None);
   }
+
+  #[test]
+  fn prefixed_integers()
+  {
+    let mut lexer = Lexer::from_str("0x2A 0B101 0xfe-1");
+    assert_eq!(lexer.next(), Some(Lexeme::integer("0x2A")));
+    assert_eq!(lexer.next(), Some(Lexeme::integer("0B101")));
+    assert_eq!(lexer.next(), Some(Lexeme::integer("0xfe")));
+    assert_eq!(lexer.next(), Some(Lexeme::operator("-")));
+    assert_eq!(lexer.next(), Some(Lexeme::integer("1")));
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn exponents_can_be_signed()
+  {
+    let mut lexer = Lexer::from_str("1e-3+2E+2");
+    assert_eq!(lexer.next(), Some(Lexeme::float("1e-3")));
+    assert_eq!(lexer.next(), Some(Lexeme::operator("+")));
+    assert_eq!(lexer.next(), Some(Lexeme::float("2E+2")));
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn integers_must_fit_in_64_bits()
+  {
+    let mut lexer = Lexer::from_str("9223372036854775808");
+    assert_eq!(
+      lexer.next(),
+      Some(Lexeme::malformed_numeric("9223372036854775808"))
+    );
+  }
 }
 
 #[cfg(test)]
@@ -437,14 +469,24 @@ mod reserved_words
   fn sym_eq_()
   {
     let mut lexer = Lexer::from_str("=_");
-    assert_eq!(lexer.next(), Some(Lexeme::identifier("=_")));
+    assert_eq!(lexer.next(), Some(Lexeme::keyword("=")));
+    assert_eq!(lexer.next(), Some(Lexeme::identifier("_")));
   }
 
   #[test]
   fn _sym_eq()
   {
     let mut lexer = Lexer::from_str("_=");
-    assert_eq!(lexer.next(), Some(Lexeme::identifier("_=")));
+    assert_eq!(lexer.next(), Some(Lexeme::identifier("_")));
+    assert_eq!(lexer.next(), Some(Lexeme::keyword("=")));
+  }
+
+  #[test]
+  fn sym_eq_eq()
+  {
+    let mut lexer = Lexer::from_str("==");
+    assert_eq!(lexer.next(), Some(Lexeme::operator("==")));
+    assert_eq!(lexer.next(), None);
   }
 
   #[test]
@@ -479,3 +521,159 @@ mod reserved_words
     assert_eq!(lexer.next(), None);
   }
 }
+
+#[cfg(test)]
+mod span
+{
+  use pretty_assertions::assert_eq;
+
+  use super::super::*;
+
+  fn spans(input: &str) -> Vec<(usize, usize, usize, usize)>
+  {
+    Lexer::from_str(input)
+      .map(|lexeme| {
+        let span = lexeme.span();
+        (span.start.offset, span.end.offset, span.start.line, span.start.column)
+      })
+      .collect()
+  }
+
+  #[test]
+  fn symbols()
+  {
+    assert_eq!(spans("( )"), vec![(0, 1, 1, 1), (2, 3, 1, 3)]);
+  }
+
+  #[test]
+  fn identifiers_and_keywords()
+  {
+    assert_eq!(spans("val foo = 10 ;"), vec![
+      (0, 3, 1, 1),
+      (4, 7, 1, 5),
+      (8, 9, 1, 9),
+      (10, 12, 1, 11),
+      (13, 14, 1, 14),
+    ]);
+  }
+
+  #[test]
+  fn string_includes_quotes()
+  {
+    assert_eq!(spans(" `foo` bar"), vec![(1, 6, 1, 2), (7, 10, 1, 8)]);
+  }
+
+  #[test]
+  fn comments_and_newlines_are_skipped()
+  {
+    assert_eq!(spans("(* a\n b *)\n  foo\nbar"), vec![
+      (13, 16, 3, 3),
+      (17, 20, 4, 1),
+    ]);
+  }
+
+  #[test]
+  fn paren_followed_by_identifier()
+  {
+    assert_eq!(spans("(foo)"), vec![(0, 1, 1, 1), (1, 4, 1, 2), (4, 5, 1, 5),]);
+  }
+
+  #[test]
+  fn offsets_are_counted_in_bytes()
+  {
+    assert_eq!(spans("λ x"), vec![(0, 2, 1, 1), (3, 4, 1, 3)]);
+  }
+}
+
+#[cfg(test)]
+mod operators
+{
+  use pretty_assertions::assert_eq;
+
+  use super::super::*;
+
+  #[test]
+  fn operators_are_separate_from_identifiers()
+  {
+    let mut lexer = Lexer::from_str("a+b");
+    assert_eq!(lexer.next(), Some(Lexeme::identifier("a")));
+    assert_eq!(lexer.next(), Some(Lexeme::operator("+")));
+    assert_eq!(lexer.next(), Some(Lexeme::identifier("b")));
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn symbols_form_a_single_operator()
+  {
+    let mut lexer = Lexer::from_str("x :: xs |> f <= 10");
+    assert_eq!(lexer.next(), Some(Lexeme::identifier("x")));
+    assert_eq!(lexer.next(), Some(Lexeme::operator("::")));
+    assert_eq!(lexer.next(), Some(Lexeme::identifier("xs")));
+    assert_eq!(lexer.next(), Some(Lexeme::operator("|>")));
+    assert_eq!(lexer.next(), Some(Lexeme::identifier("f")));
+    assert_eq!(lexer.next(), Some(Lexeme::operator("<=")));
+    assert_eq!(lexer.next(), Some(Lexeme::integer("10")));
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn dots_continue_operators()
+  {
+    let mut lexer = Lexer::from_str("x+.1.5*.y");
+    assert_eq!(lexer.next(), Some(Lexeme::identifier("x")));
+    assert_eq!(lexer.next(), Some(Lexeme::operator("+.")));
+    assert_eq!(lexer.next(), Some(Lexeme::float("1.5")));
+    assert_eq!(lexer.next(), Some(Lexeme::operator("*.")));
+    assert_eq!(lexer.next(), Some(Lexeme::identifier("y")));
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn reserved_symbols_are_keywords()
+  {
+    let mut lexer = Lexer::from_str("x->y:T");
+    assert_eq!(lexer.next(), Some(Lexeme::identifier("x")));
+    assert_eq!(lexer.next(), Some(Lexeme::keyword("->")));
+    assert_eq!(lexer.next(), Some(Lexeme::identifier("y")));
+    assert_eq!(lexer.next(), Some(Lexeme::keyword(":")));
+    assert_eq!(lexer.next(), Some(Lexeme::identifier("T")));
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn operators_do_not_split_numerics()
+  {
+    let mut lexer = Lexer::from_str("-3.14");
+    assert_eq!(lexer.next(), Some(Lexeme::operator("-")));
+    assert_eq!(lexer.next(), Some(Lexeme::float("3.14")));
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn commas_and_dots_are_symbols()
+  {
+    let mut lexer = Lexer::from_str("(a,b).0");
+    assert_eq!(lexer.next(), Some(Lexeme::symbol("(")));
+    assert_eq!(lexer.next(), Some(Lexeme::identifier("a")));
+    assert_eq!(lexer.next(), Some(Lexeme::symbol(",")));
+    assert_eq!(lexer.next(), Some(Lexeme::identifier("b")));
+    assert_eq!(lexer.next(), Some(Lexeme::symbol(")")));
+    assert_eq!(lexer.next(), Some(Lexeme::symbol(".")));
+    assert_eq!(lexer.next(), Some(Lexeme::integer("0")));
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn semicolons_end_identifiers()
+  {
+    let mut lexer = Lexer::from_str("{a=b;c}");
+    assert_eq!(lexer.next(), Some(Lexeme::symbol("{")));
+    assert_eq!(lexer.next(), Some(Lexeme::identifier("a")));
+    assert_eq!(lexer.next(), Some(Lexeme::keyword("=")));
+    assert_eq!(lexer.next(), Some(Lexeme::identifier("b")));
+    assert_eq!(lexer.next(), Some(Lexeme::keyword(";")));
+    assert_eq!(lexer.next(), Some(Lexeme::identifier("c")));
+    assert_eq!(lexer.next(), Some(Lexeme::symbol("}")));
+    assert_eq!(lexer.next(), None);
+  }
+}
@@ -2,6 +2,8 @@ mod comment;
 mod comment_or_paren;
 mod empty;
 mod identifier;
+mod numeric;
+mod operator;
 mod string_literal;
 mod whitespace;
 
@@ -9,6 +11,9 @@ use self::comment::Comment;
 use self::comment_or_paren::CommentOrParen;
 use self::empty::Empty;
 use self::identifier::Identifier;
+pub use self::numeric::parse_numeric;
+use self::numeric::Numeric;
+use self::operator::Operator;
 use self::string_literal::StringLiteral;
 use self::whitespace::Whitespace;
 use super::feedable::Feedable;
@@ -20,6 +25,8 @@ pub enum State
   Empty(Empty),
   CommentOrParen(CommentOrParen),
   Identifier(Identifier),
+  Numeric(Numeric),
+  Operator(Operator),
   StringLiteral(StringLiteral),
   Comment(Comment),
   Whitespace(Whitespace),
@@ -37,6 +44,8 @@ impl State
       | Self::CommentOrParen(state) => state.feed(char),
       | Self::Comment(state) => state.feed(char),
       | Self::Identifier(state) => state.feed(char),
+      | Self::Numeric(state) => state.feed(char),
+      | Self::Operator(state) => state.feed(char),
       | Self::StringLiteral(state) => state.feed(char),
       | Self::Whitespace(state) => state.feed(char),
     }
@@ -59,6 +68,20 @@ impl State
     })
   }
 
+  pub fn numeric() -> Self
+  {
+    Self::Numeric(Numeric {
+      buffer: vec![],
+    })
+  }
+
+  pub fn operator() -> Self
+  {
+    Self::Operator(Operator {
+      buffer: vec![],
+    })
+  }
+
   pub fn string_literal() -> Self
   {
     Self::StringLiteral(StringLiteral::default())
@@ -1,3 +1,4 @@
+use super::operator::is_operator_char;
 use super::State;
 use crate::frontend::lexemes::Lexeme;
 use crate::frontend::lexer::feedable::Feedable;
@@ -43,6 +44,21 @@ impl Feedable for Empty
         token: Lexeme::symbol("]"),
         consumed: true,
       },
+      | Some(',') => FeedableResult::Finished {
+        state: State::empty(),
+        token: Lexeme::symbol(","),
+        consumed: true,
+      },
+      | Some('.') => FeedableResult::Finished {
+        state: State::empty(),
+        token: Lexeme::symbol("."),
+        consumed: true,
+      },
+      | Some(';') => FeedableResult::Finished {
+        state: State::empty(),
+        token: Lexeme::keyword(";"),
+        consumed: true,
+      },
       | Some(' ' | '\t' | '\n' | '\r') => FeedableResult::Transition {
         state: State::whitespace(),
         consumed: false,
@@ -51,6 +67,14 @@ impl Feedable for Empty
         state: State::string_literal(),
         consumed: true,
       },
+      | Some(c) if c.is_ascii_digit() => FeedableResult::Transition {
+        state: State::numeric(),
+        consumed: false,
+      },
+      | Some(c) if is_operator_char(c) => FeedableResult::Transition {
+        state: State::operator(),
+        consumed: false,
+      },
       | Some(_) => FeedableResult::Transition {
         state: State::identifier(),
         consumed: false,
@@ -0,0 +1,130 @@
+use super::State;
+use crate::frontend::lexemes::Lexeme;
+use crate::frontend::lexer::feedable::Feedable;
+use crate::frontend::lexer::feedable_result::FeedableResult;
+use crate::syntax::surface::Literal;
+
+/// A literal starting with a digit, such as `42`, `0x2A`, `0b10_1010` or
+/// `4.2e1`.
+#[derive(Debug, PartialEq)]
+pub struct Numeric
+{
+  pub buffer: Vec<char>,
+}
+
+impl Numeric
+{
+  fn token(&self) -> Lexeme
+  {
+    let value = String::from_iter(self.buffer.iter());
+    match parse_numeric(&value) {
+      | Some(Literal::Integer(_)) => Lexeme::integer(value),
+      | Some(_) => Lexeme::float(value),
+      | None => Lexeme::malformed_numeric(value),
+    }
+  }
+
+  /// Whether a sign can follow, as in `1e-3`.
+  fn is_exponent(&self) -> bool
+  {
+    let is_prefixed =
+      matches!(self.buffer.as_slice(), ['0', 'x' | 'X' | 'b' | 'B', ..]);
+    !is_prefixed && matches!(self.buffer.last(), Some('e' | 'E'))
+  }
+}
+
+impl Feedable for Numeric
+{
+  fn feed(
+    &mut self,
+    char: Option<char>,
+  ) -> FeedableResult
+  {
+    match char {
+      | Some(c) if c.is_ascii_alphanumeric() || c == '_' || c == '.' => {
+        self.buffer.push(c);
+        FeedableResult::Continue
+      },
+      | Some(c @ ('+' | '-')) if self.is_exponent() => {
+        self.buffer.push(c);
+        FeedableResult::Continue
+      },
+      | _ => FeedableResult::Finished {
+        state: State::empty(),
+        token: self.token(),
+        consumed: false,
+      },
+    }
+  }
+}
+
+/// Reads an integer, in decimal, hexadecimal after `0x` or binary after
+/// `0b`, or a decimal float. Digits can be separated by underscores, and
+/// integers that don't fit in 64 bits, or floats too large for them, are
+/// rejected.
+pub fn parse_numeric(value: &str) -> Option<Literal>
+{
+  let (radix, digits) = match value.get(.. 2) {
+    | Some("0x" | "0X") => (16, &value[2 ..]),
+    | Some("0b" | "0B") => (2, &value[2 ..]),
+    | _ => (10, value),
+  };
+  if !digits.starts_with(|c: char| c.is_digit(radix)) {
+    return None
+  }
+  let digits = digits.replace('_', "");
+  match radix {
+    | 10 if digits.contains(['.', 'e', 'E']) => digits
+      .parse()
+      .ok()
+      .filter(|float: &f64| float.is_finite())
+      .map(Literal::Float),
+    | _ => i64::from_str_radix(&digits, radix)
+      .ok()
+      .map(Literal::Integer),
+  }
+}
+
+#[cfg(test)]
+mod spec
+{
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn integers()
+  {
+    assert_eq!(parse_numeric("42"), Some(Literal::Integer(42)));
+    assert_eq!(parse_numeric("1_000_000"), Some(Literal::Integer(1_000_000)));
+    assert_eq!(parse_numeric("0x2A"), Some(Literal::Integer(42)));
+    assert_eq!(parse_numeric("0b10_1010"), Some(Literal::Integer(42)));
+    assert_eq!(
+      parse_numeric("9223372036854775807"),
+      Some(Literal::Integer(i64::MAX))
+    );
+  }
+
+  #[test]
+  fn floats()
+  {
+    assert_eq!(parse_numeric("4.2"), Some(Literal::Float(4.2)));
+    assert_eq!(parse_numeric("42e-1"), Some(Literal::Float(4.2)));
+    assert_eq!(parse_numeric("1_000.5E3"), Some(Literal::Float(1_000_500.0)));
+    assert_eq!(parse_numeric("1.0e-999"), Some(Literal::Float(0.0)));
+  }
+
+  #[test]
+  fn malformed()
+  {
+    assert_eq!(parse_numeric("9223372036854775808"), None);
+    assert_eq!(parse_numeric("1.1.1"), None);
+    assert_eq!(parse_numeric("10foo"), None);
+    assert_eq!(parse_numeric("0x"), None);
+    assert_eq!(parse_numeric("0x_1"), None);
+    assert_eq!(parse_numeric("0b102"), None);
+    assert_eq!(parse_numeric("0x1.5"), None);
+    assert_eq!(parse_numeric("1e"), None);
+    assert_eq!(parse_numeric("1.0e999"), None);
+  }
+}
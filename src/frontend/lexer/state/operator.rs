@@ -0,0 +1,71 @@
+use super::identifier::RESERVED_WORDS;
+use super::State;
+use crate::frontend::lexemes::Lexeme;
+use crate::frontend::lexer::feedable::Feedable;
+use crate::frontend::lexer::feedable_result::FeedableResult;
+
+/// A run of symbol characters such as `+`, `::` or `|>`, unless it is
+/// reserved like `->`. Dots can follow the first character, as in `+.`.
+#[derive(Debug, PartialEq)]
+pub struct Operator
+{
+  pub buffer: Vec<char>,
+}
+
+pub fn is_operator_char(char: char) -> bool
+{
+  matches!(
+    char,
+    '!'
+      | '#'
+      | '$'
+      | '%'
+      | '&'
+      | '*'
+      | '+'
+      | '-'
+      | '/'
+      | ':'
+      | '<'
+      | '='
+      | '>'
+      | '?'
+      | '@'
+      | '^'
+      | '|'
+      | '~'
+  )
+}
+
+impl Operator
+{
+  fn token(&self) -> Lexeme
+  {
+    let value = String::from_iter(self.buffer.iter());
+    RESERVED_WORDS
+      .iter()
+      .find(|&&word| word == value)
+      .map_or_else(|| Lexeme::operator(value), |&word| Lexeme::keyword(word))
+  }
+}
+
+impl Feedable for Operator
+{
+  fn feed(
+    &mut self,
+    char: Option<char>,
+  ) -> FeedableResult
+  {
+    match char {
+      | Some(c) if is_operator_char(c) || c == '.' => {
+        self.buffer.push(c);
+        FeedableResult::Continue
+      },
+      | _ => FeedableResult::Finished {
+        state: State::empty(),
+        token: self.token(),
+        consumed: false,
+      },
+    }
+  }
+}
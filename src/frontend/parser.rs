@@ -4,9 +4,12 @@ mod can_backtrack;
 mod declaration_parser;
 mod expect_syntax;
 mod expression_parser;
+mod fixity;
 mod identifier_parser;
 mod node_type;
 mod parse_error;
+mod pattern_parser;
+mod type_parser;
 mod with_backtracking;
 
 pub use backtracking_iterator::*;
@@ -14,9 +17,12 @@ pub use can_backtrack::*;
 pub use declaration_parser::*;
 pub use expect_syntax::*;
 pub use expression_parser::*;
+pub use fixity::*;
 pub use identifier_parser::*;
 pub use node_type::*;
 pub use parse_error::*;
+pub use pattern_parser::*;
+pub use type_parser::*;
 pub use with_backtracking::*;
 
 use super::lexemes::Lexeme;
@@ -1,5 +1,7 @@
 use super::{
   CanBacktrack,
+  Fixities,
+  ParseError,
   Result,
 };
 use crate::frontend::lexemes::Lexeme;
@@ -11,6 +13,39 @@ where
   lexer: Lexer,
   buffer: Vec<Lexeme>,
   cursor: usize,
+  /// How many breakpoints the parse in progress is nested in.
+  depth: usize,
+  /// The backtracked failure that got furthest into the input.
+  furthest: Option<Failure>,
+  pub(super) fixities: Fixities,
+}
+
+/// A backtracked failure, along with what decides between failures at the
+/// same token.
+struct Failure
+{
+  error: ParseError,
+  /// Whether the parse got past its first lexeme, one that fails right away
+  /// only tells that it did not apply.
+  committed: bool,
+  /// How many breakpoints the parse was nested in.
+  depth: usize,
+}
+
+impl Failure
+{
+  /// Whether this failure explains the input better than `other`, it got
+  /// further, or it failed at the same token in the innermost construct that
+  /// was under way.
+  fn is_better_than(
+    &self,
+    other: &Failure,
+  ) -> bool
+  {
+    self.error.is_further_than(&other.error)
+      || !other.error.is_further_than(&self.error)
+        && (self.committed, self.depth) > (other.committed, other.depth)
+  }
 }
 
 impl<Lexer> BacktrackingIterator<Lexer>
@@ -23,6 +58,21 @@ where
       lexer,
       buffer: Vec::new(),
       cursor: 0,
+      depth: 0,
+      furthest: None,
+      fixities: Fixities::default(),
+    }
+  }
+
+  /// Parses with the operators declared by earlier input.
+  pub fn with_fixities(
+    self,
+    fixities: Fixities,
+  ) -> Self
+  {
+    Self {
+      fixities,
+      ..self
     }
   }
 }
@@ -35,11 +85,7 @@ where
 
   fn next(&mut self) -> Option<Self::Item>
   {
-    if self.cursor >= self.buffer.len() {
-      let lexeme = self.lexer.next()?;
-      self.buffer.push(lexeme);
-    }
-    let next = self.buffer.get(self.cursor).cloned()?;
+    let next = self.peek()?;
     self.cursor += 1;
     Some(next)
   }
@@ -55,12 +101,54 @@ where
   ) -> Result<T>
   {
     let cursor = self.cursor;
+    let start = self
+      .peek()
+      .map(|lexeme| lexeme.span().end);
+    self.depth += 1;
     let result = computation(self);
-    if result.is_err() {
+    self.depth -= 1;
+    if let Err(error) = &result {
       self.cursor = cursor;
+      let failure = Failure {
+        committed: error.reached() != start,
+        error: error.clone(),
+        depth: self.depth,
+      };
+      if self
+        .furthest
+        .as_ref()
+        .map_or(true, |furthest| failure.is_better_than(furthest))
+      {
+        self.furthest = Some(failure);
+      }
     }
     result
   }
+
+  fn peek(&mut self) -> Option<Lexeme>
+  {
+    if self.cursor >= self.buffer.len() {
+      let lexeme = self.lexer.next()?;
+      self.buffer.push(lexeme);
+    }
+    self.buffer.get(self.cursor).cloned()
+  }
+
+  fn furthest_failure(
+    &self,
+    error: ParseError,
+  ) -> ParseError
+  {
+    // an error that makes it out of every breakpoint comes from the outermost
+    // parse, any committed failure at the same token is more precise
+    match &self.furthest {
+      | Some(furthest)
+        if furthest.error.is_further_than(&error)
+          || furthest.committed && !error.is_further_than(&furthest.error) =>
+        furthest.error.clone(),
+      | _ => error,
+    }
+  }
 }
 
 
@@ -78,6 +166,18 @@ mod spec
   };
   use crate::frontend::tokens::Token;
 
+  #[test]
+  fn peek_does_not_consume_tokens()
+  {
+    let mut lexer = Lexer::from_str("val foo").with_backtracking();
+    assert_eq!(lexer.peek(), Some(Lexeme::keyword("val")));
+    assert_eq!(lexer.peek(), Some(Lexeme::keyword("val")));
+    assert_eq!(lexer.next(), Some(Lexeme::keyword("val")));
+    assert_eq!(lexer.peek(), Some(Lexeme::identifier("foo")));
+    assert_eq!(lexer.next(), Some(Lexeme::identifier("foo")));
+    assert_eq!(lexer.peek(), None);
+  }
+
   #[test]
   fn success_in_guarded_parse_consumes_tokens()
   {
@@ -1,4 +1,8 @@
-use super::Result;
+use super::{
+  ParseError,
+  Result,
+};
+use crate::frontend::lexemes::Lexeme;
 
 pub trait CanBacktrack
 {
@@ -6,6 +10,19 @@ pub trait CanBacktrack
     &mut self,
     computation: impl FnOnce(&mut Self) -> Result<T>,
   ) -> Result<T>;
+
+  /// Returns the next lexeme without consuming it.
+  fn peek(&mut self) -> Option<Lexeme>;
+
+  /// The failure of a backtracked parse that got further into the input than
+  /// `error`, or as far while under way, if any. Failed alternatives are
+  /// backtracked over, so the error
+  /// that makes it out of them otherwise points at the start of the construct
+  /// they were nested in rather than at the offending token.
+  fn furthest_failure(
+    &self,
+    error: ParseError,
+  ) -> ParseError;
 }
 
 /// Attempts a parse with backtrack breakpoint and returns immediately if
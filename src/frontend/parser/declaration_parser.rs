@@ -4,18 +4,219 @@ pub trait DeclarationParser
 where
   Self: Iterator<Item = Lexeme>,
   Self: CanBacktrack,
+  Self: HasFixities,
   Self: ExpressionParser,
 {
   fn expect_val_binding(&mut self) -> Result<surface::ValBinding>
   {
-    let _ = self.expect(Token::Keyword("val"))?;
-    let name = self.expect_identifier()?;
-    let _ = self.expect(Token::Keyword("="))?;
-    let value = self.expect_expression()?;
-    let _ = self.expect(Token::Keyword(";"))?;
+    let val = self.expect(Token::Keyword("val"))?;
+    let surface::Binding {
+      name,
+      value,
+    } = self.expect_binding()?;
+    let semicolon = self.expect(Token::Keyword(";"))?;
     Ok(surface::ValBinding {
       name,
       value,
+      span: val.span().merge(semicolon.span()),
+    })
+  }
+
+  fn expect_def_binding(&mut self) -> Result<surface::DefBinding>
+  {
+    let def = self.expect(Token::Keyword("def"))?;
+    let functions = self.expect_functions()?;
+    let semicolon = self.expect(Token::Keyword(";"))?;
+    Ok(surface::DefBinding {
+      functions,
+      span: def.span().merge(semicolon.span()),
+    })
+  }
+
+  /// Parses `type 'a option = None | Some of 'a ;`, several parameters are
+  /// written between parentheses as in `type ('k, 'v) map = ...`.
+  fn expect_type_declaration(&mut self) -> Result<surface::TypeDeclaration>
+  {
+    let keyword = self.expect(Token::Keyword("type"))?;
+    let parameters = self
+      .breakpoint(|s| {
+        let _ = s.expect(Token::Symbol("("))?;
+        let mut parameters = vec![s.expect_type_variable()?];
+        while s
+          .breakpoint(|s| s.expect(Token::Symbol(",")))
+          .is_ok()
+        {
+          parameters.push(s.expect_type_variable()?);
+        }
+        let _ = s.expect(Token::Symbol(")"))?;
+        Ok(parameters)
+      })
+      .or_else(|_| self.breakpoint(|s| Ok(vec![s.expect_type_variable()?])))
+      .unwrap_or_default();
+    let name = self.expect_type_name()?;
+    let _ = self.expect(Token::Keyword("="))?;
+    let _ = self.breakpoint(|s| s.expect(Token::Keyword("|")));
+    let mut constructors = vec![self.expect_constructor()?];
+    while self
+      .breakpoint(|s| s.expect(Token::Keyword("|")))
+      .is_ok()
+    {
+      constructors.push(self.expect_constructor()?);
+    }
+    let semicolon = self.expect(Token::Keyword(";"))?;
+    Ok(surface::TypeDeclaration {
+      name,
+      parameters,
+      constructors,
+      span: keyword.span().merge(semicolon.span()),
+    })
+  }
+
+  /// Parses `None` or `Some of 'a`.
+  fn expect_constructor(&mut self) -> Result<surface::Constructor>
+  {
+    let name = self.expect_constructor_name()?;
+    let argument = self
+      .breakpoint(|s| {
+        let _ = s.expect(Token::Keyword("of"))?;
+        s.expect_type()
+      })
+      .ok();
+    Ok(surface::Constructor {
+      name,
+      argument,
+    })
+  }
+
+  /// Parses `structure Foo = struct ... end ;`, between `struct` and `end`
+  /// come declarations as in a program.
+  fn expect_structure(&mut self) -> Result<surface::Structure>
+  {
+    let keyword = self.expect(Token::Keyword("structure"))?;
+    let name = self.expect_structure_name()?;
+    let _ = self.expect(Token::Keyword("="))?;
+    let _ = self.expect(Token::Keyword("struct"))?;
+    let mut declarations = vec![];
+    while self
+      .breakpoint(|s| s.expect(Token::Keyword("end")))
+      .is_err()
+    {
+      match self.peek() {
+        | Some(lexeme)
+          if matches!(
+            lexeme.token(),
+            Token::Keyword("infixl" | "infixr" | "infix")
+          ) =>
+        {
+          self.expect_fixity_declaration()?;
+        },
+        | _ => declarations.push(self.expect_top_level()?),
+      }
+    }
+    let semicolon = self.expect(Token::Keyword(";"))?;
+    Ok(surface::Structure {
+      name,
+      declarations,
+      span: keyword.span().merge(semicolon.span()),
+    })
+  }
+
+  /// Parses `open Foo.Bar ;`.
+  fn expect_open(&mut self) -> Result<surface::Open>
+  {
+    let keyword = self.expect(Token::Keyword("open"))?;
+    let path = self.expect_structure_path()?;
+    let semicolon = self.expect(Token::Keyword(";"))?;
+    Ok(surface::Open {
+      path,
+      span: keyword.span().merge(semicolon.span()),
+    })
+  }
+
+  fn expect_top_level(&mut self) -> Result<surface::TopLevel>
+  {
+    let top_level = match self.peek() {
+      | Some(lexeme) if lexeme.token() == &Token::Keyword("val") => self
+        .expect_val_binding()
+        .map(Into::into),
+      | Some(lexeme) if lexeme.token() == &Token::Keyword("def") => self
+        .expect_def_binding()
+        .map(Into::into),
+      | Some(lexeme) if lexeme.token() == &Token::Keyword("type") => self
+        .expect_type_declaration()
+        .map(Into::into),
+      | Some(lexeme) if lexeme.token() == &Token::Keyword("structure") =>
+        self.expect_structure().map(Into::into),
+      | Some(lexeme) if lexeme.token() == &Token::Keyword("open") =>
+        self.expect_open().map(Into::into),
+      | actual => Err(ParseError::Expected {
+        expected: NodeType::Declaration,
+        actual,
+      }),
+    };
+    top_level.map_err(|error| self.furthest_failure(error))
+  }
+
+  /// Parses `infixl 6 + -` and uses the fixity for the rest of the input, it
+  /// may end with `;` like other declarations.
+  fn expect_fixity_declaration(&mut self) -> Result<FixityDeclaration>
+  {
+    let keyword = self.next();
+    let fixity: fn(u8) -> Fixity = match keyword.as_ref().map(Lexeme::token) {
+      | Some(Token::Keyword("infixl")) => Fixity::left,
+      | Some(Token::Keyword("infixr")) => Fixity::right,
+      | Some(Token::Keyword("infix")) => Fixity::none,
+      | _ =>
+        return Err(ParseError::Expected {
+          expected: NodeType::Declaration,
+          actual: keyword,
+        }),
+    };
+    let precedence = self.next();
+    let fixity = match &precedence {
+      | Some(lexeme) if lexeme.token() == &Token::IntegerLiteral => lexeme
+        .value()
+        .parse()
+        .ok()
+        .filter(|&precedence| precedence <= Fixity::MAXIMUM_PRECEDENCE)
+        .map(fixity),
+      | _ => None,
+    }
+    .ok_or(ParseError::Expected {
+      expected: NodeType::Precedence,
+      actual: precedence,
+    })?;
+    let mut operators = vec![self.expect_operator()?.name];
+    while let Ok(operator) = self.breakpoint(|s| s.expect_operator()) {
+      operators.push(operator.name);
+    }
+    let _ = self.breakpoint(|s| s.expect(Token::Keyword(";")));
+    let declaration = FixityDeclaration {
+      fixity,
+      operators,
+    };
+    self
+      .fixities_mut()
+      .declare(&declaration);
+    Ok(declaration)
+  }
+
+  /// Parses declarations until the input is exhausted, fixity declarations
+  /// only affect how the rest of the input is parsed.
+  fn expect_program(&mut self) -> Result<surface::Program>
+  {
+    let mut declarations = vec![];
+    while let Some(lexeme) = self.peek() {
+      match lexeme.token() {
+        | Token::Keyword("infixl" | "infixr" | "infix") => {
+          self.expect_fixity_declaration()?;
+        },
+        | _ => declarations.push(self.expect_top_level()?),
+      }
+    }
+    self.expect_end_of_input()?;
+    Ok(surface::Program {
+      declarations,
     })
   }
 }
@@ -23,6 +224,7 @@ impl<Lexer> DeclarationParser for Lexer
 where
   Self: Iterator<Item = Lexeme>,
   Self: CanBacktrack,
+  Self: HasFixities,
   Self: ExpressionParser,
 {
 }
@@ -45,6 +247,7 @@ mod tests
       Ok(surface::ValBinding {
         name: surface::Identifier::new("foo"),
         value: surface::Literal::String("bar".into()).into(),
+        span: Default::default(),
       })
     );
     assert_eq!(lexer.next(), None);
@@ -58,7 +261,8 @@ mod tests
       lexer.expect_val_binding(),
       Ok(surface::ValBinding {
         name: surface::Identifier::new("foo"),
-        value: surface::Literal::Numeric("10".into()).into(),
+        value: surface::Literal::Integer(10).into(),
+        span: Default::default(),
       })
     );
     assert_eq!(lexer.next(), None);
@@ -73,6 +277,7 @@ mod tests
       Ok(surface::ValBinding {
         name: surface::Identifier::new("foo"),
         value: surface::Literal::Boolean(true).into(),
+        span: Default::default(),
       })
     );
     assert_eq!(lexer.next(), None);
@@ -87,12 +292,328 @@ mod tests
       Ok(surface::ValBinding {
         name: surface::Identifier::new("f"),
         value: surface::Abstraction {
-          parameters: vec![surface::Identifier::new("x")],
+          parameters: vec![surface::Identifier::new("x").into()],
           body: surface::Identifier::new("x").into(),
+          span: Default::default(),
+        }
+        .into(),
+        span: Default::default(),
+      })
+    );
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn val_binding_spans_keyword_to_semicolon()
+  {
+    let mut lexer = Lexer::from_str("val foo =\n  10 ;").with_backtracking();
+    let binding = lexer.expect_val_binding().unwrap();
+    assert_eq!((binding.span.start.offset, binding.span.end.offset), (0, 16));
+    assert_eq!(
+      (binding.name.span.start.offset, binding.name.span.end.offset),
+      (4, 7)
+    );
+    assert_eq!(
+      (binding.value.span().start.line, binding.value.span().start.column),
+      (2, 3)
+    );
+  }
+
+  #[test]
+  fn val_binding_with_annotation()
+  {
+    let mut lexer =
+      Lexer::from_str("val f : Int -> Int = fun x -> x ;").with_backtracking();
+    let numeric: surface::types::Type = surface::Identifier::new("Int").into();
+    assert_eq!(
+      lexer.expect_val_binding(),
+      Ok(surface::ValBinding {
+        name: surface::Identifier::new("f"),
+        value: surface::Annotation {
+          expression: surface::Abstraction {
+            parameters: vec![surface::Identifier::new("x").into()],
+            body: surface::Identifier::new("x").into(),
+            span: Default::default(),
+          }
+          .into(),
+          annotation: surface::types::Type::abstraction(
+            numeric.clone(),
+            numeric
+          ),
+          span: Default::default(),
         }
         .into(),
+        span: Default::default(),
+      })
+    );
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn def_binding()
+  {
+    let mut lexer = Lexer::from_str("def even n = odd n and odd n = even n ;")
+      .with_backtracking();
+    let function = |name: &str, other: &str| surface::Function {
+      name: surface::Identifier::new(name),
+      abstraction: surface::Abstraction {
+        parameters: vec![surface::Identifier::new("n").into()],
+        body: surface::Application {
+          abstraction: surface::Identifier::new(other).into(),
+          arguments: vec![surface::Identifier::new("n").into()],
+          span: Default::default(),
+        }
+        .into(),
+        span: Default::default(),
+      },
+    };
+    let binding = lexer.expect_top_level().unwrap();
+    assert_eq!(
+      binding,
+      surface::DefBinding {
+        functions: vec![function("even", "odd"), function("odd", "even")],
+        span: Default::default(),
+      }
+      .into()
+    );
+    assert_eq!(binding.names(), vec![
+      surface::Identifier::new("even"),
+      surface::Identifier::new("odd")
+    ]);
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn program_of_declarations()
+  {
+    let mut lexer =
+      Lexer::from_str("val x = 10 ;\nval y = x ;\n").with_backtracking();
+    assert_eq!(
+      lexer.expect_program(),
+      Ok(surface::Program {
+        declarations: vec![
+          surface::ValBinding {
+            name: surface::Identifier::new("x"),
+            value: surface::Literal::Integer(10).into(),
+            span: Default::default(),
+          }
+          .into(),
+          surface::ValBinding {
+            name: surface::Identifier::new("y"),
+            value: surface::Identifier::new("x").into(),
+            span: Default::default(),
+          }
+          .into(),
+        ],
+      })
+    );
+  }
+
+  #[test]
+  fn empty_program()
+  {
+    let mut lexer = Lexer::from_str("  (* nothing *) ").with_backtracking();
+    assert_eq!(lexer.expect_program(), Ok(surface::Program::default()));
+  }
+
+  #[test]
+  fn program_with_trailing_garbage()
+  {
+    let mut lexer = Lexer::from_str("val x = 10 ;\n)").with_backtracking();
+    assert_eq!(
+      lexer.expect_program(),
+      Err(ParseError::Expected {
+        expected: NodeType::Declaration,
+        actual: Some(Lexeme::symbol(")")),
+      })
+    );
+  }
+
+  #[test]
+  fn operators_can_be_bound()
+  {
+    let mut lexer = Lexer::from_str("val (<>) = fun x y -> not (x = y) ;")
+      .with_backtracking();
+    let binding = lexer.expect_val_binding().unwrap();
+    assert_eq!(binding.name, surface::Identifier::new("<>"));
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn fixity_declarations_apply_to_later_declarations()
+  {
+    let mut lexer = Lexer::from_str("infixr 2 <> ==\nval x = a <> b <> c ;")
+      .with_backtracking();
+    let operation = |left: surface::Expression, right: surface::Expression| {
+      surface::Application {
+        abstraction: surface::Identifier::new("<>").into(),
+        arguments: vec![left, right],
+        span: Default::default(),
+      }
+      .into()
+    };
+    let name = |name: &str| surface::Identifier::new(name).into();
+    assert_eq!(
+      lexer.expect_program(),
+      Ok(surface::Program {
+        declarations: vec![surface::ValBinding {
+          name: surface::Identifier::new("x"),
+          value: operation(name("a"), operation(name("b"), name("c"))),
+          span: Default::default(),
+        }
+        .into()],
+      })
+    );
+    assert_eq!(lexer.fixities().get("=="), Fixity::right(2));
+  }
+
+  #[test]
+  fn fixity_declarations_may_end_with_a_semicolon()
+  {
+    let mut lexer =
+      Lexer::from_str("infixl 6 +++ ;\ninfixr 2 <> ;").with_backtracking();
+    assert_eq!(
+      lexer.expect_program(),
+      Ok(surface::Program {
+        declarations: vec![],
+      })
+    );
+    assert_eq!(lexer.fixities().get("+++"), Fixity::left(6));
+    assert_eq!(lexer.fixities().get("<>"), Fixity::right(2));
+  }
+
+  #[test]
+  fn precedence_must_be_a_digit()
+  {
+    let mut lexer = Lexer::from_str("infixl 10 <>").with_backtracking();
+    assert_eq!(
+      lexer.expect_program(),
+      Err(ParseError::Expected {
+        expected: NodeType::Precedence,
+        actual: Some(Lexeme::integer("10")),
       })
     );
+  }
+
+  #[test]
+  fn type_declarations_list_their_constructors()
+  {
+    let mut lexer = Lexer::from_str("type 'a option = | None | Some of 'a ;")
+      .with_backtracking();
+    assert_eq!(
+      lexer.expect_top_level(),
+      Ok(
+        surface::TypeDeclaration {
+          name: surface::Identifier::new("option"),
+          parameters: vec![surface::Identifier::new("a")],
+          constructors: vec![
+            surface::Constructor {
+              name: surface::Identifier::new("None"),
+              argument: None,
+            },
+            surface::Constructor {
+              name: surface::Identifier::new("Some"),
+              argument: Some(
+                surface::types::Variable::Named(surface::Identifier::new("a"))
+                  .into()
+              ),
+            },
+          ],
+          span: Default::default(),
+        }
+        .into()
+      )
+    );
     assert_eq!(lexer.next(), None);
   }
+
+  #[test]
+  fn type_declarations_with_several_parameters()
+  {
+    let mut lexer = Lexer::from_str("type ('a, 'b) pair = Pair of 'a * 'b ;")
+      .with_backtracking();
+    let declaration = lexer.expect_type_declaration().unwrap();
+    assert_eq!(declaration.parameters, vec![
+      surface::Identifier::new("a"),
+      surface::Identifier::new("b")
+    ]);
+    assert_eq!(
+      declaration
+        .typ(declaration.name.clone().into())
+        .to_string(),
+      "('a, 'b) pair"
+    );
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn constructors_are_capitalised()
+  {
+    let mut lexer = Lexer::from_str("type t = a ;").with_backtracking();
+    assert_eq!(
+      lexer.expect_program(),
+      Err(ParseError::Expected {
+        expected: NodeType::Constructor,
+        actual: Some(Lexeme::identifier("a")),
+      })
+    );
+  }
+
+  #[test]
+  fn structures_contain_declarations()
+  {
+    let mut lexer = Lexer::from_str(
+      "structure A = struct val x = 1 ; structure B = struct end ; end ; open \
+       A.B ;",
+    )
+    .with_backtracking();
+    assert_eq!(
+      lexer.expect_program(),
+      Ok(surface::Program {
+        declarations: vec![
+          surface::Structure {
+            name: surface::Identifier::new("A"),
+            declarations: vec![
+              surface::ValBinding {
+                name: surface::Identifier::new("x"),
+                value: surface::Literal::Integer(1).into(),
+                span: Default::default(),
+              }
+              .into(),
+              surface::Structure {
+                name: surface::Identifier::new("B"),
+                declarations: vec![],
+                span: Default::default(),
+              }
+              .into(),
+            ],
+            span: Default::default(),
+          }
+          .into(),
+          surface::Open {
+            path: vec![
+              surface::Identifier::new("A"),
+              surface::Identifier::new("B")
+            ],
+            span: Default::default(),
+          }
+          .into(),
+        ],
+      })
+    );
+  }
+
+  #[test]
+  fn structures_are_capitalised()
+  {
+    let mut lexer =
+      Lexer::from_str("structure a = struct end ;").with_backtracking();
+    assert_eq!(
+      lexer.expect_program(),
+      Err(ParseError::Expected {
+        expected: NodeType::Structure,
+        actual: Some(Lexeme::identifier("a")),
+      })
+    );
+  }
 }
@@ -27,8 +27,48 @@ where
         }),
       })
   }
+
+  fn expect_end_of_input(&mut self) -> Result<()>
+  {
+    match self.next() {
+      | None => Ok(()),
+      | Some(actual) => Err(ParseError::ExpectedEndOfInput {
+        actual,
+      }),
+    }
+  }
 }
 
 impl<Lexer> ExpectSyntax for Lexer where Lexer: Iterator<Item = Lexeme>
 {
 }
+
+#[cfg(test)]
+mod spec
+{
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+  use crate::frontend::lexer::Lexer;
+
+  #[test]
+  fn end_of_input()
+  {
+    let mut lexer = Lexer::from_str("foo");
+    assert_eq!(lexer.expect(Token::Identifier), Ok(Lexeme::identifier("foo")));
+    assert_eq!(lexer.expect_end_of_input(), Ok(()));
+  }
+
+  #[test]
+  fn trailing_input()
+  {
+    let mut lexer = Lexer::from_str("foo )");
+    assert_eq!(lexer.expect(Token::Identifier), Ok(Lexeme::identifier("foo")));
+    assert_eq!(
+      lexer.expect_end_of_input(),
+      Err(ParseError::ExpectedEndOfInput {
+        actual: Lexeme::symbol(")"),
+      })
+    );
+  }
+}
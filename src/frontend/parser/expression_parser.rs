@@ -1,65 +1,276 @@
 mod abstraction_parser;
+mod conditional_parser;
+mod let_parser;
 mod literal_parser;
+mod match_parser;
+mod operator_parser;
+mod record_parser;
 
 pub use abstraction_parser::*;
+pub use conditional_parser::*;
+pub use let_parser::*;
 pub use literal_parser::*;
+pub use match_parser::*;
+pub use operator_parser::*;
+pub use record_parser::*;
 
 use super::*;
 
+/// The parameter of the function a right section stands for, a name that can't
+/// be written so that the operand can't refer to it.
+pub const SECTION_OPERAND: &str = "(operand)";
 
 pub trait ExpressionParser
 where
   Self: ExpectSyntax,
   Self: CanBacktrack,
+  Self: HasFixities,
   Self: LiteralParser,
 {
   fn expect_expression_value(&mut self) -> Result<surface::Expression>
   {
     attempt!(self as s => {
-      let _ = s.expect(Token::Symbol("("))?;
-      let inner_expression = s.expect_expression()?;
-      let _ = s.expect(Token::Symbol(")"))?;
-      Ok(inner_expression)
+      let open = s.expect(Token::Symbol("["))?;
+      let mut elements = vec![];
+      let close = loop {
+        if let Ok(close) = s.breakpoint(|s| s.expect(Token::Symbol("]"))) {
+          break close
+        }
+        if !elements.is_empty() {
+          let _ = s.expect(Token::Symbol(","))?;
+        }
+        elements.push(s.expect_expression()?);
+      };
+      Ok(surface::List {
+        elements,
+        span: open.span().merge(close.span()),
+      }.into())
+    });
+    // a right section such as `(+ 1)` is the function `fun x -> x + 1`, but
+    // `(- 1)` is a negation
+    attempt!(self as s => {
+      let open = s.expect(Token::Symbol("("))?;
+      let operator = s.expect_operator()?;
+      if is_prefix(&operator.name) {
+        return Err(ParseError::Expected {
+          expected: NodeType::Expression,
+          actual: s.peek(),
+        })
+      }
+      let operand = s.expect_expression()?;
+      let close = s.expect(Token::Symbol(")"))?;
+      let parameter = surface::Identifier {
+        name: SECTION_OPERAND.to_string(),
+        span: operator.span,
+      };
+      Ok(surface::Abstraction {
+        parameters: vec![parameter.clone().into()],
+        body: surface::Application {
+          span: operator.span.merge(operand.span()),
+          abstraction: operator.into(),
+          arguments: vec![parameter.into(), operand],
+        }
+        .into(),
+        span: open.span().merge(close.span()),
+      }.into())
+    });
+    // the expression after the parenthesis is parsed once, whatever follows
+    // it, so that nested parentheses don't take exponential time
+    attempt!(self as s => {
+      let open = s.expect(Token::Symbol("("))?;
+      let expression = s.expect_expression()?;
+      let expected_close = match s.breakpoint(|s| s.expect(Token::Symbol(")"))) {
+        | Ok(_) => return Ok(expression),
+        | Err(error) => error,
+      };
+      if s.breakpoint(|s| s.expect(Token::Keyword(":"))).is_ok() {
+        let annotation = s.expect_type()?;
+        let close = s.expect(Token::Symbol(")"))?;
+        return Ok(surface::Annotation {
+          expression,
+          annotation,
+          span: open.span().merge(close.span()),
+        }.into())
+      }
+      if let Ok(operator) = s.breakpoint(|s| s.expect_operator()) {
+        let close = s.expect(Token::Symbol(")"))?;
+        return Ok(surface::Application {
+          abstraction: operator.into(),
+          arguments: vec![expression],
+          span: open.span().merge(close.span()),
+        }.into())
+      }
+      if s.breakpoint(|s| s.expect(Token::Symbol(","))).is_err() {
+        return Err(s.furthest_failure(expected_close))
+      }
+      let mut elements = vec![expression, s.expect_expression()?];
+      let close = loop {
+        if let Ok(close) = s.breakpoint(|s| s.expect(Token::Symbol(")"))) {
+          break close
+        }
+        let _ = s.expect(Token::Symbol(","))?;
+        elements.push(s.expect_expression()?);
+      };
+      Ok(surface::Tuple {
+        elements,
+        span: open.span().merge(close.span()),
+      }.into())
     });
     attempt!(self as s => {
       let abstraction = s.expect_abstraction()?;
       Ok(abstraction.into())
     });
     attempt!(self as s => {
+      let conditional = s.expect_conditional()?;
+      Ok(conditional.into())
+    });
+    attempt!(self as s => {
+      let let_in = s.expect_let()?;
+      Ok(let_in.into())
+    });
+    attempt!(self as s => {
+      let let_rec = s.expect_let_rec()?;
+      Ok(let_rec.into())
+    });
+    attempt!(self as s => {
+      let pattern_match = s.expect_match()?;
+      Ok(pattern_match.into())
+    });
+    attempt!(self as s => {
+      let record = s.expect_record()?;
+      Ok(record.into())
+    });
+    attempt!(self as s => {
+      let extension = s.expect_extension()?;
+      Ok(extension.into())
+    });
+    attempt!(self as s => {
+      let update = s.expect_update()?;
+      Ok(update.into())
+    });
+    attempt!(self as s => {
+      let restriction = s.expect_restriction()?;
+      Ok(restriction.into())
+    });
+    attempt!(self as s => {
+      let span = s.peek().map(|lexeme| lexeme.span()).unwrap_or_default();
       let literal = s.expect_literal()?;
-      Ok(literal.into())
+      Ok(surface::Expression::Literal(literal, span))
     });
     attempt!(self as s => {
-      let identifier = s.expect_identifier()?;
-      Ok(identifier.into())
+      let qualified = s.expect_qualified()?;
+      Ok(qualified.into())
+    });
+    attempt!(self as s => {
+      let name = s.expect_name()?;
+      Ok(name.into())
     });
     Err(ParseError::Expected {
       expected: NodeType::Expression,
+      actual: self.peek(),
     })
   }
 
   fn expect_expression(&mut self) -> Result<surface::Expression>
   {
-    let expression = self.expect_expression_value()?;
+    self
+      .expect_operation(0)
+      .map_err(|error| self.furthest_failure(error))
+  }
+
+  fn expect_application(&mut self) -> Result<surface::Expression>
+  {
+    let expression = self.expect_projection()?;
     let mut arguments = vec![];
-    while let Ok(argument) = self.expect_expression_value() {
+    while let Ok(argument) = self.expect_projection() {
       arguments.push(argument);
     }
-    Ok(match arguments.is_empty() {
-      | true => expression,
-      | _ => surface::Application {
+    Ok(match arguments.last() {
+      | None => expression,
+      | Some(last) => surface::Application {
+        span: expression.span().merge(last.span()),
         abstraction: expression,
         arguments,
       }
       .into(),
     })
   }
+
+  /// Parses a value followed by any number of projections such as `.0` or
+  /// field accesses such as `.name`.
+  fn expect_projection(&mut self) -> Result<surface::Expression>
+  {
+    let mut expression = self.expect_expression_value()?;
+    loop {
+      if let Ok(indices) = self.breakpoint(|s| {
+        let _ = s.expect(Token::Symbol("."))?;
+        s.expect_tuple_indices()
+      }) {
+        for (index, span) in indices {
+          expression = surface::Projection {
+            span: expression.span().merge(span),
+            tuple: expression,
+            index,
+          }
+          .into();
+        }
+      }
+      else if let Ok(field) = self.breakpoint(|s| {
+        let _ = s.expect(Token::Symbol("."))?;
+        s.expect_identifier()
+      }) {
+        expression = surface::FieldAccess {
+          span: expression.span().merge(field.span),
+          record: expression,
+          field,
+        }
+        .into();
+      }
+      else {
+        return Ok(expression)
+      }
+    }
+  }
+
+  /// Parses the index of a projection. `t.0.1` is lexed as `t`, `.` and the
+  /// float `0.1`, which stands for two indices.
+  fn expect_tuple_indices(&mut self) -> Result<Vec<(usize, surface::Span)>>
+  {
+    let lexeme = self.next();
+    let indices = match &lexeme {
+      | Some(lexeme) if lexeme.token() == &Token::IntegerLiteral => lexeme
+        .value()
+        .parse()
+        .ok()
+        .map(|index| vec![index]),
+      | Some(lexeme) if lexeme.token() == &Token::FloatLiteral => lexeme
+        .value()
+        .split_once('.')
+        .and_then(|(first, second)| {
+          Some(vec![first.parse().ok()?, second.parse().ok()?])
+        }),
+      | _ => None,
+    };
+    match (indices, lexeme) {
+      | (Some(indices), Some(lexeme)) => Ok(
+        indices
+          .into_iter()
+          .map(|index| (index, lexeme.span()))
+          .collect(),
+      ),
+      | (_, actual) => Err(ParseError::Expected {
+        expected: NodeType::TupleIndex,
+        actual,
+      }),
+    }
+  }
 }
 
 impl<Lexer> ExpressionParser for Lexer
 where
   Self: ExpectSyntax,
   Self: CanBacktrack,
+  Self: HasFixities,
 {
 }
 
@@ -104,6 +315,26 @@ mod spec
     assert_eq!(lexer.next(), None);
   }
 
+  #[test]
+  fn can_parse_deeply_nested_parentheses()
+  {
+    let input = format!("{}x{}", "(".repeat(20), ")".repeat(20));
+    let mut lexer = Lexer::from_str(&input).with_backtracking();
+    assert_eq!(
+      lexer.expect_expression(),
+      Ok(surface::Identifier::new("x").into())
+    );
+    assert_eq!(lexer.next(), None);
+    let input = format!("{}x{}", "(".repeat(20), ")".repeat(19));
+    let mut lexer = Lexer::from_str(&input).with_backtracking();
+    assert_eq!(
+      lexer.expect_expression(),
+      Err(ParseError::UnexpectedEndOfInput {
+        expected: Token::Symbol(")"),
+      })
+    );
+  }
+
   #[test]
   fn can_parse_named_application_three_arguments()
   {
@@ -118,6 +349,7 @@ mod spec
             surface::Identifier::new("b").into(),
             surface::Identifier::new("c").into(),
           ],
+          span: Default::default(),
         }
         .into()
       ),
@@ -134,11 +366,13 @@ mod spec
       Ok(
         surface::Application {
           abstraction: surface::Abstraction {
-            parameters: vec![surface::Identifier::new("x")],
+            parameters: vec![surface::Identifier::new("x").into()],
             body: surface::Identifier::new("x").into(),
+            span: Default::default(),
           }
           .into(),
-          arguments: vec![surface::Literal::Numeric("10".into()).into(),],
+          arguments: vec![surface::Literal::Integer(10).into(),],
+          span: Default::default(),
         }
         .into()
       ),
@@ -157,12 +391,14 @@ mod spec
           abstraction: surface::Identifier::new("f").into(),
           arguments: vec![
             surface::Abstraction {
-              parameters: vec![surface::Identifier::new("x")],
+              parameters: vec![surface::Identifier::new("x").into()],
               body: surface::Identifier::new("x").into(),
+              span: Default::default(),
             }
             .into(),
-            surface::Literal::Numeric("10".into()).into(),
+            surface::Literal::Integer(10).into(),
           ],
+          span: Default::default(),
         }
         .into()
       ),
@@ -180,13 +416,15 @@ mod spec
         surface::Application {
           abstraction: surface::Identifier::new("f").into(),
           arguments: vec![
-            surface::Literal::Numeric("10".into()).into(),
+            surface::Literal::Integer(10).into(),
             surface::Abstraction {
-              parameters: vec![surface::Identifier::new("x")],
+              parameters: vec![surface::Identifier::new("x").into()],
               body: surface::Identifier::new("x").into(),
+              span: Default::default(),
             }
             .into(),
           ],
+          span: Default::default(),
         }
         .into()
       ),
@@ -205,13 +443,16 @@ mod spec
           abstraction: surface::Application {
             abstraction: surface::Identifier::new("g").into(),
             arguments: vec![surface::Abstraction {
-              parameters: vec![surface::Identifier::new("x")],
+              parameters: vec![surface::Identifier::new("x").into()],
               body: surface::Identifier::new("x").into(),
+              span: Default::default(),
             }
             .into()],
+            span: Default::default(),
           }
           .into(),
-          arguments: vec![surface::Literal::Numeric("10".into()).into()],
+          arguments: vec![surface::Literal::Integer(10).into()],
+          span: Default::default(),
         }
         .into()
       )
@@ -232,15 +473,18 @@ mod spec
             abstraction: surface::Identifier::new("g").into(),
             arguments: vec![
               surface::Abstraction {
-                parameters: vec![surface::Identifier::new("x")],
+                parameters: vec![surface::Identifier::new("x").into()],
                 body: surface::Identifier::new("x").into(),
+                span: Default::default(),
               }
               .into(),
-              surface::Literal::Numeric("10".into()).into(),
+              surface::Literal::Integer(10).into(),
             ],
+            span: Default::default(),
           }
           .into(),
-          arguments: vec![surface::Literal::Numeric("10".into()).into()],
+          arguments: vec![surface::Literal::Integer(10).into()],
+          span: Default::default(),
         }
         .into()
       )
@@ -265,11 +509,13 @@ mod spec
                 surface::Identifier::new("b").into(),
                 surface::Identifier::new("c").into(),
               ],
+              span: Default::default(),
             }
             .into(),
             surface::Identifier::new("b").into(),
             surface::Identifier::new("c").into(),
           ],
+          span: Default::default(),
         }
         .into()
       )
@@ -295,10 +541,12 @@ mod spec
                 surface::Identifier::new("b").into(),
                 surface::Identifier::new("c").into(),
               ],
+              span: Default::default(),
             }
             .into(),
             surface::Identifier::new("c").into(),
           ],
+          span: Default::default(),
         }
         .into()
       )
@@ -325,9 +573,11 @@ mod spec
                 surface::Identifier::new("b").into(),
                 surface::Identifier::new("c").into(),
               ],
+              span: Default::default(),
             }
             .into(),
           ],
+          span: Default::default(),
         }
         .into()
       )
@@ -349,10 +599,13 @@ mod spec
             arguments: vec![surface::Application {
               abstraction: surface::Identifier::new("h").into(),
               arguments: vec![surface::Identifier::new("a").into(),],
+              span: Default::default(),
             }
             .into(),],
+            span: Default::default(),
           }
           .into(),],
+          span: Default::default(),
         }
         .into()
       )
@@ -375,21 +628,25 @@ mod spec
               abstraction: surface::Identifier::new("g").into(),
               arguments: vec![
                 surface::Abstraction {
-                  parameters: vec![surface::Identifier::new("x")],
+                  parameters: vec![surface::Identifier::new("x").into()],
                   body: surface::Identifier::new("x").into(),
+                  span: Default::default(),
                 }
                 .into(),
-                surface::Literal::Numeric("10".into()).into(),
+                surface::Literal::Integer(10).into(),
               ],
+              span: Default::default(),
             }
             .into(),
             surface::Abstraction {
-              parameters: vec![surface::Identifier::new("y")],
+              parameters: vec![surface::Identifier::new("y").into()],
               body: surface::Identifier::new("y").into(),
+              span: Default::default(),
             }
             .into(),
-            surface::Literal::Numeric("10".into()).into(),
+            surface::Literal::Integer(10).into(),
           ],
+          span: Default::default(),
         }
         .into()
       ),
@@ -447,7 +704,240 @@ mod spec
     let mut lexer = Lexer::from_str("10").with_backtracking();
     assert_eq!(
       lexer.expect_expression(),
-      Ok(surface::Literal::Numeric("10".into()).into())
+      Ok(surface::Literal::Integer(10).into())
+    );
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn application_spans_abstraction_and_arguments()
+  {
+    let mut lexer = Lexer::from_str("f (g a)\n  `b`").with_backtracking();
+    let expression = lexer.expect_expression().unwrap();
+    let span = expression.span();
+    assert_eq!((span.start.offset, span.end.offset), (0, 13));
+    assert_eq!((span.end.line, span.end.column), (2, 6));
+    let surface::Expression::Application(application) = expression
+    else {
+      panic!("expected an application")
+    };
+    let spans = application
+      .arguments
+      .iter()
+      .map(|argument| {
+        (argument.span().start.offset, argument.span().end.offset)
+      })
+      .collect::<Vec<_>>();
+    assert_eq!(spans, vec![(3, 6), (10, 13)]);
+  }
+
+  #[test]
+  fn abstraction_spans_keyword_to_body()
+  {
+    let mut lexer = Lexer::from_str(" fun x -> x y").with_backtracking();
+    let span = lexer
+      .expect_expression()
+      .unwrap()
+      .span();
+    assert_eq!((span.start.offset, span.end.offset), (1, 13));
+  }
+
+  #[test]
+  fn can_parse_annotated_expression()
+  {
+    let mut lexer = Lexer::from_str("f (x : Int)").with_backtracking();
+    assert_eq!(
+      lexer.expect_expression(),
+      Ok(
+        surface::Application {
+          abstraction: surface::Identifier::new("f").into(),
+          arguments: vec![surface::Annotation {
+            expression: surface::Identifier::new("x").into(),
+            annotation: surface::Identifier::new("Int").into(),
+            span: Default::default(),
+          }
+          .into()],
+          span: Default::default(),
+        }
+        .into()
+      )
+    );
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn can_parse_unit_and_tuples()
+  {
+    let mut lexer = Lexer::from_str("((), (a, b), c)").with_backtracking();
+    assert_eq!(
+      lexer.expect_expression(),
+      Ok(
+        surface::Tuple {
+          elements: vec![
+            surface::Literal::Unit.into(),
+            surface::Tuple {
+              elements: vec![
+                surface::Identifier::new("a").into(),
+                surface::Identifier::new("b").into(),
+              ],
+              span: Default::default(),
+            }
+            .into(),
+            surface::Identifier::new("c").into(),
+          ],
+          span: Default::default(),
+        }
+        .into()
+      )
+    );
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn can_parse_lists_and_cons()
+  {
+    let mut lexer = Lexer::from_str("0 :: [1, x] :: []").with_backtracking();
+    let list = |elements| surface::List {
+      elements,
+      span: Default::default(),
+    };
+    let cons = |head, tail| surface::Application {
+      abstraction: surface::Identifier::new("::").into(),
+      arguments: vec![head, tail],
+      span: Default::default(),
+    };
+    assert_eq!(
+      lexer.expect_expression(),
+      Ok(
+        cons(
+          surface::Literal::Integer(0).into(),
+          cons(
+            list(vec![
+              surface::Literal::Integer(1).into(),
+              surface::Identifier::new("x").into(),
+            ])
+            .into(),
+            list(vec![]).into()
+          )
+          .into()
+        )
+        .into()
+      )
+    );
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn qualified_names_end_at_the_first_lowercase_name()
+  {
+    let mut lexer = Lexer::from_str("A.B.x.name A.Some").with_backtracking();
+    assert_eq!(
+      lexer.expect_expression(),
+      Ok(
+        surface::Application {
+          abstraction: surface::FieldAccess {
+            record: surface::Qualified {
+              path: vec![
+                surface::Identifier::new("A"),
+                surface::Identifier::new("B")
+              ],
+              name: surface::Identifier::new("x"),
+              span: Default::default(),
+            }
+            .into(),
+            field: surface::Identifier::new("name"),
+            span: Default::default(),
+          }
+          .into(),
+          arguments: vec![surface::Qualified {
+            path: vec![surface::Identifier::new("A")],
+            name: surface::Identifier::new("Some"),
+            span: Default::default(),
+          }
+          .into()],
+          span: Default::default(),
+        }
+        .into()
+      )
+    );
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn projections_bind_tighter_than_application()
+  {
+    let mut lexer = Lexer::from_str("f t.0.1 (a, b).1").with_backtracking();
+    let projection =
+      |tuple: surface::Expression, index| -> surface::Expression {
+        surface::Projection {
+          tuple,
+          index,
+          span: Default::default(),
+        }
+        .into()
+      };
+    assert_eq!(
+      lexer.expect_expression(),
+      Ok(
+        surface::Application {
+          abstraction: surface::Identifier::new("f").into(),
+          arguments: vec![
+            projection(projection(surface::Identifier::new("t").into(), 0), 1),
+            projection(
+              surface::Tuple {
+                elements: vec![
+                  surface::Identifier::new("a").into(),
+                  surface::Identifier::new("b").into(),
+                ],
+                span: Default::default(),
+              }
+              .into(),
+              1
+            ),
+          ],
+          span: Default::default(),
+        }
+        .into()
+      )
+    );
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn projection_needs_an_index_or_a_field()
+  {
+    let mut lexer = Lexer::from_str("t.`x`").with_backtracking();
+    assert_eq!(
+      lexer.expect_expression(),
+      Ok(surface::Identifier::new("t").into())
+    );
+    assert_eq!(lexer.next(), Some(Lexeme::symbol(".")));
+  }
+
+  #[test]
+  fn field_accesses_bind_tighter_than_application()
+  {
+    let mut lexer = Lexer::from_str("f r.pair.0").with_backtracking();
+    assert_eq!(
+      lexer.expect_expression(),
+      Ok(
+        surface::Application {
+          abstraction: surface::Identifier::new("f").into(),
+          arguments: vec![surface::Projection {
+            tuple: surface::FieldAccess {
+              record: surface::Identifier::new("r").into(),
+              field: surface::Identifier::new("pair"),
+              span: Default::default(),
+            }
+            .into(),
+            index: 0,
+            span: Default::default(),
+          }
+          .into()],
+          span: Default::default(),
+        }
+        .into()
+      )
     );
     assert_eq!(lexer.next(), None);
   }
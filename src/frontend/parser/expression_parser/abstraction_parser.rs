@@ -4,29 +4,50 @@ pub trait AbstractionParser
 where
   Self: ExpectSyntax,
   Self: CanBacktrack,
+  Self: HasFixities,
   Self: LiteralParser,
 {
   fn expect_abstraction(&mut self) -> Result<surface::Abstraction>
   {
-    let _ = self.expect(Token::Keyword("fun"))?;
-    let parameter = self.expect_identifier()?;
+    let fun = self.expect(Token::Keyword("fun"))?;
+    let parameter = self.expect_parameter()?;
     let mut parameters = vec![parameter];
-    while let Ok(parameter) = self.breakpoint(|s| s.expect_identifier()) {
+    while let Ok(parameter) = self.breakpoint(|s| s.expect_parameter()) {
       parameters.push(parameter);
     }
     let _ = self.expect(Token::Keyword("->"))?;
     let body = self.expect_expression()?;
     Ok(surface::Abstraction {
+      span: fun.span().merge(body.span()),
       parameters,
       body,
     })
   }
+
+  /// Parses an atomic pattern such as `x` or `(x, y)`, or an annotated one
+  /// such as `(x : Type)`.
+  fn expect_parameter(&mut self) -> Result<surface::Parameter>
+  {
+    attempt!(self as s => {
+      let _ = s.expect(Token::Symbol("("))?;
+      let pattern = s.expect_pattern()?;
+      let _ = s.expect(Token::Keyword(":"))?;
+      let annotation = s.expect_type()?;
+      let _ = s.expect(Token::Symbol(")"))?;
+      Ok(surface::Parameter {
+        pattern,
+        annotation: Some(annotation),
+      })
+    });
+    Ok(self.expect_atomic_pattern()?.into())
+  }
 }
 
 impl<Lexer> AbstractionParser for Lexer
 where
   Self: ExpectSyntax,
   Self: CanBacktrack,
+  Self: HasFixities,
 {
 }
 
@@ -45,8 +66,9 @@ mod spec
     assert_eq!(
       lexer.expect_abstraction(),
       Ok(surface::Abstraction {
-        parameters: vec![surface::Identifier::new("x")],
+        parameters: vec![surface::Identifier::new("x").into()],
         body: surface::Identifier::new("x").into(),
+        span: Default::default(),
       })
     );
     assert_eq!(lexer.next(), None);
@@ -56,10 +78,11 @@ mod spec
       lexer.expect_abstraction(),
       Ok(surface::Abstraction {
         parameters: vec![
-          surface::Identifier::new("x"),
-          surface::Identifier::new("y"),
+          surface::Identifier::new("x").into(),
+          surface::Identifier::new("y").into(),
         ],
         body: surface::Identifier::new("x").into(),
+        span: Default::default(),
       })
     );
     assert_eq!(lexer.next(), None);
@@ -69,11 +92,61 @@ mod spec
       lexer.expect_abstraction(),
       Ok(surface::Abstraction {
         parameters: vec![
-          surface::Identifier::new("x"),
-          surface::Identifier::new("y"),
-          surface::Identifier::new("z"),
+          surface::Identifier::new("x").into(),
+          surface::Identifier::new("y").into(),
+          surface::Identifier::new("z").into(),
         ],
         body: surface::Identifier::new("x").into(),
+        span: Default::default(),
+      })
+    );
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn can_parse_annotated_parameters()
+  {
+    let mut lexer = Lexer::from_str("fun (x : Int) y -> x").with_backtracking();
+    assert_eq!(
+      lexer.expect_abstraction(),
+      Ok(surface::Abstraction {
+        parameters: vec![
+          surface::Parameter {
+            pattern: surface::Identifier::new("x").into(),
+            annotation: Some(surface::Identifier::new("Int").into()),
+          },
+          surface::Identifier::new("y").into(),
+        ],
+        body: surface::Identifier::new("x").into(),
+        span: Default::default(),
+      })
+    );
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn can_parse_tuple_parameters()
+  {
+    let mut lexer =
+      Lexer::from_str("fun (x, y) ((z, w) : T) -> x").with_backtracking();
+    let pair = |left: &str, right: &str| {
+      surface::Pattern::Tuple(
+        vec![
+          surface::Identifier::new(left).into(),
+          surface::Identifier::new(right).into(),
+        ],
+        Default::default(),
+      )
+    };
+    assert_eq!(
+      lexer.expect_abstraction(),
+      Ok(surface::Abstraction {
+        parameters: vec![pair("x", "y").into(), surface::Parameter {
+          pattern: pair("z", "w"),
+          annotation: Some(surface::Identifier::new("T").into()),
+        },],
+        body: surface::Identifier::new("x").into(),
+        span: Default::default(),
       })
     );
     assert_eq!(lexer.next(), None);
@@ -0,0 +1,102 @@
+use super::*;
+
+pub trait ConditionalParser
+where
+  Self: ExpectSyntax,
+  Self: CanBacktrack,
+  Self: HasFixities,
+  Self: LiteralParser,
+{
+  fn expect_conditional(&mut self) -> Result<surface::Conditional>
+  {
+    let keyword = self.expect(Token::Keyword("if"))?;
+    let condition = self.expect_expression()?;
+    let _ = self.expect(Token::Keyword("then"))?;
+    let consequence = self.expect_expression()?;
+    let _ = self.expect(Token::Keyword("else"))?;
+    let alternative = self.expect_expression()?;
+    Ok(surface::Conditional {
+      span: keyword.span().merge(alternative.span()),
+      condition,
+      consequence,
+      alternative,
+    })
+  }
+}
+
+impl<Lexer> ConditionalParser for Lexer
+where
+  Self: ExpectSyntax,
+  Self: CanBacktrack,
+  Self: HasFixities,
+{
+}
+
+#[cfg(test)]
+mod spec
+{
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+  use crate::frontend::lexer::Lexer;
+
+  #[test]
+  fn can_parse_conditional()
+  {
+    let mut lexer =
+      Lexer::from_str("if f x then `yes` else `no`").with_backtracking();
+    assert_eq!(
+      lexer.expect_conditional(),
+      Ok(surface::Conditional {
+        condition: surface::Application {
+          abstraction: surface::Identifier::new("f").into(),
+          arguments: vec![surface::Identifier::new("x").into()],
+          span: Default::default(),
+        }
+        .into(),
+        consequence: surface::Literal::String("yes".into()).into(),
+        alternative: surface::Literal::String("no".into()).into(),
+        span: Default::default(),
+      })
+    );
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn else_extends_as_far_as_possible()
+  {
+    let mut lexer = Lexer::from_str("if a then b else if c then d else e f")
+      .with_backtracking();
+    let conditional = lexer.expect_conditional().unwrap();
+    assert!(matches!(
+      conditional.alternative,
+      surface::Expression::Conditional(ref inner)
+        if matches!(inner.alternative, surface::Expression::Application(_))
+    ));
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn conditional_spans_keyword_to_alternative()
+  {
+    let mut lexer =
+      Lexer::from_str("(if true\n then 1\n else 2)").with_backtracking();
+    let span = lexer
+      .expect_expression()
+      .unwrap()
+      .span();
+    assert_eq!((span.start.offset, span.end.offset), (1, 24));
+  }
+
+  #[test]
+  fn missing_else()
+  {
+    let mut lexer = Lexer::from_str("if true then 1").with_backtracking();
+    assert_eq!(
+      lexer.expect_conditional(),
+      Err(ParseError::UnexpectedEndOfInput {
+        expected: Token::Keyword("else"),
+      })
+    );
+  }
+}
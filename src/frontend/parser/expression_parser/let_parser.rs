@@ -0,0 +1,270 @@
+use super::*;
+
+pub trait LetParser
+where
+  Self: ExpectSyntax,
+  Self: CanBacktrack,
+  Self: HasFixities,
+  Self: LiteralParser,
+{
+  fn expect_let(&mut self) -> Result<surface::Let>
+  {
+    let keyword = self.expect(Token::Keyword("let"))?;
+    let mut bindings = vec![self.expect_binding()?];
+    while self
+      .breakpoint(|s| s.expect(Token::Keyword("and")))
+      .is_ok()
+    {
+      bindings.push(self.expect_binding()?);
+    }
+    let _ = self.expect(Token::Keyword("in"))?;
+    let body = self.expect_expression()?;
+    Ok(surface::Let {
+      span: keyword.span().merge(body.span()),
+      bindings,
+      body,
+    })
+  }
+
+  fn expect_let_rec(&mut self) -> Result<surface::LetRec>
+  {
+    let keyword = self.expect(Token::Keyword("let"))?;
+    let _ = self.expect(Token::Keyword("rec"))?;
+    let functions = self.expect_functions()?;
+    let _ = self.expect(Token::Keyword("in"))?;
+    let body = self.expect_expression()?;
+    Ok(surface::LetRec {
+      span: keyword.span().merge(body.span()),
+      functions,
+      body,
+    })
+  }
+
+  /// Parses one or more functions separated by `and`.
+  fn expect_functions(&mut self) -> Result<Vec<surface::Function>>
+  {
+    let mut functions = vec![self.expect_function()?];
+    while self
+      .breakpoint(|s| s.expect(Token::Keyword("and")))
+      .is_ok()
+    {
+      functions.push(self.expect_function()?);
+    }
+    Ok(functions)
+  }
+
+  /// Parses `f x y = e` or `f x y : T = e`, where `T` is the type of `e`.
+  fn expect_function(&mut self) -> Result<surface::Function>
+  {
+    let name = self.expect_name()?;
+    let mut parameters = vec![self.expect_parameter()?];
+    while let Ok(parameter) = self.breakpoint(|s| s.expect_parameter()) {
+      parameters.push(parameter);
+    }
+    let annotation = self
+      .breakpoint(|s| {
+        let _ = s.expect(Token::Keyword(":"))?;
+        s.expect_type()
+      })
+      .ok();
+    let _ = self.expect(Token::Keyword("="))?;
+    let body = self.expect_expression()?;
+    let body = match annotation {
+      | Some(annotation) => surface::Annotation {
+        span: body.span(),
+        expression: body,
+        annotation,
+      }
+      .into(),
+      | None => body,
+    };
+    Ok(surface::Function {
+      abstraction: surface::Abstraction {
+        span: name.span.merge(body.span()),
+        parameters,
+        body,
+      },
+      name,
+    })
+  }
+
+  /// Parses `x = e` or `x : T = e`, the latter is checked just like
+  /// `x = (e : T)`.
+  fn expect_binding(&mut self) -> Result<surface::Binding>
+  {
+    let name = self.expect_name()?;
+    let annotation = self
+      .breakpoint(|s| {
+        let _ = s.expect(Token::Keyword(":"))?;
+        s.expect_type()
+      })
+      .ok();
+    let _ = self.expect(Token::Keyword("="))?;
+    let value = self.expect_expression()?;
+    let value = match annotation {
+      | Some(annotation) => surface::Annotation {
+        span: value.span(),
+        expression: value,
+        annotation,
+      }
+      .into(),
+      | None => value,
+    };
+    Ok(surface::Binding {
+      name,
+      value,
+    })
+  }
+}
+
+impl<Lexer> LetParser for Lexer
+where
+  Self: ExpectSyntax,
+  Self: CanBacktrack,
+  Self: HasFixities,
+{
+}
+
+#[cfg(test)]
+mod spec
+{
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+  use crate::frontend::lexer::Lexer;
+
+  #[test]
+  fn can_parse_let()
+  {
+    let mut lexer = Lexer::from_str("let x = 10 in f x").with_backtracking();
+    assert_eq!(
+      lexer.expect_let(),
+      Ok(surface::Let {
+        bindings: vec![surface::Binding {
+          name: surface::Identifier::new("x"),
+          value: surface::Literal::Integer(10).into(),
+        }],
+        body: surface::Application {
+          abstraction: surface::Identifier::new("f").into(),
+          arguments: vec![surface::Identifier::new("x").into()],
+          span: Default::default(),
+        }
+        .into(),
+        span: Default::default(),
+      })
+    );
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn can_parse_simultaneous_bindings()
+  {
+    let mut lexer =
+      Lexer::from_str("let x = f y and y = `foo` in x").with_backtracking();
+    assert_eq!(
+      lexer.expect_let(),
+      Ok(surface::Let {
+        bindings: vec![
+          surface::Binding {
+            name: surface::Identifier::new("x"),
+            value: surface::Application {
+              abstraction: surface::Identifier::new("f").into(),
+              arguments: vec![surface::Identifier::new("y").into()],
+              span: Default::default(),
+            }
+            .into(),
+          },
+          surface::Binding {
+            name: surface::Identifier::new("y"),
+            value: surface::Literal::String("foo".into()).into(),
+          },
+        ],
+        body: surface::Identifier::new("x").into(),
+        span: Default::default(),
+      })
+    );
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn let_spans_keyword_to_body()
+  {
+    let mut lexer = Lexer::from_str("(let x = 10 in\n  x)").with_backtracking();
+    let span = lexer
+      .expect_expression()
+      .unwrap()
+      .span();
+    assert_eq!((span.start.offset, span.end.offset), (1, 18));
+  }
+
+  #[test]
+  fn can_parse_mutually_recursive_functions()
+  {
+    let mut lexer =
+      Lexer::from_str("let rec f x = g x and g (y : Int) : Int = f y in f")
+        .with_backtracking();
+    assert_eq!(
+      lexer.expect_let_rec(),
+      Ok(surface::LetRec {
+        functions: vec![
+          surface::Function {
+            name: surface::Identifier::new("f"),
+            abstraction: surface::Abstraction {
+              parameters: vec![surface::Identifier::new("x").into()],
+              body: surface::Application {
+                abstraction: surface::Identifier::new("g").into(),
+                arguments: vec![surface::Identifier::new("x").into()],
+                span: Default::default(),
+              }
+              .into(),
+              span: Default::default(),
+            },
+          },
+          surface::Function {
+            name: surface::Identifier::new("g"),
+            abstraction: surface::Abstraction {
+              parameters: vec![surface::Parameter {
+                pattern: surface::Identifier::new("y").into(),
+                annotation: Some(surface::Identifier::new("Int").into()),
+              }],
+              body: surface::Annotation {
+                expression: surface::Application {
+                  abstraction: surface::Identifier::new("f").into(),
+                  arguments: vec![surface::Identifier::new("y").into()],
+                  span: Default::default(),
+                }
+                .into(),
+                annotation: surface::Identifier::new("Int").into(),
+                span: Default::default(),
+              }
+              .into(),
+              span: Default::default(),
+            },
+          },
+        ],
+        body: surface::Identifier::new("f").into(),
+        span: Default::default(),
+      })
+    );
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn recursive_bindings_need_a_parameter()
+  {
+    let mut lexer = Lexer::from_str("let rec f = f in f").with_backtracking();
+    assert!(lexer.expect_let_rec().is_err());
+  }
+
+  #[test]
+  fn missing_in()
+  {
+    let mut lexer = Lexer::from_str("let x = 10 x").with_backtracking();
+    assert_eq!(
+      lexer.expect_let(),
+      Err(ParseError::UnexpectedEndOfInput {
+        expected: Token::Keyword("in"),
+      })
+    );
+  }
+}
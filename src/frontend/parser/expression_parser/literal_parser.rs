@@ -3,12 +3,14 @@ mod false_literal_parser;
 mod numeric_literal_parser;
 mod string_literal_parser;
 mod true_literal_parser;
+mod unit_literal_parser;
 
 pub use boolean_literal_parser::*;
 pub use false_literal_parser::*;
 pub use numeric_literal_parser::*;
 pub use string_literal_parser::*;
 pub use true_literal_parser::*;
+pub use unit_literal_parser::*;
 
 use super::*;
 
@@ -17,14 +19,17 @@ where
   Self: Sized,
   Self: StringLiteralParser,
   Self: BooleanLiteralParser,
+  Self: UnitLiteralParser,
 {
   fn expect_literal(&mut self) -> Result<surface::Literal>
   {
     attempt!(self as s => s.expect_string_literal());
     attempt!(self as s => s.expect_boolean_literal());
     attempt!(self as s => s.expect_numeric_literal());
+    attempt!(self as s => s.expect_unit_literal());
     Err(ParseError::Expected {
       expected: NodeType::Literal,
+      actual: self.peek(),
     })
   }
 }
@@ -68,10 +73,7 @@ mod spec
   fn can_parse_numeric_literal()
   {
     let mut lexer = Lexer::from_str("10").with_backtracking();
-    assert_eq!(
-      lexer.expect_literal(),
-      Ok(surface::Literal::Numeric("10".into()))
-    );
+    assert_eq!(lexer.expect_literal(), Ok(surface::Literal::Integer(10)));
     assert_eq!(lexer.next(), None);
   }
 }
@@ -11,6 +11,7 @@ where
     attempt!(self as s => s.expect_boolean_false());
     Err(ParseError::Expected {
       expected: NodeType::BooleanLiteral,
+      actual: self.peek(),
     })
   }
 }
@@ -1,4 +1,5 @@
 use super::*;
+use crate::frontend::lexer::parse_numeric;
 
 pub trait NumericLiteralParser
 where
@@ -7,9 +8,14 @@ where
 {
   fn expect_numeric_literal(&mut self) -> Result<surface::Literal>
   {
+    let literal = |lexeme: Lexeme| {
+      parse_numeric(lexeme.value())
+        .expect("numeric literals are validated by the lexer")
+    };
+    attempt!(self as s => s.expect(Token::IntegerLiteral).map(literal));
     self
-      .expect(Token::NumericLiteral)
-      .map(|lexeme| surface::Literal::Numeric(lexeme.value().clone()))
+      .expect(Token::FloatLiteral)
+      .map(literal)
   }
 }
 impl<Lexer> NumericLiteralParser for Lexer
@@ -30,11 +36,19 @@ mod spec
   #[test]
   fn can_parse_numerics()
   {
-    let mut lexer = Lexer::from_str("1").with_backtracking();
+    let mut lexer = Lexer::from_str("1 0x1_F 1.5e3").with_backtracking();
     assert_eq!(
       lexer.expect_numeric_literal(),
-      Ok(surface::Literal::Numeric("1".into()))
-    )
+      Ok(surface::Literal::Integer(1))
+    );
+    assert_eq!(
+      lexer.expect_numeric_literal(),
+      Ok(surface::Literal::Integer(31))
+    );
+    assert_eq!(
+      lexer.expect_numeric_literal(),
+      Ok(surface::Literal::Float(1500.0))
+    );
   }
 
   #[test]
@@ -44,7 +58,7 @@ mod spec
     assert_eq!(
       lexer.expect_numeric_literal(),
       Err(ParseError::UnexpectedToken {
-        expected: Token::NumericLiteral,
+        expected: Token::FloatLiteral,
         actual: Lexeme::malformed_numeric("1.1.1"),
       })
     )
@@ -0,0 +1,37 @@
+use super::*;
+
+pub trait UnitLiteralParser
+where
+  Self: ExpectSyntax,
+  Self: CanBacktrack,
+{
+  fn expect_unit_literal(&mut self) -> Result<surface::Literal>
+  {
+    let _ = self.expect(Token::Symbol("("))?;
+    let _ = self.expect(Token::Symbol(")"))?;
+    Ok(surface::Literal::Unit)
+  }
+}
+impl<Lexer> UnitLiteralParser for Lexer
+where
+  Self: ExpectSyntax,
+  Self: CanBacktrack,
+{
+}
+
+#[cfg(test)]
+mod spec
+{
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+  use crate::frontend::lexer::Lexer;
+
+  #[test]
+  fn empty_parentheses()
+  {
+    let mut lexer = Lexer::from_str("( )").with_backtracking();
+    assert_eq!(lexer.expect_unit_literal(), Ok(surface::Literal::Unit));
+    assert_eq!(lexer.next(), None);
+  }
+}
@@ -0,0 +1,148 @@
+use super::*;
+
+pub trait MatchParser
+where
+  Self: ExpectSyntax,
+  Self: CanBacktrack,
+  Self: HasFixities,
+  Self: LiteralParser,
+{
+  /// Parses `match e with | p -> e | ...`, the `|` before the first arm is
+  /// optional.
+  fn expect_match(&mut self) -> Result<surface::Match>
+  {
+    let keyword = self.expect(Token::Keyword("match"))?;
+    let scrutinee = self.expect_expression()?;
+    let _ = self.expect(Token::Keyword("with"))?;
+    let _ = self.breakpoint(|s| s.expect(Token::Keyword("|")));
+    let mut arms = vec![self.expect_arm()?];
+    while self
+      .breakpoint(|s| s.expect(Token::Keyword("|")))
+      .is_ok()
+    {
+      arms.push(self.expect_arm()?);
+    }
+    let end = arms
+      .last()
+      .map(|arm| arm.body.span())
+      .unwrap_or_default();
+    Ok(surface::Match {
+      span: keyword.span().merge(end),
+      scrutinee,
+      arms,
+    })
+  }
+
+  fn expect_arm(&mut self) -> Result<surface::Arm>
+  {
+    let pattern = self.expect_pattern()?;
+    let _ = self.expect(Token::Keyword("->"))?;
+    let body = self.expect_expression()?;
+    Ok(surface::Arm {
+      pattern,
+      body,
+    })
+  }
+}
+
+impl<Lexer> MatchParser for Lexer
+where
+  Self: ExpectSyntax,
+  Self: CanBacktrack,
+  Self: HasFixities,
+{
+}
+
+#[cfg(test)]
+mod spec
+{
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+  use crate::frontend::lexer::Lexer;
+
+  #[test]
+  fn can_parse_match()
+  {
+    let mut lexer = Lexer::from_str("match x with | Some y -> y | None -> 0")
+      .with_backtracking();
+    assert_eq!(
+      lexer.expect_match(),
+      Ok(surface::Match {
+        scrutinee: surface::Identifier::new("x").into(),
+        arms: vec![
+          surface::Arm {
+            pattern: surface::Pattern::Constructor(
+              vec![],
+              surface::Identifier::new("Some"),
+              Some(Box::new(surface::Identifier::new("y").into())),
+            ),
+            body: surface::Identifier::new("y").into(),
+          },
+          surface::Arm {
+            pattern: surface::Pattern::Constructor(
+              vec![],
+              surface::Identifier::new("None"),
+              None,
+            ),
+            body: surface::Literal::Integer(0).into(),
+          },
+        ],
+        span: Default::default(),
+      })
+    );
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn the_first_bar_is_optional()
+  {
+    let mut lexer =
+      Lexer::from_str("match x with _ -> 1 | _ -> 2").with_backtracking();
+    assert_eq!(
+      lexer
+        .expect_match()
+        .map(|pattern_match| pattern_match.arms.len()),
+      Ok(2)
+    );
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn nested_matches_take_the_following_arms()
+  {
+    let mut lexer =
+      Lexer::from_str("match x with 1 -> match y with _ -> 1 | _ -> 2")
+        .with_backtracking();
+    let pattern_match = lexer.expect_match().unwrap();
+    assert_eq!(pattern_match.arms.len(), 1);
+    assert!(matches!(
+      pattern_match.arms[0].body,
+      surface::Expression::Match(ref inner) if inner.arms.len() == 2
+    ));
+  }
+
+  #[test]
+  fn match_spans_keyword_to_last_arm()
+  {
+    let mut lexer =
+      Lexer::from_str("(match x with\n | _ -> 1)").with_backtracking();
+    let span = lexer
+      .expect_expression()
+      .unwrap()
+      .span();
+    assert_eq!((span.start.offset, span.end.offset), (1, 23));
+  }
+
+  #[test]
+  fn arms_need_an_arrow()
+  {
+    let mut lexer = Lexer::from_str("match x with y").with_backtracking();
+    assert_eq!(
+      lexer.expect_match(),
+      Err(ParseError::UnexpectedEndOfInput {
+        expected: Token::Keyword("->"),
+      })
+    );
+  }
+}
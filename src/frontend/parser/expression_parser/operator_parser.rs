@@ -0,0 +1,397 @@
+use super::*;
+
+pub trait OperatorParser
+where
+  Self: ExpectSyntax,
+  Self: CanBacktrack,
+  Self: HasFixities,
+  Self: LiteralParser,
+{
+  /// Parses an operator symbol, `=` is both a keyword and the equality
+  /// operator.
+  fn expect_operator(&mut self) -> Result<surface::Identifier>
+  {
+    match self.next() {
+      | Some(lexeme)
+        if matches!(lexeme.token(), Token::Operator | Token::Keyword("=")) =>
+        Ok(surface::Identifier {
+          name: lexeme.value().clone(),
+          span: lexeme.span(),
+        }),
+      | actual => Err(ParseError::Expected {
+        expected: NodeType::Operator,
+        actual,
+      }),
+    }
+  }
+
+  /// Parses a name that can be bound, `x` or an operator between parentheses
+  /// such as `(+)`.
+  fn expect_name(&mut self) -> Result<surface::Identifier>
+  {
+    attempt!(self as s => s.expect_identifier());
+    let open = self.expect(Token::Symbol("("))?;
+    // `let (x, y) = ...` is not a binding of an operator, but of no name
+    let operator =
+      self
+        .expect_operator()
+        .map_err(|_| ParseError::Expected {
+          expected: NodeType::Name,
+          actual: Some(open.clone()),
+        })?;
+    let close = self.expect(Token::Symbol(")"))?;
+    Ok(surface::Identifier {
+      name: operator.name,
+      span: open.span().merge(close.span()),
+    })
+  }
+
+  /// Parses operands joined by infix operators, leaving any operator that
+  /// binds less tightly than `minimum` to the caller.
+  fn expect_operation(
+    &mut self,
+    minimum: u8,
+  ) -> Result<surface::Expression>
+  {
+    let mut left = self.expect_operand()?;
+    // the last non-associative operator, which can't be chained with another
+    // of its precedence
+    let mut unchainable: Option<(surface::Span, u8)> = None;
+    while let Ok((operator, fixity)) = self.breakpoint(|s| {
+      let lexeme = s.peek();
+      let operator = s.expect_operator()?;
+      let fixity = s.fixities().get(&operator.name);
+      match s.peek() {
+        // the operator ends a left section such as `(x +)`
+        | Some(lexeme) if lexeme.token() == &Token::Symbol(")") =>
+          Err(ParseError::Expected {
+            expected: NodeType::Expression,
+            actual: Some(lexeme),
+          }),
+        // left to the caller, so it fails at the operator rather than at
+        // what follows it
+        | _ if fixity.precedence < minimum => Err(ParseError::Expected {
+          expected: NodeType::Expression,
+          actual: lexeme,
+        }),
+        | _ => Ok((operator, fixity)),
+      }
+    }) {
+      if let Some((previous, precedence)) = unchainable.take() {
+        if precedence == fixity.precedence {
+          return Err(ParseError::ChainedOperator {
+            operator,
+            previous,
+          })
+        }
+      }
+      let right = self.expect_operation(match fixity.associativity {
+        | Associativity::Right => fixity.precedence,
+        | Associativity::Left | Associativity::None => fixity.precedence + 1,
+      })?;
+      if fixity.associativity == Associativity::None {
+        unchainable = Some((operator.span, fixity.precedence));
+      }
+      left = surface::Application {
+        span: left.span().merge(right.span()),
+        abstraction: operator.into(),
+        arguments: vec![left, right],
+      }
+      .into();
+    }
+    Ok(left)
+  }
+
+  /// Parses an application, or an operand behind a prefix operator. `-x` is
+  /// the negation `~- x` and `-.x` is `~-. x`, but `-1` and `-1.5` are
+  /// literals. Operators starting with `~` or `!` are applied as they are.
+  fn expect_operand(&mut self) -> Result<surface::Expression>
+  {
+    match self.peek() {
+      | Some(lexeme) if lexeme.token() == &Token::Operator => {
+        let operator = self.expect_operator()?;
+        let name = match operator.name.as_str() {
+          | "-" | "-." => format!("~{}", operator.name),
+          | name if is_prefix(name) => operator.name.clone(),
+          | _ =>
+            return Err(ParseError::Expected {
+              expected: NodeType::Expression,
+              actual: Some(lexeme),
+            }),
+        };
+        let operand = self.expect_operand()?;
+        let span = operator.span.merge(operand.span());
+        Ok(match (name.as_str(), operand) {
+          | (
+            "~-",
+            surface::Expression::Literal(surface::Literal::Integer(value), _),
+          ) => surface::Expression::Literal(
+            surface::Literal::Integer(-value),
+            span,
+          ),
+          | (
+            "~-" | "~-.",
+            surface::Expression::Literal(surface::Literal::Float(value), _),
+          ) =>
+            surface::Expression::Literal(surface::Literal::Float(-value), span),
+          | (_, operand) => surface::Application {
+            span,
+            abstraction: surface::Identifier {
+              name,
+              span: operator.span,
+            }
+            .into(),
+            arguments: vec![operand],
+          }
+          .into(),
+        })
+      },
+      | _ => self.expect_application(),
+    }
+  }
+}
+
+/// Whether the operator `name` can be written before its operand.
+pub fn is_prefix(name: &str) -> bool
+{
+  matches!(name, "-" | "-.") || name.starts_with(['~', '!'])
+}
+
+impl<Lexer> OperatorParser for Lexer
+where
+  Self: ExpectSyntax,
+  Self: CanBacktrack,
+  Self: HasFixities,
+{
+}
+
+#[cfg(test)]
+mod spec
+{
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+  use crate::frontend::lexer::Lexer;
+
+  fn operation(
+    operator: &str,
+    left: surface::Expression,
+    right: surface::Expression,
+  ) -> surface::Expression
+  {
+    surface::Application {
+      abstraction: surface::Identifier::new(operator).into(),
+      arguments: vec![left, right],
+      span: Default::default(),
+    }
+    .into()
+  }
+
+  fn name(name: &str) -> surface::Expression
+  {
+    surface::Identifier::new(name).into()
+  }
+
+  #[test]
+  fn multiplication_binds_tighter_than_addition()
+  {
+    let mut lexer = Lexer::from_str("a + b * c - d").with_backtracking();
+    assert_eq!(
+      lexer.expect_expression(),
+      Ok(operation(
+        "-",
+        operation("+", name("a"), operation("*", name("b"), name("c"))),
+        name("d")
+      ))
+    );
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn right_associative_operators_group_to_the_right()
+  {
+    let mut lexer = Lexer::from_str("a :: b :: c").with_backtracking();
+    assert_eq!(
+      lexer.expect_expression(),
+      Ok(operation("::", name("a"), operation("::", name("b"), name("c"))))
+    );
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn application_binds_tighter_than_operators()
+  {
+    let mut lexer = Lexer::from_str("f x = g y").with_backtracking();
+    assert_eq!(
+      lexer.expect_expression(),
+      Ok(operation(
+        "=",
+        surface::Application {
+          abstraction: name("f"),
+          arguments: vec![name("x")],
+          span: Default::default(),
+        }
+        .into(),
+        surface::Application {
+          abstraction: name("g"),
+          arguments: vec![name("y")],
+          span: Default::default(),
+        }
+        .into()
+      ))
+    );
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn declared_fixities_are_used()
+  {
+    let mut fixities = Fixities::default();
+    fixities.declare(&FixityDeclaration {
+      fixity: Fixity::right(8),
+      operators: vec!["-".into()],
+    });
+    let mut lexer = Lexer::from_str("a * b - c - d")
+      .with_backtracking()
+      .with_fixities(fixities);
+    assert_eq!(
+      lexer.expect_expression(),
+      Ok(operation(
+        "*",
+        name("a"),
+        operation("-", name("b"), operation("-", name("c"), name("d")))
+      ))
+    );
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn non_associative_operators_do_not_chain()
+  {
+    let mut fixities = Fixities::default();
+    fixities.declare(&FixityDeclaration {
+      fixity: Fixity::none(4),
+      operators: vec!["==".into()],
+    });
+    let mut lexer = Lexer::from_str("a == b == c")
+      .with_backtracking()
+      .with_fixities(fixities);
+    assert_eq!(
+      lexer.expect_expression(),
+      Err(ParseError::ChainedOperator {
+        operator: surface::Identifier::new("=="),
+        previous: Default::default(),
+      })
+    );
+  }
+
+  #[test]
+  fn prefix_minus_is_negation()
+  {
+    let mut lexer = Lexer::from_str("-x * 2").with_backtracking();
+    assert_eq!(
+      lexer.expect_expression(),
+      Ok(operation(
+        "*",
+        surface::Application {
+          abstraction: name("~-"),
+          arguments: vec![name("x")],
+          span: Default::default(),
+        }
+        .into(),
+        surface::Literal::Integer(2).into()
+      ))
+    );
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn negative_numbers_are_literals()
+  {
+    let mut lexer = Lexer::from_str("-1 -. -2.5").with_backtracking();
+    assert_eq!(
+      lexer.expect_expression(),
+      Ok(operation(
+        "-.",
+        surface::Literal::Integer(-1).into(),
+        surface::Literal::Float(-2.5).into()
+      ))
+    );
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn operators_between_parentheses_are_names()
+  {
+    let mut lexer = Lexer::from_str("(+) 1 (-)").with_backtracking();
+    assert_eq!(
+      lexer.expect_expression(),
+      Ok(
+        surface::Application {
+          abstraction: name("+"),
+          arguments: vec![surface::Literal::Integer(1).into(), name("-")],
+          span: Default::default(),
+        }
+        .into()
+      )
+    );
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn left_sections_apply_the_operator_to_one_operand()
+  {
+    let mut lexer = Lexer::from_str("(x ^)").with_backtracking();
+    assert_eq!(
+      lexer.expect_expression(),
+      Ok(
+        surface::Application {
+          abstraction: name("^"),
+          arguments: vec![name("x")],
+          span: Default::default(),
+        }
+        .into()
+      )
+    );
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn right_sections_are_functions_of_the_left_operand()
+  {
+    let mut lexer = Lexer::from_str("(^ x) (- 1)").with_backtracking();
+    assert_eq!(
+      lexer.expect_expression(),
+      Ok(
+        surface::Application {
+          abstraction: surface::Abstraction {
+            parameters: vec![surface::Identifier::new(SECTION_OPERAND).into()],
+            body: surface::Application {
+              abstraction: name("^"),
+              arguments: vec![name(SECTION_OPERAND), name("x")],
+              span: Default::default(),
+            }
+            .into(),
+            span: Default::default(),
+          }
+          .into(),
+          arguments: vec![surface::Literal::Integer(-1).into()],
+          span: Default::default(),
+        }
+        .into()
+      )
+    );
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn operation_spans_both_operands()
+  {
+    let mut lexer = Lexer::from_str(" a + b").with_backtracking();
+    let span = lexer
+      .expect_expression()
+      .unwrap()
+      .span();
+    assert_eq!((span.start.offset, span.end.offset), (1, 6));
+  }
+}
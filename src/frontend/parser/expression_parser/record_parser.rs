@@ -0,0 +1,222 @@
+use super::*;
+
+pub trait RecordParser
+where
+  Self: ExpectSyntax,
+  Self: CanBacktrack,
+  Self: HasFixities,
+  Self: LiteralParser,
+{
+  /// Parses `{ name = e; age = e }`.
+  fn expect_record(&mut self) -> Result<surface::Record>
+  {
+    let open = self.expect(Token::Symbol("{"))?;
+    let fields = self.expect_fields()?;
+    let close = self.expect(Token::Symbol("}"))?;
+    Ok(surface::Record {
+      fields,
+      span: open.span().merge(close.span()),
+    })
+  }
+
+  /// Parses `{ r with age = e }`.
+  fn expect_update(&mut self) -> Result<surface::Update>
+  {
+    let open = self.expect(Token::Symbol("{"))?;
+    let record = self.expect_expression()?;
+    let _ = self.expect(Token::Keyword("with"))?;
+    let fields = self.expect_fields()?;
+    let close = self.expect(Token::Symbol("}"))?;
+    Ok(surface::Update {
+      record,
+      fields,
+      span: open.span().merge(close.span()),
+    })
+  }
+
+  /// Parses `{ age = e | r }`.
+  fn expect_extension(&mut self) -> Result<surface::Extension>
+  {
+    let open = self.expect(Token::Symbol("{"))?;
+    let fields = self.expect_fields()?;
+    let _ = self.expect(Token::Keyword("|"))?;
+    let record = self.expect_expression()?;
+    let close = self.expect(Token::Symbol("}"))?;
+    Ok(surface::Extension {
+      fields,
+      record,
+      span: open.span().merge(close.span()),
+    })
+  }
+
+  /// Parses `{ r without name; age }`.
+  fn expect_restriction(&mut self) -> Result<surface::Restriction>
+  {
+    let open = self.expect(Token::Symbol("{"))?;
+    let record = self.expect_expression()?;
+    let _ = self.expect(Token::Keyword("without"))?;
+    let mut fields = vec![self.expect_identifier()?];
+    while let Ok(field) = self.breakpoint(|s| {
+      let _ = s.expect(Token::Keyword(";"))?;
+      s.expect_identifier()
+    }) {
+      fields.push(field);
+    }
+    let close = self.expect(Token::Symbol("}"))?;
+    Ok(surface::Restriction {
+      record,
+      fields,
+      span: open.span().merge(close.span()),
+    })
+  }
+
+  /// Parses one or more fields separated by `;`.
+  fn expect_fields(&mut self) -> Result<Vec<surface::Field>>
+  {
+    let mut fields = vec![self.expect_field()?];
+    while let Ok(field) = self.breakpoint(|s| {
+      let _ = s.expect(Token::Keyword(";"))?;
+      s.expect_field()
+    }) {
+      fields.push(field);
+    }
+    Ok(fields)
+  }
+
+  fn expect_field(&mut self) -> Result<surface::Field>
+  {
+    let name = self.expect_identifier()?;
+    let _ = self.expect(Token::Keyword("="))?;
+    let value = self.expect_expression()?;
+    Ok(surface::Field {
+      name,
+      value,
+    })
+  }
+}
+
+impl<Lexer> RecordParser for Lexer
+where
+  Self: ExpectSyntax,
+  Self: CanBacktrack,
+  Self: HasFixities,
+{
+}
+
+#[cfg(test)]
+mod spec
+{
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+  use crate::frontend::lexer::Lexer;
+
+  fn field(
+    name: &str,
+    value: surface::Expression,
+  ) -> surface::Field
+  {
+    surface::Field {
+      name: surface::Identifier::new(name),
+      value,
+    }
+  }
+
+  #[test]
+  fn can_parse_record()
+  {
+    let mut lexer =
+      Lexer::from_str("{ name = `a`; age = f x }").with_backtracking();
+    assert_eq!(
+      lexer.expect_expression(),
+      Ok(
+        surface::Record {
+          fields: vec![
+            field("name", surface::Literal::String("a".into()).into()),
+            field(
+              "age",
+              surface::Application {
+                abstraction: surface::Identifier::new("f").into(),
+                arguments: vec![surface::Identifier::new("x").into()],
+                span: Default::default(),
+              }
+              .into()
+            ),
+          ],
+          span: Default::default(),
+        }
+        .into()
+      )
+    );
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn can_parse_update()
+  {
+    let mut lexer = Lexer::from_str("{ r with age = 1 }").with_backtracking();
+    assert_eq!(
+      lexer.expect_expression(),
+      Ok(
+        surface::Update {
+          record: surface::Identifier::new("r").into(),
+          fields: vec![field("age", surface::Literal::Integer(1).into())],
+          span: Default::default(),
+        }
+        .into()
+      )
+    );
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn can_parse_extension_and_restriction()
+  {
+    let mut lexer = Lexer::from_str("{ id = 1 | { r without name; age } }")
+      .with_backtracking();
+    assert_eq!(
+      lexer.expect_expression(),
+      Ok(
+        surface::Extension {
+          fields: vec![field("id", surface::Literal::Integer(1).into())],
+          record: surface::Restriction {
+            record: surface::Identifier::new("r").into(),
+            fields: vec![
+              surface::Identifier::new("name"),
+              surface::Identifier::new("age"),
+            ],
+            span: Default::default(),
+          }
+          .into(),
+          span: Default::default(),
+        }
+        .into()
+      )
+    );
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn record_spans_braces()
+  {
+    let mut lexer = Lexer::from_str(" { a = 1 }").with_backtracking();
+    let span = lexer
+      .expect_expression()
+      .unwrap()
+      .span();
+    assert_eq!((span.start.offset, span.end.offset), (1, 10));
+  }
+
+  #[test]
+  fn missing_value()
+  {
+    let mut lexer = Lexer::from_str("{ a }").with_backtracking();
+    assert_eq!(
+      lexer.expect_record(),
+      Err(ParseError::UnexpectedToken {
+        expected: Token::Keyword("="),
+        actual: Lexeme::symbol("}"),
+      })
+    );
+  }
+}
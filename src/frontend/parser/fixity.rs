@@ -0,0 +1,207 @@
+use std::collections::HashMap;
+
+use super::BacktrackingIterator;
+use crate::frontend::lexemes::Lexeme;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Associativity
+{
+  Left,
+  Right,
+  None,
+}
+
+/// How tightly an infix operator binds, operators of a higher precedence are
+/// applied first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fixity
+{
+  pub associativity: Associativity,
+  pub precedence: u8,
+}
+
+impl Fixity
+{
+  pub const DEFAULT: Fixity = Fixity::left(9);
+  pub const MAXIMUM_PRECEDENCE: u8 = 9;
+
+  pub const fn left(precedence: u8) -> Self
+  {
+    Self {
+      associativity: Associativity::Left,
+      precedence,
+    }
+  }
+
+  pub const fn right(precedence: u8) -> Self
+  {
+    Self {
+      associativity: Associativity::Right,
+      precedence,
+    }
+  }
+
+  pub const fn none(precedence: u8) -> Self
+  {
+    Self {
+      associativity: Associativity::None,
+      precedence,
+    }
+  }
+
+  pub fn keyword(self) -> &'static str
+  {
+    match self.associativity {
+      | Associativity::Left => "infixl",
+      | Associativity::Right => "infixr",
+      | Associativity::None => "infix",
+    }
+  }
+}
+
+/// `infixl 6 + -`, gives every listed operator the same fixity.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixityDeclaration
+{
+  pub fixity: Fixity,
+  pub operators: Vec<String>,
+}
+
+impl std::fmt::Display for FixityDeclaration
+{
+  fn fmt(
+    &self,
+    f: &mut std::fmt::Formatter<'_>,
+  ) -> std::fmt::Result
+  {
+    write!(
+      f,
+      "{} {} {}",
+      self.fixity.keyword(),
+      self.fixity.precedence,
+      self.operators.join(" ")
+    )
+  }
+}
+
+/// The fixity of every operator, operators that were never declared are
+/// `infixl 9`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fixities
+{
+  operators: HashMap<String, Fixity>,
+}
+
+impl Fixities
+{
+  pub fn get(
+    &self,
+    operator: &str,
+  ) -> Fixity
+  {
+    self
+      .operators
+      .get(operator)
+      .copied()
+      .unwrap_or(Fixity::DEFAULT)
+  }
+
+  pub fn declare(
+    &mut self,
+    declaration: &FixityDeclaration,
+  )
+  {
+    for operator in &declaration.operators {
+      self
+        .operators
+        .insert(operator.clone(), declaration.fixity);
+    }
+  }
+}
+
+impl Default for Fixities
+{
+  fn default() -> Self
+  {
+    let defaults = [
+      ("|>", Fixity::left(1)),
+      ("||", Fixity::right(2)),
+      ("&&", Fixity::right(3)),
+      ("=", Fixity::left(4)),
+      ("<", Fixity::left(4)),
+      ("<=", Fixity::left(4)),
+      (">", Fixity::left(4)),
+      (">=", Fixity::left(4)),
+      ("::", Fixity::right(5)),
+      ("@", Fixity::right(5)),
+      ("^", Fixity::right(5)),
+      ("+", Fixity::left(6)),
+      ("-", Fixity::left(6)),
+      ("+.", Fixity::left(6)),
+      ("-.", Fixity::left(6)),
+      ("*", Fixity::left(7)),
+      ("/", Fixity::left(7)),
+      ("*.", Fixity::left(7)),
+      ("/.", Fixity::left(7)),
+    ];
+    Self {
+      operators: defaults
+        .into_iter()
+        .map(|(operator, fixity)| (operator.to_string(), fixity))
+        .collect(),
+    }
+  }
+}
+
+/// A parser that knows the fixity of every operator in scope.
+pub trait HasFixities
+{
+  fn fixities(&self) -> &Fixities;
+
+  fn fixities_mut(&mut self) -> &mut Fixities;
+}
+
+impl<Lexer> HasFixities for BacktrackingIterator<Lexer>
+where
+  Lexer: Iterator<Item = Lexeme>,
+{
+  fn fixities(&self) -> &Fixities
+  {
+    &self.fixities
+  }
+
+  fn fixities_mut(&mut self) -> &mut Fixities
+  {
+    &mut self.fixities
+  }
+}
+
+#[cfg(test)]
+mod spec
+{
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn undeclared_operators_are_infixl_9()
+  {
+    let fixities = Fixities::default();
+    assert_eq!(fixities.get("+"), Fixity::left(6));
+    assert_eq!(fixities.get("<*>"), Fixity::DEFAULT);
+  }
+
+  #[test]
+  fn declarations_override_defaults()
+  {
+    let mut fixities = Fixities::default();
+    let declaration = FixityDeclaration {
+      fixity: Fixity::none(3),
+      operators: vec!["+".into(), "<*>".into()],
+    };
+    fixities.declare(&declaration);
+    assert_eq!(fixities.get("+"), Fixity::none(3));
+    assert_eq!(fixities.get("<*>"), Fixity::none(3));
+    assert_eq!(declaration.to_string(), "infix 3 + <*>");
+  }
+}
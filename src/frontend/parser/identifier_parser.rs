@@ -8,8 +8,79 @@ where
   fn expect_identifier(&mut self) -> Result<surface::Identifier>
   {
     let name = self.expect(Token::Identifier)?;
-    return Ok(surface::Identifier {
+    Ok(surface::Identifier {
       name: name.value().into(),
+      span: name.span(),
+    })
+  }
+
+  /// Parses the name of a constructor, which starts with a capital letter.
+  fn expect_constructor_name(&mut self) -> Result<surface::Identifier>
+  {
+    self.expect_capitalised(NodeType::Constructor)
+  }
+
+  /// Parses the name of a structure, which starts with a capital letter.
+  fn expect_structure_name(&mut self) -> Result<surface::Identifier>
+  {
+    self.expect_capitalised(NodeType::Structure)
+  }
+
+  fn expect_capitalised(
+    &mut self,
+    expected: NodeType,
+  ) -> Result<surface::Identifier>
+  {
+    match self.peek() {
+      | Some(lexeme)
+        if lexeme.token() == &Token::Identifier
+          && lexeme
+            .value()
+            .starts_with(char::is_uppercase) =>
+        self.expect_identifier(),
+      | actual => Err(ParseError::Expected {
+        expected,
+        actual,
+      }),
+    }
+  }
+
+  /// Parses `Foo.Bar`, the structure `Bar` nested in `Foo`.
+  fn expect_structure_path(&mut self) -> Result<Vec<surface::Identifier>>
+  {
+    let mut path = vec![self.expect_structure_name()?];
+    while let Ok(name) = self.breakpoint(|s| {
+      let _ = s.expect(Token::Symbol("."))?;
+      s.expect_structure_name()
+    }) {
+      path.push(name);
+    }
+    Ok(path)
+  }
+
+  /// Parses `Foo.Bar.baz`. Every capitalised name followed by another is a
+  /// structure, so that `Foo.Some` is the constructor `Some` of `Foo`.
+  fn expect_qualified(&mut self) -> Result<surface::Qualified>
+  {
+    let mut path = vec![self.expect_structure_name()?];
+    let _ = self.expect(Token::Symbol("."))?;
+    let mut name = self.expect_identifier()?;
+    while name
+      .name
+      .starts_with(char::is_uppercase)
+    {
+      match self.breakpoint(|s| {
+        let _ = s.expect(Token::Symbol("."))?;
+        s.expect_identifier()
+      }) {
+        | Ok(member) => path.push(std::mem::replace(&mut name, member)),
+        | Err(_) => break,
+      }
+    }
+    Ok(surface::Qualified {
+      span: path[0].span.merge(name.span),
+      path,
+      name,
     })
   }
 }
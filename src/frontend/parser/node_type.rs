@@ -4,7 +4,39 @@ pub enum NodeType
   Literal,
   BooleanLiteral,
   Expression,
+  Type,
   Declaration,
-  ValBinding,
-  DefBinding,
+  Operator,
+  Precedence,
+  Pattern,
+  TypeVariable,
+  Constructor,
+  Structure,
+  TupleIndex,
+  Name,
+}
+
+impl std::fmt::Display for NodeType
+{
+  fn fmt(
+    &self,
+    f: &mut std::fmt::Formatter<'_>,
+  ) -> std::fmt::Result
+  {
+    match self {
+      | NodeType::Literal => write!(f, "literal"),
+      | NodeType::BooleanLiteral => write!(f, "boolean literal"),
+      | NodeType::Expression => write!(f, "expression"),
+      | NodeType::Type => write!(f, "type"),
+      | NodeType::Declaration => write!(f, "declaration"),
+      | NodeType::Operator => write!(f, "operator"),
+      | NodeType::Precedence => write!(f, "precedence between 0 and 9"),
+      | NodeType::Pattern => write!(f, "pattern"),
+      | NodeType::TypeVariable => write!(f, "type variable"),
+      | NodeType::Constructor => write!(f, "constructor, a capitalised name"),
+      | NodeType::Structure => write!(f, "structure, a capitalised name"),
+      | NodeType::TupleIndex => write!(f, "tuple index"),
+      | NodeType::Name => write!(f, "name such as `x` or `(+)`"),
+    }
+  }
 }
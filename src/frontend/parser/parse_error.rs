@@ -1,26 +1,367 @@
 use thiserror::Error;
 
 use super::NodeType;
+use crate::diagnostics::{
+  Anchor,
+  Diagnostic,
+  ToDiagnostic,
+};
 use crate::frontend::lexemes::Lexeme;
 use crate::frontend::tokens::Token;
+use crate::syntax::surface::{
+  self,
+  Location,
+};
 
 #[derive(Debug, Clone, PartialEq, Error)]
 pub enum ParseError
 {
-  #[error("unexpected end of input")]
+  #[error("unexpected end of input, expected {expected}")]
   UnexpectedEndOfInput
   {
     expected: Token,
   },
-  #[error("unexpected token")]
+  #[error("expected {expected}, found {actual}")]
   UnexpectedToken
   {
     expected: Token,
     actual: Lexeme,
   },
-  #[error("expected syntax ...")]
+  #[error("expected end of input, found {actual}")]
+  ExpectedEndOfInput
+  {
+    actual: Lexeme,
+  },
+  #[error("expected {expected}, found {}", found(.actual))]
   Expected
   {
     expected: NodeType,
+    actual: Option<Lexeme>,
   },
+  #[error(
+    "`{}` cannot follow a non-associative operator of the same precedence",
+    .operator.name
+  )]
+  ChainedOperator
+  {
+    operator: surface::Identifier,
+    previous: surface::Span,
+  },
+}
+
+impl ParseError
+{
+  /// Whether parsing got further into the input before failing than it did
+  /// for `other`, running out of input is as far as it gets.
+  pub fn is_further_than(
+    &self,
+    other: &ParseError,
+  ) -> bool
+  {
+    match (self.reached(), other.reached()) {
+      | (None, Some(_)) => true,
+      | (Some(this), Some(that)) => this > that,
+      | _ => false,
+    }
+  }
+
+  /// The end of the lexeme parsing failed at, `None` at the end of input.
+  pub(super) fn reached(&self) -> Option<Location>
+  {
+    match self {
+      | ParseError::UnexpectedEndOfInput {
+        ..
+      } => None,
+      | ParseError::UnexpectedToken {
+        actual,
+        ..
+      }
+      | ParseError::ExpectedEndOfInput {
+        actual,
+      } => Some(actual.span().end),
+      | ParseError::Expected {
+        actual,
+        ..
+      } => actual
+        .as_ref()
+        .map(|actual| actual.span().end),
+      | ParseError::ChainedOperator {
+        operator,
+        ..
+      } => Some(operator.span.end),
+    }
+  }
+}
+
+fn found(actual: &Option<Lexeme>) -> String
+{
+  match actual {
+    | Some(lexeme) => lexeme.to_string(),
+    | None => "end of input".to_string(),
+  }
+}
+
+/// Explains lexemes that can never be valid, whatever was expected.
+fn lexical_note(actual: &Lexeme) -> Option<&'static str>
+{
+  match actual.token() {
+    | Token::UnclosedComment =>
+      Some("comments opened with `(*` must be closed with `*)`"),
+    | Token::UnclosedString =>
+      Some("string literals must be closed with a backtick"),
+    | Token::MalformedNumericLiteral => Some(
+      "numeric literals are integers such as `42`, `0x2A` or `0b101010`, or \
+       floats such as `4.2` or `42e-1`, that fit in 64 bits",
+    ),
+    | _ => None,
+  }
+}
+
+impl ToDiagnostic for ParseError
+{
+  fn to_diagnostic(&self) -> Diagnostic
+  {
+    let (anchor, label, actual) = match self {
+      | ParseError::UnexpectedEndOfInput {
+        expected,
+      } => (Anchor::EndOfInput, format!("expected {}", expected), None),
+      | ParseError::UnexpectedToken {
+        expected,
+        actual,
+      } =>
+        (actual.span().into(), format!("expected {}", expected), Some(actual)),
+      | ParseError::ExpectedEndOfInput {
+        actual,
+      } => (actual.span().into(), "unexpected input".to_string(), Some(actual)),
+      | ParseError::Expected {
+        expected,
+        actual,
+      } => (
+        actual
+          .as_ref()
+          .map_or(Anchor::EndOfInput, |actual| actual.span().into()),
+        format!("expected {}", expected),
+        actual.as_ref(),
+      ),
+      | ParseError::ChainedOperator {
+        operator,
+        previous,
+      } =>
+        return Diagnostic::error(self.to_string())
+          .with_primary(operator.span, "cannot be chained")
+          .with_secondary(*previous, "with this operator")
+          .with_note("add parentheses to say which operation comes first"),
+    };
+    let diagnostic =
+      Diagnostic::error(self.to_string()).with_primary(anchor, label);
+    match actual.and_then(lexical_note) {
+      | Some(note) => diagnostic.with_note(note),
+      | None => diagnostic,
+    }
+  }
+}
+
+#[cfg(test)]
+mod spec
+{
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+  use crate::diagnostics::{
+    OutputMode,
+    Renderer,
+  };
+  use crate::frontend::lexer::Lexer;
+  use crate::frontend::parser::{
+    DeclarationParser,
+    Fixities,
+    Fixity,
+    FixityDeclaration,
+    WithBacktracking,
+  };
+
+  fn render(source: &str) -> String
+  {
+    let mut lexer = Lexer::from_str(source).with_backtracking();
+    let error = lexer.expect_val_binding().unwrap_err();
+    Renderer::new("main.ml", source, OutputMode::Plain)
+      .render(&error.to_diagnostic())
+  }
+
+  #[test]
+  fn unexpected_token()
+  {
+    assert_eq!(
+      render("val foo bar ;"),
+      [
+        "error: expected `=`, found identifier `bar`",
+        " --> main.ml:1:9",
+        "  |",
+        "1 | val foo bar ;",
+        "  |         ^^^ expected `=`",
+        "  |",
+        "",
+      ]
+      .join("\n")
+    );
+  }
+
+  #[test]
+  fn unexpected_end_of_input()
+  {
+    assert_eq!(
+      render("val foo = 10"),
+      [
+        "error: unexpected end of input, expected `;`",
+        " --> main.ml:1:13",
+        "  |",
+        "1 | val foo = 10",
+        "  |             ^ expected `;`",
+        "  |",
+        "",
+      ]
+      .join("\n")
+    );
+  }
+
+  #[test]
+  fn expected_node()
+  {
+    assert_eq!(
+      render("val foo =\n  ) ;"),
+      [
+        "error: expected expression, found `)`",
+        " --> main.ml:2:3",
+        "  |",
+        "2 |   ) ;",
+        "  |   ^ expected expression",
+        "  |",
+        "",
+      ]
+      .join("\n")
+    );
+  }
+
+  #[test]
+  fn errors_point_at_the_furthest_token_reached()
+  {
+    assert_eq!(
+      render("val foo =\n  let x = 1 in\n  match x with\n  | 1 -> ) ;"),
+      [
+        "error: expected expression, found `)`",
+        " --> main.ml:4:10",
+        "  |",
+        "4 |   | 1 -> ) ;",
+        "  |          ^ expected expression",
+        "  |",
+        "",
+      ]
+      .join("\n")
+    );
+    assert_eq!(
+      render("val foo = let x = (1 ; in x ;"),
+      [
+        "error: expected `)`, found `;`",
+        " --> main.ml:1:22",
+        "  |",
+        "1 | val foo = let x = (1 ; in x ;",
+        "  |                      ^ expected `)`",
+        "  |",
+        "",
+      ]
+      .join("\n")
+    );
+  }
+
+  #[test]
+  fn failures_at_the_same_token_report_the_innermost_construct()
+  {
+    assert_eq!(
+      render("val r = { a | r } ;"),
+      [
+        "error: expected `=`, found `|`",
+        " --> main.ml:1:13",
+        "  |",
+        "1 | val r = { a | r } ;",
+        "  |             ^ expected `=`",
+        "  |",
+        "",
+      ]
+      .join("\n")
+    );
+    assert_eq!(
+      render("val z = let (x, y) = (1, 2) in x ;"),
+      [
+        "error: expected name such as `x` or `(+)`, found `(`",
+        " --> main.ml:1:13",
+        "  |",
+        "1 | val z = let (x, y) = (1, 2) in x ;",
+        "  |             ^ expected name such as `x` or `(+)`",
+        "  |",
+        "",
+      ]
+      .join("\n")
+    );
+  }
+
+  #[test]
+  fn chained_non_associative_operators()
+  {
+    let source = "val b = 1 == 1 == true ;";
+    let mut fixities = Fixities::default();
+    fixities.declare(&FixityDeclaration {
+      fixity: Fixity::none(4),
+      operators: vec!["==".into()],
+    });
+    let error = Lexer::from_str(source)
+      .with_backtracking()
+      .with_fixities(fixities)
+      .expect_val_binding()
+      .unwrap_err();
+    assert_eq!(
+      Renderer::new("main.ml", source, OutputMode::Plain)
+        .render(&error.to_diagnostic()),
+      [
+        "error: `==` cannot follow a non-associative operator of the same \
+         precedence",
+        " --> main.ml:1:16",
+        "  |",
+        "1 | val b = 1 == 1 == true ;",
+        "  |           -- with this operator",
+        "  |                ^^ cannot be chained",
+        "  |",
+        "  = note: add parentheses to say which operation comes first",
+        "",
+      ]
+      .join("\n")
+    );
+  }
+
+  #[test]
+  fn malformed_literal_has_a_note()
+  {
+    assert_eq!(
+      render("val foo = 1.1.1 ;"),
+      [
+        "error: expected expression, found malformed numeric literal `1.1.1`",
+        " --> main.ml:1:11",
+        "  |",
+        "1 | val foo = 1.1.1 ;",
+        "  |           ^^^^^ expected expression",
+        "  |",
+        "  = note: numeric literals are integers such as `42`, `0x2A` or \
+         `0b101010`, or floats such as `4.2` or `42e-1`, that fit in 64 bits",
+        "",
+      ]
+      .join("\n")
+    );
+  }
+
+  #[test]
+  fn overflowing_float_is_malformed()
+  {
+    assert!(render("val foo = 1.0e999 ;").starts_with(
+      "error: expected expression, found malformed numeric literal `1.0e999`"
+    ));
+  }
 }
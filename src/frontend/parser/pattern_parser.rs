@@ -0,0 +1,280 @@
+use super::*;
+
+pub trait PatternParser
+where
+  Self: Sized,
+  Self: ExpectSyntax,
+  Self: CanBacktrack,
+  Self: LiteralParser,
+{
+  /// Parses a list pattern such as `x :: xs`, where `::` associates to the
+  /// right, or any pattern that can be its head.
+  fn expect_pattern(&mut self) -> Result<surface::Pattern>
+  {
+    let head = self.expect_constructor_pattern()?;
+    let cons = self.breakpoint(|s| match s.next() {
+      | Some(lexeme)
+        if lexeme.token() == &Token::Operator && lexeme.value() == "::" =>
+        Ok(()),
+      | actual => Err(ParseError::Expected {
+        expected: NodeType::Pattern,
+        actual,
+      }),
+    });
+    match cons {
+      | Ok(()) => Ok(surface::Pattern::Cons(
+        Box::new(head),
+        Box::new(self.expect_pattern()?),
+      )),
+      | Err(_) => Ok(head),
+    }
+  }
+
+  /// Parses a constructor applied to a pattern such as `Some (x, _)`, or any
+  /// pattern that needs no parentheses.
+  fn expect_constructor_pattern(&mut self) -> Result<surface::Pattern>
+  {
+    attempt!(self as s => {
+      let (path, name) = s.expect_constructor()?;
+      let argument = s.expect_atomic_pattern()?;
+      Ok(surface::Pattern::Constructor(path, name, Some(Box::new(argument))))
+    });
+    self.expect_atomic_pattern()
+  }
+
+  /// Parses a constructor such as `Some`, or `Option.Some` along with the path
+  /// of structures it is a member of.
+  fn expect_constructor(
+    &mut self
+  ) -> Result<(Vec<surface::Identifier>, surface::Identifier)>
+  {
+    attempt!(self as s => {
+      let qualified = s.expect_qualified()?;
+      match qualified.name.name.starts_with(char::is_uppercase) {
+        | true => Ok((qualified.path, qualified.name)),
+        | false => Err(ParseError::Expected {
+          expected: NodeType::Constructor,
+          actual: s.peek(),
+        }),
+      }
+    });
+    Ok((vec![], self.expect_constructor_name()?))
+  }
+
+  /// Parses `x`, `_`, a literal, a constructor on its own, a parenthesised
+  /// pattern, a tuple of patterns such as `(x, (y, z))` or a list of
+  /// patterns such as `[x, _]`.
+  fn expect_atomic_pattern(&mut self) -> Result<surface::Pattern>
+  {
+    attempt!(self as s => {
+      let _ = s.expect(Token::Symbol("("))?;
+      let pattern = s.expect_pattern()?;
+      let _ = s.expect(Token::Symbol(")"))?;
+      Ok(pattern)
+    });
+    attempt!(self as s => {
+      let open = s.expect(Token::Symbol("("))?;
+      let mut elements = vec![s.expect_pattern()?];
+      let close = loop {
+        let _ = s.expect(Token::Symbol(","))?;
+        elements.push(s.expect_pattern()?);
+        if let Ok(close) = s.breakpoint(|s| s.expect(Token::Symbol(")"))) {
+          break close
+        }
+      };
+      Ok(surface::Pattern::Tuple(elements, open.span().merge(close.span())))
+    });
+    attempt!(self as s => {
+      let open = s.expect(Token::Symbol("["))?;
+      let mut elements = vec![];
+      let close = loop {
+        if let Ok(close) = s.breakpoint(|s| s.expect(Token::Symbol("]"))) {
+          break close
+        }
+        if !elements.is_empty() {
+          let _ = s.expect(Token::Symbol(","))?;
+        }
+        elements.push(s.expect_pattern()?);
+      };
+      Ok(surface::Pattern::List(elements, open.span().merge(close.span())))
+    });
+    attempt!(self as s => {
+      let span = s.peek().map(|lexeme| lexeme.span()).unwrap_or_default();
+      let literal = s.expect_literal()?;
+      Ok(surface::Pattern::Literal(literal, span))
+    });
+    attempt!(self as s => {
+      let (path, name) = s.expect_constructor()?;
+      Ok(surface::Pattern::Constructor(path, name, None))
+    });
+    attempt!(self as s => {
+      let identifier = s.expect_identifier()?;
+      Ok(match identifier.name.as_str() {
+        | "_" => surface::Pattern::Wildcard(identifier.span),
+        | _ => identifier.into(),
+      })
+    });
+    Err(ParseError::Expected {
+      expected: NodeType::Pattern,
+      actual: self.peek(),
+    })
+  }
+}
+
+impl<Lexer> PatternParser for Lexer
+where
+  Self: ExpectSyntax,
+  Self: CanBacktrack,
+{
+}
+
+#[cfg(test)]
+mod spec
+{
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+  use crate::frontend::lexer::Lexer;
+
+  fn name(name: &str) -> surface::Pattern
+  {
+    surface::Identifier::new(name).into()
+  }
+
+  #[test]
+  fn can_parse_nested_tuples()
+  {
+    let mut lexer = Lexer::from_str("(x, ((y)), (z, w))").with_backtracking();
+    assert_eq!(
+      lexer.expect_pattern(),
+      Ok(surface::Pattern::Tuple(
+        vec![
+          name("x"),
+          name("y"),
+          surface::Pattern::Tuple(
+            vec![name("z"), name("w")],
+            Default::default()
+          ),
+        ],
+        Default::default()
+      ))
+    );
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn tuple_span_covers_the_parentheses()
+  {
+    let mut lexer = Lexer::from_str(" (x, y) ").with_backtracking();
+    let Ok(surface::Pattern::Tuple(_, span)) = lexer.expect_pattern()
+    else {
+      panic!("expected a tuple pattern")
+    };
+    assert_eq!((span.start.offset, span.end.offset), (1, 7));
+  }
+
+  #[test]
+  fn constructors_take_an_atomic_pattern()
+  {
+    let mut lexer = Lexer::from_str("Some (None, _, 1) x").with_backtracking();
+    assert_eq!(
+      lexer.expect_pattern(),
+      Ok(surface::Pattern::Constructor(
+        vec![],
+        surface::Identifier::new("Some"),
+        Some(Box::new(surface::Pattern::Tuple(
+          vec![
+            surface::Pattern::Constructor(
+              vec![],
+              surface::Identifier::new("None"),
+              None
+            ),
+            surface::Pattern::Wildcard(Default::default()),
+            surface::Pattern::Literal(
+              surface::Literal::Integer(1),
+              Default::default()
+            ),
+          ],
+          Default::default()
+        )))
+      ))
+    );
+    assert_eq!(lexer.next(), Some(Lexeme::identifier("x")));
+  }
+
+  #[test]
+  fn constructors_may_be_qualified_by_their_structure()
+  {
+    let mut lexer =
+      Lexer::from_str("A.B.Some Option.None x").with_backtracking();
+    assert_eq!(
+      lexer.expect_pattern(),
+      Ok(surface::Pattern::Constructor(
+        vec![surface::Identifier::new("A"), surface::Identifier::new("B")],
+        surface::Identifier::new("Some"),
+        Some(Box::new(surface::Pattern::Constructor(
+          vec![surface::Identifier::new("Option")],
+          surface::Identifier::new("None"),
+          None
+        )))
+      ))
+    );
+    assert_eq!(lexer.next(), Some(Lexeme::identifier("x")));
+  }
+
+  #[test]
+  fn atomic_patterns_need_parentheses_around_arguments()
+  {
+    let mut lexer = Lexer::from_str("Some x").with_backtracking();
+    assert_eq!(
+      lexer.expect_atomic_pattern(),
+      Ok(surface::Pattern::Constructor(
+        vec![],
+        surface::Identifier::new("Some"),
+        None
+      ))
+    );
+    assert_eq!(lexer.next(), Some(Lexeme::identifier("x")));
+  }
+
+  #[test]
+  fn cons_patterns_associate_to_the_right()
+  {
+    let mut lexer =
+      Lexer::from_str("Some x :: [_, (y :: ys)] :: []").with_backtracking();
+    let cons =
+      |head, tail| surface::Pattern::Cons(Box::new(head), Box::new(tail));
+    let list = |elements| surface::Pattern::List(elements, Default::default());
+    assert_eq!(
+      lexer.expect_pattern(),
+      Ok(cons(
+        surface::Pattern::Constructor(
+          vec![],
+          surface::Identifier::new("Some"),
+          Some(Box::new(name("x")))
+        ),
+        cons(
+          list(vec![
+            surface::Pattern::Wildcard(Default::default()),
+            cons(name("y"), name("ys")),
+          ]),
+          list(vec![])
+        )
+      ))
+    );
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn missing_pattern()
+  {
+    let mut lexer = Lexer::from_str("->").with_backtracking();
+    assert_eq!(
+      lexer.expect_pattern(),
+      Err(ParseError::Expected {
+        expected: NodeType::Pattern,
+        actual: Some(Lexeme::keyword("->")),
+      })
+    );
+  }
+}
@@ -0,0 +1,401 @@
+use super::*;
+use crate::syntax::surface::types;
+
+pub trait TypeParser
+where
+  Self: Sized,
+  Self: ExpectSyntax,
+  Self: CanBacktrack,
+{
+  /// Parses a type variable such as `'a`, named without the quote.
+  fn expect_type_variable(&mut self) -> Result<surface::Identifier>
+  {
+    match self.peek() {
+      | Some(lexeme)
+        if lexeme.token() == &Token::Identifier
+          && lexeme.value().starts_with('\'') =>
+      {
+        let identifier = self.expect_identifier()?;
+        Ok(surface::Identifier {
+          name: identifier.name[1 ..].into(),
+          span: identifier.span,
+        })
+      },
+      | actual => Err(ParseError::Expected {
+        expected: NodeType::TypeVariable,
+        actual,
+      }),
+    }
+  }
+
+  /// Parses the name of a type such as `Int` or `option`.
+  fn expect_type_name(&mut self) -> Result<surface::Identifier>
+  {
+    match self.peek() {
+      | Some(lexeme)
+        if lexeme.token() == &Token::Identifier
+          && !lexeme.value().starts_with('\'') =>
+        self.expect_identifier(),
+      | actual => Err(ParseError::Expected {
+        expected: NodeType::Type,
+        actual,
+      }),
+    }
+  }
+
+  /// Parses the name of a type along with the path of structures it is a
+  /// member of, such as `Option.t`.
+  fn expect_type_constant(&mut self) -> Result<types::Constant>
+  {
+    attempt!(self as s => {
+      let qualified = s.expect_qualified()?;
+      Ok(types::Constant {
+        path: qualified.path,
+        name: qualified.name,
+        declaration: None,
+      })
+    });
+    Ok(self.expect_type_name()?.into())
+  }
+
+  /// Parses a named type such as `Int` or `Option.t`, a type variable such as
+  /// `'a`, a list type such as `List Int`, a record type such as
+  /// `{ name : String; age : Int }` or `{ name : String | 'r }` that has the
+  /// fields of `'r` too, or a parenthesised type.
+  fn expect_type_value(&mut self) -> Result<types::Type>
+  {
+    attempt!(self as s => {
+      let _ = s.expect(Token::Symbol("("))?;
+      let inner_type = s.expect_type()?;
+      let _ = s.expect(Token::Symbol(")"))?;
+      Ok(inner_type)
+    });
+    attempt!(self as s => {
+      let _ = s.expect(Token::Symbol("{"))?;
+      let mut fields = vec![s.expect_field_type()?];
+      while let Ok(field) = s.breakpoint(|s| {
+        let _ = s.expect(Token::Keyword(";"))?;
+        s.expect_field_type()
+      }) {
+        fields.push(field);
+      }
+      let rest = s.breakpoint(|s| {
+        let _ = s.expect(Token::Keyword("|"))?;
+        s.expect_type_variable()
+      });
+      let _ = s.expect(Token::Symbol("}"))?;
+      Ok(types::Type::record(fields, rest.ok().map(types::Variable::Named)))
+    });
+    attempt!(self as s => {
+      let _ = s.expect(Token::Symbol("{"))?;
+      let _ = s.expect(Token::Keyword("|"))?;
+      let rest = s.expect_type_variable()?;
+      let _ = s.expect(Token::Symbol("}"))?;
+      Ok(types::Type::record(vec![], Some(types::Variable::Named(rest))))
+    });
+    attempt!(self as s => {
+      let name = s.expect_type_name()?;
+      match name.name.as_str() {
+        | "List" => Ok(types::Type::list(s.expect_type_value()?)),
+        | _ => Err(ParseError::Expected {
+          expected: NodeType::Type,
+          actual: s.peek(),
+        }),
+      }
+    });
+    attempt!(self as s => Ok(types::Variable::Named(s.expect_type_variable()?).into()));
+    attempt!(self as s => Ok(types::Type::Concrete(s.expect_type_constant()?, vec![])));
+    Err(ParseError::Expected {
+      expected: NodeType::Type,
+      actual: self.peek(),
+    })
+  }
+
+  /// Parses `name : T`, a field of a record type.
+  fn expect_field_type(&mut self)
+    -> Result<(surface::Identifier, types::Type)>
+  {
+    let name = self.expect_identifier()?;
+    let _ = self.expect(Token::Keyword(":"))?;
+    Ok((name, self.expect_type()?))
+  }
+
+  /// Parses a type followed by the names of the types it is an argument of,
+  /// such as `'a option list`. Several arguments are written between
+  /// parentheses, as in `(Int, String) either`.
+  fn expect_applied_type(&mut self) -> Result<types::Type>
+  {
+    let arguments = self.breakpoint(|s| {
+      let _ = s.expect(Token::Symbol("("))?;
+      let mut arguments = vec![s.expect_type()?];
+      loop {
+        let _ = s.expect(Token::Symbol(","))?;
+        arguments.push(s.expect_type()?);
+        if s
+          .breakpoint(|s| s.expect(Token::Symbol(")")))
+          .is_ok()
+        {
+          break
+        }
+      }
+      Ok(types::Type::Concrete(s.expect_type_constant()?, arguments))
+    });
+    let mut typ = match arguments {
+      | Ok(typ) => typ,
+      | Err(_) => self.expect_type_value()?,
+    };
+    while let Ok(constant) = self.breakpoint(|s| s.expect_type_constant()) {
+      typ = types::Type::Concrete(constant, vec![typ]);
+    }
+    Ok(typ)
+  }
+
+  /// Parses a product such as `Int * String`, which binds tighter than `->`.
+  fn expect_product_type(&mut self) -> Result<types::Type>
+  {
+    let mut elements = vec![self.expect_applied_type()?];
+    while let Ok(element) = self.breakpoint(|s| {
+      match s.next() {
+        | Some(lexeme)
+          if lexeme.token() == &Token::Operator && lexeme.value() == "*" =>
+          (),
+        | actual =>
+          return Err(ParseError::Expected {
+            expected: NodeType::Type,
+            actual,
+          }),
+      }
+      s.expect_applied_type()
+    }) {
+      elements.push(element);
+    }
+    Ok(match elements.len() {
+      | 1 => elements.remove(0),
+      | _ => types::Type::Tuple(elements),
+    })
+  }
+
+  /// Parses a type, `->` associates to the right.
+  fn expect_type(&mut self) -> Result<types::Type>
+  {
+    let parameter_type = self.expect_product_type()?;
+    match self.breakpoint(|s| s.expect(Token::Keyword("->"))) {
+      | Ok(_) =>
+        Ok(types::Type::abstraction(parameter_type, self.expect_type()?)),
+      | Err(_) => Ok(parameter_type),
+    }
+  }
+}
+
+impl<Lexer> TypeParser for Lexer
+where
+  Self: ExpectSyntax,
+  Self: CanBacktrack,
+{
+}
+
+#[cfg(test)]
+mod spec
+{
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+  use crate::frontend::lexer::Lexer;
+
+  fn numeric() -> types::Type
+  {
+    surface::Identifier::new("Int").into()
+  }
+
+  #[test]
+  fn can_parse_named_type()
+  {
+    let mut lexer = Lexer::from_str("Int").with_backtracking();
+    assert_eq!(lexer.expect_type(), Ok(numeric()));
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn can_parse_type_variable()
+  {
+    let mut lexer = Lexer::from_str("'a").with_backtracking();
+    assert_eq!(
+      lexer.expect_type(),
+      Ok(types::Variable::Named(surface::Identifier::new("a")).into())
+    );
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn abstractions_associate_to_the_right()
+  {
+    let mut lexer = Lexer::from_str("Int -> Int -> Int").with_backtracking();
+    assert_eq!(
+      lexer.expect_type(),
+      Ok(types::Type::abstraction(
+        numeric(),
+        types::Type::abstraction(numeric(), numeric())
+      ))
+    );
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn parentheses_group_parameter_types()
+  {
+    let mut lexer = Lexer::from_str("(Int -> Int) -> Int").with_backtracking();
+    assert_eq!(
+      lexer.expect_type(),
+      Ok(types::Type::abstraction(
+        types::Type::abstraction(numeric(), numeric()),
+        numeric()
+      ))
+    );
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn products_bind_tighter_than_abstractions()
+  {
+    let mut lexer =
+      Lexer::from_str("Int * (Int * Int) -> Int * Int").with_backtracking();
+    assert_eq!(
+      lexer.expect_type(),
+      Ok(types::Type::abstraction(
+        types::Type::Tuple(vec![
+          numeric(),
+          types::Type::Tuple(vec![numeric(), numeric()])
+        ]),
+        types::Type::Tuple(vec![numeric(), numeric()])
+      ))
+    );
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn type_arguments_come_first()
+  {
+    let mut lexer =
+      Lexer::from_str("'a option list * (Int, 'a) either").with_backtracking();
+    let variable: types::Type =
+      types::Variable::Named(surface::Identifier::new("a")).into();
+    let applied = |name: &str, arguments| {
+      types::Type::Concrete(surface::Identifier::new(name).into(), arguments)
+    };
+    assert_eq!(
+      lexer.expect_type(),
+      Ok(types::Type::Tuple(vec![
+        applied("list", vec![applied("option", vec![variable.clone()])]),
+        applied("either", vec![numeric(), variable]),
+      ]))
+    );
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn type_names_may_be_qualified_by_their_structure()
+  {
+    let mut lexer = Lexer::from_str("'a Option.t -> A.B.u").with_backtracking();
+    let qualified = |path: &[&str], name: &str, arguments| {
+      types::Type::Concrete(
+        types::Constant {
+          path: path
+            .iter()
+            .map(|structure| surface::Identifier::new(*structure))
+            .collect(),
+          name: surface::Identifier::new(name),
+          declaration: None,
+        },
+        arguments,
+      )
+    };
+    let typ = lexer.expect_type().unwrap();
+    assert_eq!(
+      typ,
+      types::Type::abstraction(
+        qualified(&["Option"], "t", vec![types::Variable::Named(
+          surface::Identifier::new("a")
+        )
+        .into()]),
+        qualified(&["A", "B"], "u", vec![]),
+      )
+    );
+    assert_eq!(typ.to_string(), "'a Option.t -> A.B.u");
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn list_types_take_their_element_type_after()
+  {
+    let mut lexer =
+      Lexer::from_str("List (List 'a) -> List Int option").with_backtracking();
+    let variable: types::Type =
+      types::Variable::Named(surface::Identifier::new("a")).into();
+    assert_eq!(
+      lexer.expect_type(),
+      Ok(types::Type::abstraction(
+        types::Type::list(types::Type::list(variable)),
+        types::Type::Concrete(surface::Identifier::new("option").into(), vec![
+          types::Type::list(numeric())
+        ])
+      ))
+    );
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn record_types_sort_their_fields()
+  {
+    let mut lexer = Lexer::from_str("{ name : String; age : Int } -> Int")
+      .with_backtracking();
+    assert_eq!(
+      lexer.expect_type(),
+      Ok(types::Type::abstraction(
+        types::Type::Record(
+          vec![
+            (surface::Identifier::new("age"), numeric()),
+            (
+              surface::Identifier::new("name"),
+              surface::Identifier::new("String").into()
+            ),
+          ],
+          None
+        ),
+        numeric()
+      ))
+    );
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn record_types_can_be_open()
+  {
+    let mut lexer =
+      Lexer::from_str("{ age : Int | 'r } -> { | 'r }").with_backtracking();
+    let rest = types::Variable::Named(surface::Identifier::new("r"));
+    assert_eq!(
+      lexer.expect_type(),
+      Ok(types::Type::abstraction(
+        types::Type::Record(
+          vec![(surface::Identifier::new("age"), numeric())],
+          Some(rest.clone())
+        ),
+        types::Type::Record(vec![], Some(rest))
+      ))
+    );
+    assert_eq!(lexer.next(), None);
+  }
+
+  #[test]
+  fn missing_type()
+  {
+    let mut lexer = Lexer::from_str("-> Int").with_backtracking();
+    assert_eq!(
+      lexer.expect_type(),
+      Err(ParseError::Expected {
+        expected: NodeType::Type,
+        actual: Some(Lexeme::keyword("->")),
+      })
+    );
+  }
+}
@@ -4,9 +4,34 @@ pub enum Token
   Symbol(&'static str),
   Keyword(&'static str),
   Identifier,
+  Operator,
   StringLiteral,
-  NumericLiteral,
+  IntegerLiteral,
+  FloatLiteral,
   MalformedNumericLiteral,
   UnclosedComment,
   UnclosedString,
 }
+
+impl std::fmt::Display for Token
+{
+  fn fmt(
+    &self,
+    f: &mut std::fmt::Formatter<'_>,
+  ) -> std::fmt::Result
+  {
+    match self {
+      | Token::Symbol(symbol) => write!(f, "`{}`", symbol),
+      | Token::Keyword(keyword) => write!(f, "`{}`", keyword),
+      | Token::Identifier => write!(f, "identifier"),
+      | Token::Operator => write!(f, "operator"),
+      | Token::StringLiteral => write!(f, "string literal"),
+      | Token::IntegerLiteral => write!(f, "integer literal"),
+      | Token::FloatLiteral => write!(f, "float literal"),
+      | Token::MalformedNumericLiteral =>
+        write!(f, "malformed numeric literal"),
+      | Token::UnclosedComment => write!(f, "unclosed comment"),
+      | Token::UnclosedString => write!(f, "unclosed string literal"),
+    }
+  }
+}
@@ -1,45 +1,45 @@
+#![feature(is_terminal)]
 #![feature(result_flattening)]
 #![feature(iter_collect_into)]
 
+mod diagnostics;
+mod driver;
 mod frontend;
+mod repl;
+mod session;
 mod syntax;
 mod transform_into;
 
-use frontend::{
-  ExpressionParser,
-  Lexer,
-  ParseError,
-  WithBacktracking,
-};
-use syntax::debrujin::transformations::Evaluate;
-use syntax::surface::transformations::debrujin_encoding::DebrujinEncoding;
-use thiserror::Error;
+use diagnostics::OutputMode;
+use driver::Command;
 
-#[derive(Error, Debug)]
-enum CompilationError
+fn main() -> std::process::ExitCode
 {
-  #[error("failed to parse input")]
-  ParseError(#[from] ParseError),
-  #[error("failed to debrujin encode")]
-  TransformError(
-    #[from] syntax::surface::transformations::debrujin_encoding::TransformError,
-  ),
-}
-
-type Result<T> = std::result::Result<T, CompilationError>;
-
-fn main() -> Result<()>
-{
-  let program = "(fun x -> x) 10";
-  println!("{}", program);
-  let mut lexer = Lexer::from_str(program).with_backtracking();
-  let s = lexer.expect_expression()?;
-  println!("=> {:?}", s);
-  let mut context = Default::default();
-  let d = s.debrujin_encoding(&mut context)?;
-  println!("=> {:?}", d);
-  let mut context = Default::default();
-  let v = d.evaluate(&mut context);
-  println!("=> {:?}", v);
-  Ok(())
+  let command = match driver::parse_args(std::env::args().skip(1)) {
+    | Ok(command) => command,
+    | Err(error) => {
+      eprint!("error: {}\n\n{}", error, driver::USAGE);
+      return std::process::ExitCode::from(driver::ExitCode::Usage as u8)
+    },
+  };
+  let result = session::on_large_stack(|| match command {
+    | Command::Help => {
+      print!("{}", driver::USAGE);
+      Ok(driver::ExitCode::Success)
+    },
+    | Command::Repl => repl::run().map(|()| driver::ExitCode::Success),
+    | Command::Run(options) => driver::run(
+      &options,
+      OutputMode::detect(),
+      &mut std::io::stdout(),
+      &mut std::io::stderr(),
+    ),
+  });
+  match result {
+    | Ok(code) => std::process::ExitCode::from(code as u8),
+    | Err(error) => {
+      eprintln!("error: {}", error);
+      std::process::ExitCode::from(driver::ExitCode::Usage as u8)
+    },
+  }
 }
@@ -0,0 +1,87 @@
+(* The standard prelude, declared before every program and REPL session.
+   Declaring a name again shadows the one from here. *)
+
+(* Functions *)
+
+def id x = x ;
+def const x _ = x ;
+def flip f x y = f y x ;
+def compose f g x = f (g x) ;
+def (|>) x f = f x ;
+def fst (x, _) = x ;
+def snd (_, y) = y ;
+
+(* Numbers *)
+
+def abs n = if n < 0 then -n else n ;
+def abs_float x = if x < 0.0 then -.x else x ;
+def min x y = if x <= y then x else y ;
+def max x y = if x >= y then x else y ;
+def succ n = n + 1 ;
+def pred n = n - 1 ;
+def is_even n = mod n 2 = 0 ;
+def is_odd n = not (is_even n) ;
+def pow base exponent =
+  if exponent <= 0 then 1 else base * pow base (exponent - 1) ;
+def gcd a b = if b = 0 then abs a else gcd b (mod a b) ;
+
+(* Options *)
+
+type 'a option = None | Some of 'a ;
+
+def is_some o = match o with Some _ -> true | None -> false ;
+def is_none o = not (is_some o) ;
+def option_default default o = match o with Some x -> x | None -> default ;
+def option_map f o = match o with Some x -> Some (f x) | None -> None ;
+def option_bind f o = match o with Some x -> f x | None -> None ;
+
+(* Lists *)
+
+def is_empty l = match l with [] -> true | _ -> false ;
+def length l = match l with [] -> 0 | _ :: rest -> 1 + length rest ;
+def head l = match l with x :: _ -> Some x | [] -> None ;
+def tail l = match l with _ :: rest -> Some rest | [] -> None ;
+def nth l n =
+  match l with
+  | [] -> None
+  | x :: rest -> if n = 0 then Some x else nth rest (n - 1) ;
+def map f l = match l with [] -> [] | x :: rest -> f x :: map f rest ;
+def filter p l =
+  match l with
+  | [] -> []
+  | x :: rest -> if p x then x :: filter p rest else filter p rest ;
+def fold_left f acc l =
+  match l with [] -> acc | x :: rest -> fold_left f (f acc x) rest ;
+def fold_right f l acc =
+  match l with [] -> acc | x :: rest -> f x (fold_right f rest acc) ;
+def append l r = fold_right (fun x acc -> x :: acc) l r ;
+def (@) l r = append l r ;
+def reverse l = fold_left (fun acc x -> x :: acc) [] l ;
+def concat ls = fold_right append ls [] ;
+def concat_map f l = concat (map f l) ;
+def exists p l =
+  match l with [] -> false | x :: rest -> if p x then true else exists p rest ;
+def for_all p l =
+  match l with [] -> true | x :: rest -> if p x then for_all p rest else false ;
+def find p l =
+  match l with
+  | [] -> None
+  | x :: rest -> if p x then Some x else find p rest ;
+def contains x l = exists (fun y -> y = x) l ;
+def range from until =
+  if from >= until then [] else from :: range (from + 1) until ;
+def zip l r =
+  match (l, r) with
+  | (x :: xs, y :: ys) -> (x, y) :: zip xs ys
+  | _ -> [] ;
+def sum l = fold_left (+) 0 l ;
+def product l = fold_left (fun x y -> x * y) 1 l ;
+
+(* Strings *)
+
+def join separator strings =
+  match strings with
+  | [] -> ``
+  | [s] -> s
+  | s :: rest -> s ^ separator ^ join separator rest ;
+def repeat n s = if n <= 0 then `` else s ^ repeat (n - 1) s ;
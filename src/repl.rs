@@ -0,0 +1,511 @@
+use std::io::Write;
+
+use crate::diagnostics::{
+  OutputMode,
+  Renderer,
+  ToDiagnostic,
+};
+use crate::frontend::{
+  CanBacktrack,
+  DeclarationParser,
+  ExpectSyntax,
+  ExpressionParser,
+  Fixities,
+  FixityDeclaration,
+  HasFixities,
+  Lexer,
+  ParseError,
+  Token,
+  WithBacktracking,
+};
+use crate::session::{
+  CompilationError,
+  Session,
+};
+use crate::syntax::debrujin::transformations::Value;
+use crate::syntax::surface;
+use crate::syntax::surface::types;
+
+const HELP: &str = "\
+commands:
+  <expression>         evaluate an expression
+  val <name> = <expression> ;
+                       bind a name for all later inputs
+  def <name> <parameters> = <expression> ;
+                       bind a recursive function, more can follow `and`
+  type <parameters> <name> = <constructors> ;
+                       declare a type such as `type 'a option = None | Some
+                       of 'a ;`, binding its constructors
+  structure <Name> = struct <declarations> end ;
+                       declare a structure, its members are referred to as
+                       `Name.member` unless `open Name ;` binds them
+  infixl <precedence> <operators>
+                       parse operators as left associative, also `infixr`
+                       and `infix`, from 0 binding loosest to 9
+  :type <expression>   show the type of an expression
+  :ast <input>         show the surface syntax tree of an input
+  :debrujin <expression>
+                       show the de Bruijn encoding of an expression
+  :load <file>         declare every binding in a file
+  :reset               forget all bindings but the prelude
+  :help                show this message
+  :quit                leave the REPL
+";
+
+#[derive(Debug, Clone, PartialEq)]
+enum Input
+{
+  Declaration(surface::TopLevel),
+  Expression(surface::Expression),
+  Fixity(FixityDeclaration),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome
+{
+  /// The input ended before it could be parsed, more lines are needed.
+  Incomplete,
+  Output(String),
+  Error(String),
+  Quit,
+}
+
+pub struct Repl
+{
+  session: Session,
+  fixities: Fixities,
+  mode: OutputMode,
+}
+
+impl Repl
+{
+  pub fn new(mode: OutputMode) -> Self
+  {
+    Self {
+      session: Session::with_prelude(),
+      fixities: Fixities::default(),
+      mode,
+    }
+  }
+
+  pub fn execute(
+    &mut self,
+    input: &str,
+  ) -> Outcome
+  {
+    let trimmed = input.trim();
+    match trimmed.strip_prefix(':') {
+      | Some(command) => self.command(command),
+      | None if trimmed.is_empty() => Outcome::Output(String::new()),
+      | None => match parse_input(input, &self.fixities) {
+        | Err(error) if is_incomplete(&error) && !input.ends_with("\n\n") =>
+          Outcome::Incomplete,
+        | Err(error) => self.error("<repl>", input, error.into()),
+        | Ok(Input::Declaration(declaration)) => {
+          let result = self
+            .session
+            .declare(&declaration)
+            .map(|declared| describe(&declaration, &declared));
+          self.report("<repl>", input, result)
+        },
+        | Ok(Input::Expression(expression)) => {
+          let result = self
+            .session
+            .evaluate(&expression)
+            .map(|(value, typ)| format!("{} : {}\n", value, typ));
+          self.report("<repl>", input, result)
+        },
+        | Ok(Input::Fixity(declaration)) => {
+          self.fixities.declare(&declaration);
+          Outcome::Output(format!("{}\n", declaration))
+        },
+      },
+    }
+  }
+
+  fn command(
+    &mut self,
+    command: &str,
+  ) -> Outcome
+  {
+    let (name, argument) = command
+      .split_once(char::is_whitespace)
+      .unwrap_or((command, ""));
+    match name {
+      | "help" | "h" => Outcome::Output(HELP.to_string()),
+      | "quit" | "q" => Outcome::Quit,
+      | "reset" => {
+        self.session = Session::with_prelude();
+        self.fixities = Fixities::default();
+        Outcome::Output("session reset\n".to_string())
+      },
+      | "type" | "t" => {
+        let result = parse_expression(argument, &self.fixities)
+          .map_err(Into::into)
+          .and_then(|expression| {
+            self.session.encode(&expression)?;
+            Ok(format!("{}\n", self.session.infer_type(&expression)?))
+          });
+        self.report("<repl>", argument, result)
+      },
+      | "ast" => {
+        let result = parse_input(argument, &self.fixities)
+          .map(|input| match input {
+            | Input::Declaration(declaration) =>
+              format!("{:#?}\n", declaration),
+            | Input::Expression(expression) => format!("{:#?}\n", expression),
+            | Input::Fixity(declaration) => format!("{:#?}\n", declaration),
+          })
+          .map_err(Into::into);
+        self.report("<repl>", argument, result)
+      },
+      | "debrujin" => {
+        let result = parse_expression(argument, &self.fixities)
+          .map_err(Into::into)
+          .and_then(|expression| self.session.encode(&expression))
+          .map(|encoded| format!("{:#?}\n", encoded));
+        self.report("<repl>", argument, result)
+      },
+      | "load" => self.load(argument.trim()),
+      | _ =>
+        Outcome::Error(format!("unknown command `:{}`, try `:help`\n", name)),
+    }
+  }
+
+  fn load(
+    &mut self,
+    path: &str,
+  ) -> Outcome
+  {
+    let source = match std::fs::read_to_string(path) {
+      | Ok(source) => source,
+      | Err(error) =>
+        return Outcome::Error(format!("could not read `{}`: {}\n", path, error)),
+    };
+    let mut lexer = Lexer::from_str(&source)
+      .with_backtracking()
+      .with_fixities(self.fixities.clone());
+    let program = match lexer.expect_program() {
+      | Ok(program) => program,
+      | Err(error) => return self.error(path, &source, error.into()),
+    };
+    self.fixities = lexer.fixities().clone();
+    let mut output = String::new();
+    for declaration in &program.declarations {
+      match self.session.declare(declaration) {
+        | Ok(declared) => output += &describe(declaration, &declared),
+        | Err(error) => return self.report(path, &source, Err(error)),
+      }
+    }
+    self.report(path, &source, Ok(output))
+  }
+
+  /// Renders `result` after the warnings the session gathered meanwhile.
+  fn report(
+    &mut self,
+    name: &str,
+    source: &str,
+    result: Result<String, CompilationError>,
+  ) -> Outcome
+  {
+    let renderer = Renderer::new(name, source, self.mode);
+    let warnings = self
+      .session
+      .take_warnings()
+      .iter()
+      .map(|warning| renderer.render(&warning.to_diagnostic()))
+      .collect::<String>();
+    match result {
+      | Ok(output) => Outcome::Output(warnings + &output),
+      | Err(error) =>
+        Outcome::Error(warnings + &renderer.render(&error.to_diagnostic())),
+    }
+  }
+
+  fn error(
+    &self,
+    name: &str,
+    source: &str,
+    error: CompilationError,
+  ) -> Outcome
+  {
+    let renderer = Renderer::new(name, source, self.mode);
+    Outcome::Error(renderer.render(&error.to_diagnostic()))
+  }
+}
+
+/// One `val <name> = <value> : <type>` line for every name of `declaration`.
+fn describe(
+  declaration: &surface::TopLevel,
+  declared: &[(Value, types::Scheme)],
+) -> String
+{
+  declaration
+    .names()
+    .into_iter()
+    .zip(declared)
+    .map(|(name, (value, typ))| {
+      format!("val {} = {} : {}\n", name.name, value, typ)
+    })
+    .collect()
+}
+
+fn is_incomplete(error: &ParseError) -> bool
+{
+  matches!(
+    error,
+    ParseError::UnexpectedEndOfInput { .. }
+      | ParseError::Expected {
+        actual: None,
+        ..
+      }
+  )
+}
+
+fn parse_input(
+  source: &str,
+  fixities: &Fixities,
+) -> Result<Input, ParseError>
+{
+  let mut lexer = Lexer::from_str(source)
+    .with_backtracking()
+    .with_fixities(fixities.clone());
+  let input = match lexer
+    .peek()
+    .as_ref()
+    .map(|lexeme| lexeme.token())
+  {
+    | Some(Token::Keyword("val" | "def" | "type" | "structure" | "open")) =>
+      Input::Declaration(lexer.expect_top_level()?),
+    | Some(Token::Keyword("infixl" | "infixr" | "infix")) =>
+      Input::Fixity(lexer.expect_fixity_declaration()?),
+    | _ => Input::Expression(lexer.expect_expression()?),
+  };
+  lexer.expect_end_of_input()?;
+  Ok(input)
+}
+
+fn parse_expression(
+  source: &str,
+  fixities: &Fixities,
+) -> Result<surface::Expression, ParseError>
+{
+  let mut lexer = Lexer::from_str(source)
+    .with_backtracking()
+    .with_fixities(fixities.clone());
+  let expression = lexer.expect_expression()?;
+  lexer.expect_end_of_input()?;
+  Ok(expression)
+}
+
+/// Reads inputs from standard input until it is closed or `:quit` is entered.
+pub fn run() -> std::io::Result<()>
+{
+  let mut repl = Repl::new(OutputMode::detect());
+  let mut input = String::new();
+  loop {
+    let prompt = match input.is_empty() {
+      | true => "> ",
+      | false => "| ",
+    };
+    print!("{}", prompt);
+    std::io::stdout().flush()?;
+    if std::io::stdin().read_line(&mut input)? == 0 {
+      println!();
+      return Ok(())
+    }
+    match repl.execute(&input) {
+      | Outcome::Incomplete => continue,
+      | Outcome::Output(output) => print!("{}", output),
+      | Outcome::Error(error) => eprint!("{}", error),
+      | Outcome::Quit => return Ok(()),
+    }
+    input.clear();
+  }
+}
+
+#[cfg(test)]
+mod spec
+{
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  fn output(text: &str) -> Outcome
+  {
+    Outcome::Output(text.to_string())
+  }
+
+  #[test]
+  fn expressions_print_value_and_type()
+  {
+    let mut repl = Repl::new(OutputMode::Plain);
+    assert_eq!(repl.execute("10\n"), output("10 : Int\n"));
+    assert_eq!(repl.execute("`foo`\n"), output("`foo` : String\n"));
+    assert_eq!(repl.execute("fun x -> x\n"), output("<fun> : 'a -> 'a\n"));
+  }
+
+  #[test]
+  fn declarations_persist_across_inputs()
+  {
+    let mut repl = Repl::new(OutputMode::Plain);
+    assert_eq!(
+      repl.execute("val x = true ;\n"),
+      output("val x = true : Boolean\n")
+    );
+    assert_eq!(repl.execute("(fun y -> y) x\n"), output("true : Boolean\n"));
+  }
+
+  #[test]
+  fn recursive_declarations_print_every_function()
+  {
+    let mut repl = Repl::new(OutputMode::Plain);
+    assert_eq!(
+      repl.execute("def f x = g x and g y = y ;\n"),
+      output("val f = <fun> : 'a -> 'a\nval g = <fun> : 'a -> 'a\n")
+    );
+    assert_eq!(repl.execute("f true\n"), output("true : Boolean\n"));
+  }
+
+  #[test]
+  fn type_declarations_print_every_constructor()
+  {
+    let mut repl = Repl::new(OutputMode::Plain);
+    assert_eq!(
+      repl.execute("type 'a option = None | Some of 'a ;\n"),
+      output(
+        "val None = None : 'a option\nval Some = <fun> : 'a -> 'a option\n"
+      )
+    );
+    assert_eq!(
+      repl.execute("Some (Some (1, None))\n"),
+      output("Some (Some (1, None)) : (Int * 'a option) option option\n")
+    );
+  }
+
+  #[test]
+  fn warnings_precede_the_output()
+  {
+    let mut repl = Repl::new(OutputMode::Plain);
+    let Outcome::Output(rendered) = repl.execute("match 0 with 0 -> true\n")
+    else {
+      panic!("expected the value of the match")
+    };
+    assert!(rendered
+      .starts_with("warning: non-exhaustive patterns: `1` is not matched\n"));
+    assert!(rendered.ends_with("true : Boolean\n"));
+    assert_eq!(repl.execute("0\n"), output("0 : Int\n"));
+  }
+
+  #[test]
+  fn fixity_declarations_persist_across_inputs()
+  {
+    let mut repl = Repl::new(OutputMode::Plain);
+    repl.execute("def (--) x y = x - y ;\n");
+    assert_eq!(repl.execute("10 -- 4 -- 3\n"), output("3 : Int\n"));
+    assert_eq!(repl.execute("infixr 6 --\n"), output("infixr 6 --\n"));
+    assert_eq!(repl.execute("10 -- 4 -- 3\n"), output("9 : Int\n"));
+    assert_eq!(repl.execute(":reset"), output("session reset\n"));
+    repl.execute("def (--) x y = x - y ;\n");
+    assert_eq!(repl.execute("10 -- 4 -- 3\n"), output("3 : Int\n"));
+  }
+
+  #[test]
+  fn incomplete_input_asks_for_more()
+  {
+    let mut repl = Repl::new(OutputMode::Plain);
+    assert_eq!(repl.execute("val x =\n"), Outcome::Incomplete);
+    assert_eq!(repl.execute("val x =\n 10 ;\n"), output("val x = 10 : Int\n"));
+  }
+
+  #[test]
+  fn blank_line_ends_incomplete_input()
+  {
+    let mut repl = Repl::new(OutputMode::Plain);
+    assert!(matches!(repl.execute("val x =\n\n"), Outcome::Error(_)));
+  }
+
+  #[test]
+  fn errors_are_rendered()
+  {
+    let mut repl = Repl::new(OutputMode::Plain);
+    assert_eq!(
+      repl.execute("foo\n"),
+      Outcome::Error(
+        [
+          "error: unbound variable `foo`",
+          " --> <repl>:1:1",
+          "  |",
+          "1 | foo",
+          "  | ^^^ not found in this scope",
+          "  |",
+          "",
+        ]
+        .join("\n")
+      )
+    );
+    assert!(matches!(repl.execute("foo )\n"), Outcome::Error(_)));
+  }
+
+  #[test]
+  fn type_command()
+  {
+    let mut repl = Repl::new(OutputMode::Plain);
+    assert_eq!(
+      repl.execute(":type (fun x y -> x) 10\n"),
+      output("'a -> Int\n")
+    );
+  }
+
+  #[test]
+  fn ast_and_debrujin_commands()
+  {
+    let mut repl = Repl::new(OutputMode::Plain);
+    let Outcome::Output(ast) = repl.execute(":ast fun x -> x")
+    else {
+      panic!("expected output")
+    };
+    assert!(ast.starts_with("Abstraction("));
+    assert_eq!(
+      repl.execute(":debrujin fun x -> x"),
+      output(&format!(
+        "{:#?}\n",
+        crate::syntax::debrujin::Expression::from(
+          crate::syntax::debrujin::Abstraction {
+            body: crate::syntax::debrujin::Identifier::new(0).into(),
+          }
+        )
+      ))
+    );
+  }
+
+  #[test]
+  fn reset_forgets_bindings()
+  {
+    let mut repl = Repl::new(OutputMode::Plain);
+    repl.execute("val x = 10 ;");
+    assert_eq!(repl.execute(":reset"), output("session reset\n"));
+    assert!(matches!(repl.execute("x"), Outcome::Error(_)));
+  }
+
+  #[test]
+  fn load_declares_bindings_from_a_file()
+  {
+    let path = std::env::temp_dir().join("rusty-ml-repl-load.ml");
+    std::fs::write(&path, "val x = 10 ;\nval id = fun y -> y ;\n").unwrap();
+    let mut repl = Repl::new(OutputMode::Plain);
+    assert_eq!(
+      repl.execute(&format!(":load {}", path.display())),
+      output("val x = 10 : Int\nval id = <fun> : 'a -> 'a\n")
+    );
+    assert_eq!(repl.execute("id x"), output("10 : Int\n"));
+  }
+
+  #[test]
+  fn quit_and_unknown_commands()
+  {
+    let mut repl = Repl::new(OutputMode::Plain);
+    assert_eq!(repl.execute(":quit"), Outcome::Quit);
+    assert!(matches!(repl.execute(":frobnicate"), Outcome::Error(_)));
+  }
+}
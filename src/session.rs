@@ -0,0 +1,905 @@
+use thiserror::Error;
+
+use crate::diagnostics::{
+  Diagnostic,
+  ToDiagnostic,
+};
+use crate::frontend::{
+  DeclarationParser,
+  Lexer,
+  ParseError,
+  WithBacktracking,
+};
+use crate::syntax::debrujin::transformations::{
+  evaluation,
+  Evaluate,
+  RuntimeError,
+  Value,
+};
+use crate::syntax::surface::transformations::debrujin_encoding::{
+  self,
+  DebrujinEncoding,
+  TransformError,
+};
+use crate::syntax::surface::transformations::exhaustiveness::{
+  CheckPatterns,
+  PatternWarning,
+};
+use crate::syntax::surface::transformations::infer_type::{
+  self,
+  InferType,
+  TypeError,
+};
+use crate::syntax::surface::types;
+use crate::syntax::{
+  debrujin,
+  surface,
+};
+
+#[derive(Error, Debug)]
+pub enum CompilationError
+{
+  /// Boxed, since a parse error holds whole lexemes and would make every
+  /// result of the session as large.
+  #[error("failed to parse input")]
+  Parse(Box<ParseError>),
+  #[error("failed to debrujin encode")]
+  Encoding(#[from] TransformError),
+  #[error("failed to infer type")]
+  Type(#[from] TypeError),
+  #[error("failed to evaluate")]
+  Runtime(#[from] RuntimeError),
+}
+
+impl From<ParseError> for CompilationError
+{
+  fn from(error: ParseError) -> Self
+  {
+    Self::Parse(Box::new(error))
+  }
+}
+
+impl ToDiagnostic for CompilationError
+{
+  fn to_diagnostic(&self) -> Diagnostic
+  {
+    match self {
+      | CompilationError::Parse(error) => error.to_diagnostic(),
+      | CompilationError::Encoding(error) => error.to_diagnostic(),
+      | CompilationError::Type(error) => error.to_diagnostic(),
+      | CompilationError::Runtime(error) => error.to_diagnostic(),
+    }
+  }
+}
+
+pub type Result<T> = std::result::Result<T, CompilationError>;
+
+/// The declarations every program starts with, see `Session::with_prelude`.
+const PRELUDE: &str = include_str!("prelude.ml");
+
+/// The stack sessions are run on, evaluation nests up to
+/// `evaluation::MAXIMUM_DEPTH` deep on it. Only the part that is used is
+/// backed by memory.
+pub const STACK_SIZE: usize = 1 << 30;
+
+/// Runs `interpreter` on a thread with a stack of `STACK_SIZE` and waits for
+/// its result.
+pub fn on_large_stack<T>(interpreter: impl FnOnce() -> T + Send) -> T
+where
+  T: Send,
+{
+  std::thread::scope(|scope| {
+    std::thread::Builder::new()
+      .stack_size(STACK_SIZE)
+      .spawn_scoped(scope, interpreter)
+      .expect("failed to spawn the interpreter thread")
+      .join()
+      .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+  })
+}
+
+/// The state shared by consecutive inputs: every declaration extends the
+/// contexts of all pipeline stages, so later inputs can refer to it.
+pub struct Session
+{
+  names: debrujin_encoding::Context,
+  types: infer_type::Context,
+  values: evaluation::Context,
+  /// The warnings about the inputs since the last `take_warnings`.
+  warnings: Vec<PatternWarning>,
+}
+
+/// A session that knows nothing but the primitives.
+impl Default for Session
+{
+  fn default() -> Self
+  {
+    Self {
+      names: debrujin_encoding::Context::with_primitives(),
+      types: infer_type::Context::with_primitives(),
+      values: evaluation::Context::with_primitives(),
+      warnings: vec![],
+    }
+  }
+}
+
+impl Session
+{
+  /// A session where the prelude is declared, its names are bound in every
+  /// context as if they were declared by the user.
+  pub fn with_prelude() -> Self
+  {
+    let mut session = Self::default();
+    let program = Lexer::from_str(PRELUDE)
+      .with_backtracking()
+      .expect_program()
+      .expect("the prelude parses");
+    session.names.unlocated(true);
+    for declaration in &program.declarations {
+      session
+        .declare(declaration)
+        .expect("the prelude is well typed");
+    }
+    session.names.unlocated(false);
+    session
+  }
+
+  /// Infers the most general type of `expression`, leaving the session
+  /// untouched if that fails.
+  pub fn infer_type(
+    &mut self,
+    expression: &surface::Expression,
+  ) -> Result<types::Scheme>
+  {
+    let scheme = self.typed(|context| {
+      context.binding(|context| {
+        let typ: types::Type = expression.infer_type(context)?;
+        context.solve_constraints()?;
+        context.generalise(&typ)
+      })
+    })?;
+    self
+      .warnings
+      .extend(expression.check_patterns(&self.types));
+    self.types.forget_scrutinees();
+    Ok(scheme)
+  }
+
+  /// The warnings about every input checked since the last call.
+  pub fn take_warnings(&mut self) -> Vec<PatternWarning>
+  {
+    std::mem::take(&mut self.warnings)
+  }
+
+  /// Runs `inference` on the typing context, restoring the context if it
+  /// fails so that a rejected input leaves no trace.
+  fn typed<T>(
+    &mut self,
+    inference: impl FnOnce(
+      &mut infer_type::Context,
+    ) -> std::result::Result<T, TypeError>,
+  ) -> Result<T>
+  {
+    let snapshot = self.types.clone();
+    let result = inference(&mut self.types);
+    if result.is_err() {
+      self.types = snapshot;
+    }
+    Ok(result?)
+  }
+
+  pub fn encode(
+    &mut self,
+    expression: &surface::Expression,
+  ) -> Result<debrujin::Expression>
+  {
+    Ok(expression.debrujin_encoding(&mut self.names)?)
+  }
+
+  pub fn evaluate(
+    &mut self,
+    expression: &surface::Expression,
+  ) -> Result<(Value, types::Scheme)>
+  {
+    let encoded = self.encode(expression)?;
+    let typ = self.infer_type(expression)?;
+    Ok((self.values.evaluate(encoded)?, typ))
+  }
+
+  /// Runs every stage on `declaration`, binding its names only if all
+  /// succeed. Returns the value and scheme of every name, in order.
+  pub fn declare(
+    &mut self,
+    declaration: &surface::TopLevel,
+  ) -> Result<Vec<(Value, types::Scheme)>>
+  {
+    let names = self.names.clone();
+    let types = self.types.clone();
+    let result = self.bind(declaration);
+    if result.is_err() {
+      self.names = names;
+      self.types = types;
+    }
+    result
+  }
+
+  fn bind(
+    &mut self,
+    declaration: &surface::TopLevel,
+  ) -> Result<Vec<(Value, types::Scheme)>>
+  {
+    let encoded: debrujin::TopLevel =
+      declaration.debrujin_encoding(&mut self.names)?;
+    let schemes: Vec<types::Scheme> =
+      declaration.infer_type(&mut self.types)?;
+    self
+      .warnings
+      .extend(declaration.check_patterns(&self.types));
+    self.types.forget_scrutinees();
+    let values: Vec<Value> = encoded.evaluate(&mut self.values)?;
+    Ok(
+      values
+        .into_iter()
+        .zip(schemes)
+        .collect(),
+    )
+  }
+
+  /// Encodes every declaration of `program`, each with the names of the
+  /// earlier ones in scope.
+  pub fn encode_program(
+    &mut self,
+    program: &surface::Program,
+  ) -> Result<debrujin::Program>
+  {
+    Ok(program.debrujin_encoding(&mut self.names)?)
+  }
+
+  /// Infers the scheme of every declaration of `program`, each with the
+  /// names of the earlier ones in scope.
+  pub fn infer_program(
+    &mut self,
+    program: &surface::Program,
+  ) -> Result<Vec<(surface::Identifier, types::Scheme)>>
+  {
+    let schemes = self.typed(|context| program.infer_type(context))?;
+    self
+      .warnings
+      .extend(program.check_patterns(&self.types));
+    self.types.forget_scrutinees();
+    Ok(schemes)
+  }
+
+  /// Evaluates a declaration returned by `encode_program` and pushes its
+  /// values for the evaluation of later declarations.
+  pub fn evaluate_declaration(
+    &mut self,
+    declaration: &debrujin::TopLevel,
+  ) -> Result<Vec<Value>>
+  {
+    Ok(declaration.evaluate(&mut self.values)?)
+  }
+}
+
+#[cfg(test)]
+mod spec
+{
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+  use crate::frontend::{
+    DeclarationParser,
+    ExpressionParser,
+    Lexer,
+    WithBacktracking,
+  };
+
+  fn declare(
+    session: &mut Session,
+    input: &str,
+  ) -> Result<Vec<(Value, types::Scheme)>>
+  {
+    let mut lexer = Lexer::from_str(input).with_backtracking();
+    session.declare(&lexer.expect_top_level()?)
+  }
+
+  fn evaluate(
+    session: &mut Session,
+    input: &str,
+  ) -> Result<(Value, types::Scheme)>
+  {
+    let mut lexer = Lexer::from_str(input).with_backtracking();
+    session.evaluate(&lexer.expect_expression()?)
+  }
+
+  #[test]
+  fn the_prelude_declares_without_warnings()
+  {
+    let mut session = Session::with_prelude();
+    assert!(session.take_warnings().is_empty());
+    let (_, typ) = evaluate(&mut session, "fold_left").unwrap();
+    assert_eq!(typ.to_string(), "('a -> 'b -> 'a) -> 'a -> List 'b -> 'a");
+    let (_, typ) = evaluate(&mut session, "option_map").unwrap();
+    assert_eq!(typ.to_string(), "('a -> 'b) -> 'a option -> 'b option");
+  }
+
+  #[test]
+  fn declared_types_shadow_the_prelude()
+  {
+    let mut session = Session::with_prelude();
+    declare(&mut session, "type 'a option = Nothing | Just of 'a ;").unwrap();
+    let (_, typ) = evaluate(
+      &mut session,
+      "fun (o : Int option) -> match o with Just x -> x | Nothing -> 0",
+    )
+    .unwrap();
+    assert_eq!(typ.to_string(), "Int option -> Int");
+    assert!(matches!(
+      evaluate(&mut session, "option_map (fun x -> x) (Just 1)"),
+      Err(CompilationError::Type(TypeError::Mismatch { .. }))
+    ));
+    assert!(matches!(
+      evaluate(&mut session, "(fun (o : Int option) -> o) (Some 1)"),
+      Err(CompilationError::Type(TypeError::Mismatch { .. }))
+    ));
+    let (_, typ) =
+      evaluate(&mut session, "option_map (fun x -> x) (Some 1)").unwrap();
+    assert_eq!(typ.to_string(), "Int option");
+  }
+
+  #[test]
+  fn the_prelude_provides_list_option_string_and_numeric_utilities()
+  {
+    let mut session = Session::with_prelude();
+    let mut check = |input: &str, expected: &str| {
+      let (value, _) = evaluate(&mut session, input).unwrap();
+      assert_eq!(value.to_string(), expected, "{}", input);
+    };
+    check("range 0 5 |> filter is_even |> map succ", "[1, 3, 5]");
+    check("reverse ([1, 2] @ [3])", "[3, 2, 1]");
+    check("(sum [1, 2, 3], product [1, 2, 3], length [])", "(6, 6, 0)");
+    check("zip [1, 2, 3] [`a`, `b`]", "[(1, `a`), (2, `b`)]");
+    check("(find is_odd [2, 3, 5], nth [1] 1)", "(Some 3, None)");
+    check("option_default 0 (option_map succ (head [41]))", "42");
+    check("join `, ` (map (repeat 2) [`a`, `b`])", "`aa, bb`");
+    check("(gcd 12 (-18), pow 2 10, max 1 (abs (-3)))", "(6, 1024, 3)");
+    check("compose fst (flip const 0) (true, ())", "true");
+  }
+
+  #[test]
+  fn the_prelude_can_be_shadowed()
+  {
+    let mut session = Session::with_prelude();
+    declare(&mut session, "def map x = x + 1 ;").unwrap();
+    let (value, _) = evaluate(&mut session, "map 1").unwrap();
+    assert_eq!(value, Value::Integer(2));
+    let (value, _) =
+      evaluate(&mut session, "filter is_some [None, Some 1]").unwrap();
+    assert_eq!(value.to_string(), "[Some 1]");
+  }
+
+  #[test]
+  fn declarations_are_visible_to_later_inputs()
+  {
+    let mut session = Session::default();
+    declare(&mut session, "val x = 10 ;").unwrap();
+    declare(&mut session, "val s = `foo` ;").unwrap();
+    let (value, typ) = evaluate(&mut session, "(fun a b -> a) x s").unwrap();
+    assert_eq!(value, Value::Integer(10));
+    assert_eq!(typ.to_string(), "Int");
+  }
+
+  #[test]
+  fn functions_can_be_declared()
+  {
+    let mut session = Session::default();
+    let declared = declare(&mut session, "val id = fun x -> x ;").unwrap();
+    let [(value, typ)] = declared.as_slice()
+    else {
+      panic!("expected a single binding")
+    };
+    assert_eq!(value.to_string(), "<fun>");
+    assert_eq!(typ.to_string(), "'a -> 'a");
+    let (value, _) = evaluate(&mut session, "id `foo`").unwrap();
+    assert_eq!(value, Value::String("foo".into()));
+  }
+
+  #[test]
+  fn failed_declarations_are_not_bound()
+  {
+    let mut session = Session::default();
+    assert!(declare(&mut session, "val x = y ;").is_err());
+    assert!(matches!(
+      evaluate(&mut session, "x"),
+      Err(CompilationError::Encoding(_))
+    ));
+  }
+
+  #[test]
+  fn type_errors_leave_the_session_usable()
+  {
+    let mut session = Session::default();
+    declare(&mut session, "val x = 10 ;").unwrap();
+    assert!(matches!(
+      declare(&mut session, "val y = x x ;"),
+      Err(CompilationError::Type(_))
+    ));
+    let (value, typ) = evaluate(&mut session, "x").unwrap();
+    assert_eq!(value, Value::Integer(10));
+    assert_eq!(typ.to_string(), "Int");
+  }
+
+  #[test]
+  fn declarations_are_polymorphic()
+  {
+    let mut session = Session::default();
+    declare(&mut session, "val id = fun x -> x ;").unwrap();
+    declare(&mut session, "val const = fun a b -> a ;").unwrap();
+    let (value, typ) =
+      evaluate(&mut session, "const (id 10) (id `foo`)").unwrap();
+    assert_eq!(value, Value::Integer(10));
+    assert_eq!(typ.to_string(), "Int");
+    let (_, typ) = evaluate(&mut session, "const id").unwrap();
+    assert_eq!(typ.to_string(), "'a -> 'b -> 'b");
+  }
+
+  #[test]
+  fn parameters_stay_monomorphic()
+  {
+    let mut session = Session::default();
+    assert!(matches!(
+      evaluate(&mut session, "fun f -> (fun a b -> a) (f 10) (f `foo`)"),
+      Err(CompilationError::Type(_))
+    ));
+  }
+
+  #[test]
+  fn recursive_functions_can_be_declared()
+  {
+    let mut session = Session::default();
+    let declared = declare(
+      &mut session,
+      "def apply f n = f n and twice f x = apply f (apply f x) ;",
+    )
+    .unwrap();
+    // `apply` is used at the type of `twice` within the group, so it is not
+    // as general as it would be on its own
+    let types = declared
+      .iter()
+      .map(|(_, typ)| typ.to_string())
+      .collect::<Vec<_>>();
+    assert_eq!(types, vec!["('a -> 'a) -> 'a -> 'a", "('a -> 'a) -> 'a -> 'a"]);
+    let (value, typ) = evaluate(&mut session, "twice (fun x -> x) 10").unwrap();
+    assert_eq!(value, Value::Integer(10));
+    assert_eq!(typ.to_string(), "Int");
+  }
+
+  #[test]
+  fn failed_recursive_declarations_are_not_bound()
+  {
+    let mut session = Session::default();
+    declare(&mut session, "val f = 10 ;").unwrap();
+    assert!(matches!(
+      declare(&mut session, "def f x = f ;"),
+      Err(CompilationError::Type(_))
+    ));
+    let (value, typ) = evaluate(&mut session, "f").unwrap();
+    assert_eq!(value, Value::Integer(10));
+    assert_eq!(typ.to_string(), "Int");
+  }
+
+  #[test]
+  fn recursion_stops_at_a_conditional()
+  {
+    let mut session = Session::default();
+    declare(&mut session, "def f b = if b then `done` else f true ;").unwrap();
+    let (value, typ) = evaluate(&mut session, "f false").unwrap();
+    assert_eq!(value, Value::String("done".into()));
+    assert_eq!(typ.to_string(), "String");
+  }
+
+  #[test]
+  fn primitives_are_bound_from_the_start()
+  {
+    let mut session = Session::default();
+    let (value, typ) = evaluate(&mut session, "1 + (10 - 4)").unwrap();
+    assert_eq!(value, Value::Integer(7));
+    assert_eq!(typ.to_string(), "Int");
+    let (value, typ) = evaluate(&mut session, "`foo` ^ `bar`").unwrap();
+    assert_eq!(value, Value::String("foobar".into()));
+    assert_eq!(typ.to_string(), "String");
+    let (_, typ) = evaluate(&mut session, "(<=)").unwrap();
+    assert_eq!(typ.to_string(), "'a -> 'a -> Boolean");
+    let (value, _) = evaluate(&mut session, "not true || 1 < 2").unwrap();
+    assert_eq!(value, Value::Bool(true));
+  }
+
+  #[test]
+  fn primitives_are_type_checked()
+  {
+    let mut session = Session::default();
+    assert!(matches!(
+      evaluate(&mut session, "1 + `foo`"),
+      Err(CompilationError::Type(_))
+    ));
+    assert!(matches!(
+      evaluate(&mut session, "1 < true"),
+      Err(CompilationError::Type(_))
+    ));
+    assert!(matches!(
+      evaluate(&mut session, "1 + 2.0"),
+      Err(CompilationError::Type(_))
+    ));
+  }
+
+  #[test]
+  fn integers_and_floats_are_distinct()
+  {
+    let mut session = Session::default();
+    let (value, typ) = evaluate(&mut session, "0xFF + 0b1 * 1_000").unwrap();
+    assert_eq!(value, Value::Integer(1255));
+    assert_eq!(typ.to_string(), "Int");
+    let (value, typ) = evaluate(&mut session, "-1.5e1 +. float 3").unwrap();
+    assert_eq!(value, Value::Float(-12.0));
+    assert_eq!(typ.to_string(), "Float");
+    let (value, _) = evaluate(&mut session, "truncate (7.0 /. 2.0)").unwrap();
+    assert_eq!(value, Value::Integer(3));
+  }
+
+  #[test]
+  fn division_by_zero_is_a_runtime_error()
+  {
+    let mut session = Session::default();
+    assert!(matches!(
+      evaluate(&mut session, "mod 10 (2 - 2)"),
+      Err(CompilationError::Runtime(RuntimeError::At { error, .. }))
+        if *error == RuntimeError::DivisionByZero
+    ));
+    let (value, _) = evaluate(&mut session, "10 / 4").unwrap();
+    assert_eq!(value, Value::Integer(2));
+  }
+
+  #[test]
+  fn boolean_operators_short_circuit()
+  {
+    let mut session = Session::default();
+    declare(&mut session, "def loop x = loop x ;").unwrap();
+    declare(&mut session, "val x = 0 ;").unwrap();
+    let (value, _) = evaluate(&mut session, "false && loop ()").unwrap();
+    assert_eq!(value, Value::Bool(false));
+    let (value, _) = evaluate(&mut session, "true || loop ()").unwrap();
+    assert_eq!(value, Value::Bool(true));
+    let (value, _) =
+      evaluate(&mut session, "not (x = 0) && 10 / x > 1").unwrap();
+    assert_eq!(value, Value::Bool(false));
+    assert!(matches!(
+      evaluate(&mut session, "x = 0 && 10 / x > 1"),
+      Err(CompilationError::Runtime(RuntimeError::At { error, .. }))
+        if *error == RuntimeError::DivisionByZero
+    ));
+  }
+
+  #[test]
+  fn operators_can_be_defined_and_sectioned()
+  {
+    let mut session = Session::default();
+    declare(&mut session, "def (|>) x f = f x ;").unwrap();
+    let (value, typ) = evaluate(&mut session, "-3 |> (10 -)").unwrap();
+    assert_eq!(value, Value::Integer(13));
+    assert_eq!(typ.to_string(), "Int");
+    let (value, typ) = evaluate(&mut session, "30 |> (/ 2) |> (20 -)").unwrap();
+    assert_eq!(value, Value::Integer(5));
+    assert_eq!(typ.to_string(), "Int");
+  }
+
+  #[test]
+  fn recursion_with_primitives()
+  {
+    let mut session = Session::default();
+    declare(
+      &mut session,
+      "def fact n = if n <= 1 then 1 else n * fact (n - 1) ;",
+    )
+    .unwrap();
+    let (value, _) = evaluate(&mut session, "fact 20").unwrap();
+    assert_eq!(value, Value::Integer(2432902008176640000));
+    assert!(matches!(
+      evaluate(&mut session, "fact 21"),
+      Err(CompilationError::Runtime(RuntimeError::At { error, .. }))
+        if *error == RuntimeError::Overflow
+    ));
+  }
+
+  #[test]
+  fn tuples_are_built_and_taken_apart()
+  {
+    let mut session = Session::default();
+    declare(&mut session, "def swap (x, y) = (y, x) ;").unwrap();
+    let (value, typ) =
+      evaluate(&mut session, "let p = swap (1, (`a`, ())) in (p.0.1, p.1)")
+        .unwrap();
+    assert_eq!(value, Value::Tuple(vec![Value::Unit, Value::Integer(1)]));
+    assert_eq!(typ.to_string(), "Unit * Int");
+  }
+
+  #[test]
+  fn records_are_built_read_and_updated()
+  {
+    let mut session = Session::default();
+    declare(
+      &mut session,
+      "def birthday (p : { name : String; age : Int }) = { p with age = p.age \
+       + 1 } ;",
+    )
+    .unwrap();
+    let (value, typ) = evaluate(
+      &mut session,
+      "let p = birthday { name = `ada`; age = 36 } in (p.age, p.name)",
+    )
+    .unwrap();
+    assert_eq!(
+      value,
+      Value::Tuple(vec![Value::Integer(37), Value::String("ada".into())])
+    );
+    assert_eq!(typ.to_string(), "Int * String");
+    let (value, _) =
+      evaluate(&mut session, "{ a = 1; b = 2 } = { b = 2; a = 1 }").unwrap();
+    assert_eq!(value, Value::Bool(true));
+  }
+
+  #[test]
+  fn row_polymorphic_functions_accept_any_record_with_their_fields()
+  {
+    let mut session = Session::default();
+    declare(&mut session, "def older r = { r with age = r.age + 1 } ;")
+      .unwrap();
+    declare(&mut session, "def tag r = { id = 0 | { r without name } } ;")
+      .unwrap();
+    let (value, typ) = evaluate(
+      &mut session,
+      "((older { age = 1 }).age, tag (older { name = `ada`; age = 36 }))",
+    )
+    .unwrap();
+    assert_eq!(
+      value,
+      Value::Tuple(vec![
+        Value::Integer(2),
+        Value::Record(vec![
+          ("age".into(), Value::Integer(37)),
+          ("id".into(), Value::Integer(0)),
+        ]),
+      ])
+    );
+    assert_eq!(typ.to_string(), "Int * { age : Int; id : Int }");
+  }
+
+  #[test]
+  fn lists_are_built_and_taken_apart()
+  {
+    let mut session = Session::default();
+    declare(
+      &mut session,
+      "def map f l = match l with [] -> [] | x :: xs -> f x :: map f xs ;",
+    )
+    .unwrap();
+    declare(
+      &mut session,
+      "def sum l = match l with [] -> 0 | [x] -> x | x :: xs -> x + sum xs ;",
+    )
+    .unwrap();
+    let (value, typ) =
+      evaluate(&mut session, "map (fun x -> x * 2) (0 :: [1, 2])").unwrap();
+    assert_eq!(value.to_string(), "[0, 2, 4]");
+    assert_eq!(typ.to_string(), "List Int");
+    let (value, _) = evaluate(&mut session, "sum [1, 2, 3]").unwrap();
+    assert_eq!(value, Value::Integer(6));
+    let (value, typ) = evaluate(&mut session, "map").unwrap();
+    assert_eq!(value.to_string(), "<fun>");
+    assert_eq!(typ.to_string(), "('a -> 'b) -> List 'a -> List 'b");
+    let (value, _) =
+      evaluate(&mut session, "([1, 2] < [1, 2, 0], [] = [1] :: [])").unwrap();
+    assert_eq!(
+      value,
+      Value::Tuple(vec![Value::Bool(true), Value::Bool(false)])
+    );
+  }
+
+  #[test]
+  fn constructors_compare_in_declaration_order()
+  {
+    let mut session = Session::default();
+    declare(
+      &mut session,
+      "type shape = Dot | Circle of Float | Square of Float ;",
+    )
+    .unwrap();
+    let (value, _) = evaluate(
+      &mut session,
+      "(Dot < Circle 1.0, Circle 2.0 < Circle 1.0, Square 1.0 = Square 1.0)",
+    )
+    .unwrap();
+    assert_eq!(
+      value,
+      Value::Tuple(vec![
+        Value::Bool(true),
+        Value::Bool(false),
+        Value::Bool(true)
+      ])
+    );
+  }
+
+  #[test]
+  fn rejected_type_declarations_bind_nothing()
+  {
+    let mut session = Session::default();
+    assert!(matches!(
+      declare(&mut session, "type t = A of 'a ;"),
+      Err(CompilationError::Type(TypeError::UnboundTypeVariable(_)))
+    ));
+    assert!(matches!(
+      evaluate(&mut session, "A"),
+      Err(CompilationError::Encoding(_))
+    ));
+  }
+
+  #[test]
+  fn structures_group_declarations_under_a_name()
+  {
+    let mut session = Session::with_prelude();
+    let declared = declare(
+      &mut session,
+      "structure Stack = struct
+         type 'a stack = Empty | Push of 'a * 'a stack ;
+         def size s = match s with Empty -> 0 | Push (_, s) -> 1 + size s ;
+         structure Int = struct val zero = 0 ; end ;
+       end ;",
+    )
+    .unwrap();
+    assert_eq!(declared.len(), 4);
+    let (value, typ) = evaluate(
+      &mut session,
+      "Stack.size (Stack.Push (Stack.Int.zero, Stack.Empty))",
+    )
+    .unwrap();
+    assert_eq!(value, Value::Integer(1));
+    assert_eq!(typ.to_string(), "Int");
+    declare(&mut session, "open Stack ;").unwrap();
+    let (value, _) = evaluate(&mut session, "size (Push (1, Empty))").unwrap();
+    assert_eq!(value, Value::Integer(1));
+  }
+
+  #[test]
+  fn members_match_and_annotate_through_their_structure()
+  {
+    let mut session = Session::with_prelude();
+    declare(
+      &mut session,
+      "structure Shape = struct type t = Circle of Int | Square of Int ; end ;",
+    )
+    .unwrap();
+    let (value, typ) = evaluate(
+      &mut session,
+      "(fun (s : Shape.t) -> match s with Shape.Circle r -> 3 * r * r | \
+       Shape.Square a -> a * a) (Shape.Circle 2)",
+    )
+    .unwrap();
+    assert_eq!(value, Value::Integer(12));
+    assert_eq!(typ.to_string(), "Int");
+    assert!(session.take_warnings().is_empty());
+    assert!(matches!(
+      evaluate(&mut session, "fun s -> match s with Shape.Triangle -> 0"),
+      Err(CompilationError::Encoding(TransformError::NoMember { .. }))
+    ));
+  }
+
+  #[test]
+  fn failed_structures_bind_nothing()
+  {
+    let mut session = Session::default();
+    assert!(matches!(
+      declare(&mut session, "structure A = struct val x = 1 / 0 ; end ;"),
+      Err(CompilationError::Runtime(RuntimeError::At { error, .. }))
+        if *error == RuntimeError::DivisionByZero
+    ));
+    assert!(matches!(
+      evaluate(&mut session, "A.x"),
+      Err(CompilationError::Encoding(_))
+    ));
+    declare(&mut session, "val z = 2 ;").unwrap();
+    let (value, _) = evaluate(&mut session, "z").unwrap();
+    assert_eq!(value, Value::Integer(2));
+  }
+
+  #[test]
+  fn deep_recursion_is_a_runtime_error()
+  {
+    on_large_stack(|| {
+      let mut session = Session::default();
+      declare(
+        &mut session,
+        "def count n = if n = 0 then 0 else 1 + count (n - 1) ;",
+      )
+      .unwrap();
+      let (value, _) = evaluate(&mut session, "count 10000").unwrap();
+      assert_eq!(value, Value::Integer(10000));
+      assert!(matches!(
+        evaluate(&mut session, "count 1000000"),
+        Err(CompilationError::Runtime(RuntimeError::At { error, .. }))
+          if *error == RuntimeError::StackOverflow
+      ));
+      let (value, _) = evaluate(&mut session, "count 10").unwrap();
+      assert_eq!(value, Value::Integer(10));
+    })
+  }
+
+  #[test]
+  fn matches_take_recursive_values_apart()
+  {
+    let mut session = Session::default();
+    declare(&mut session, "type 'a list = Nil | Cons of 'a * 'a list ;")
+      .unwrap();
+    declare(
+      &mut session,
+      "def sum l = match l with | Nil -> 0 | Cons (x, rest) -> x + sum rest ;",
+    )
+    .unwrap();
+    let (value, typ) =
+      evaluate(&mut session, "sum (Cons (1, Cons (2, Nil)))").unwrap();
+    assert_eq!(value, Value::Integer(3));
+    assert_eq!(typ.to_string(), "Int");
+  }
+
+  #[test]
+  fn unmatched_values_are_a_runtime_error()
+  {
+    let mut session = Session::default();
+    declare(&mut session, "type 'a option = None | Some of 'a ;").unwrap();
+    assert!(matches!(
+      evaluate(&mut session, "match Some 2 with Some 1 -> true | None -> false"),
+      Err(CompilationError::Runtime(RuntimeError::MatchFailure(value)))
+        if value.to_string() == "Some 2"
+    ));
+    assert!(matches!(
+      evaluate(&mut session, "(fun (Some x) -> x) None"),
+      Err(CompilationError::Runtime(RuntimeError::At { error, .. }))
+        if matches!(
+          *error,
+          RuntimeError::MatchFailure(ref value) if value.to_string() == "None"
+        )
+    ));
+  }
+
+  #[test]
+  fn checked_matches_forget_their_scrutinee_types()
+  {
+    let source = "def f o = match o with Some x -> x | None -> 0 ;";
+    let span = Lexer::from_str(source)
+      .find(|lexeme| lexeme.value() == "match")
+      .unwrap()
+      .span();
+    let mut session = Session::with_prelude();
+    declare(&mut session, source).unwrap();
+    assert_eq!(session.types.scrutinee(span), None);
+  }
+
+  #[test]
+  fn runtime_errors_are_located_at_the_innermost_application_of_the_user()
+  {
+    let mut session = Session::with_prelude();
+    let located = |result: Result<(Value, types::Scheme)>| match result {
+      | Err(CompilationError::Runtime(RuntimeError::At {
+        span,
+        ..
+      })) => Some((span.start.offset, span.end.offset)),
+      | _ => None,
+    };
+    assert_eq!(
+      located(evaluate(&mut session, "fold_left (fun a x -> a / x) 1 [0]")),
+      Some((22, 27))
+    );
+    assert_eq!(
+      located(evaluate(&mut session, "map (fun (Some x) -> x) [None]")),
+      Some((0, 30))
+    );
+  }
+}
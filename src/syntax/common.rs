@@ -1,7 +1,19 @@
+mod primitive;
+mod span;
+
+pub use primitive::Primitive;
+pub use span::{
+  Location,
+  Span,
+};
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Literal
 {
   String(String),
-  Numeric(String),
+  Integer(i64),
+  Float(f64),
   Boolean(bool),
+  /// `()`, the only value of type `Unit`.
+  Unit,
 }
@@ -0,0 +1,101 @@
+/// An operation that is built into the language and implemented natively by
+/// the evaluator, bound to its name before any declaration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Primitive
+{
+  Add,
+  Subtract,
+  Multiply,
+  Divide,
+  Modulo,
+  Negate,
+  AddFloat,
+  SubtractFloat,
+  MultiplyFloat,
+  DivideFloat,
+  NegateFloat,
+  Float,
+  Truncate,
+  Equal,
+  Less,
+  LessOrEqual,
+  Greater,
+  GreaterOrEqual,
+  Concatenate,
+  Not,
+  And,
+  Or,
+  Cons,
+}
+
+impl Primitive
+{
+  /// Every primitive, in the order they are bound.
+  pub const ALL: [Primitive; 23] = [
+    Primitive::Add,
+    Primitive::Subtract,
+    Primitive::Multiply,
+    Primitive::Divide,
+    Primitive::Modulo,
+    Primitive::Negate,
+    Primitive::AddFloat,
+    Primitive::SubtractFloat,
+    Primitive::MultiplyFloat,
+    Primitive::DivideFloat,
+    Primitive::NegateFloat,
+    Primitive::Float,
+    Primitive::Truncate,
+    Primitive::Equal,
+    Primitive::Less,
+    Primitive::LessOrEqual,
+    Primitive::Greater,
+    Primitive::GreaterOrEqual,
+    Primitive::Concatenate,
+    Primitive::Not,
+    Primitive::And,
+    Primitive::Or,
+    Primitive::Cons,
+  ];
+
+  pub fn name(self) -> &'static str
+  {
+    match self {
+      | Primitive::Add => "+",
+      | Primitive::Subtract => "-",
+      | Primitive::Multiply => "*",
+      | Primitive::Divide => "/",
+      | Primitive::Modulo => "mod",
+      | Primitive::Negate => "~-",
+      | Primitive::AddFloat => "+.",
+      | Primitive::SubtractFloat => "-.",
+      | Primitive::MultiplyFloat => "*.",
+      | Primitive::DivideFloat => "/.",
+      | Primitive::NegateFloat => "~-.",
+      | Primitive::Float => "float",
+      | Primitive::Truncate => "truncate",
+      | Primitive::Equal => "=",
+      | Primitive::Less => "<",
+      | Primitive::LessOrEqual => "<=",
+      | Primitive::Greater => ">",
+      | Primitive::GreaterOrEqual => ">=",
+      | Primitive::Concatenate => "^",
+      | Primitive::Not => "not",
+      | Primitive::And => "&&",
+      | Primitive::Or => "||",
+      | Primitive::Cons => "::",
+    }
+  }
+
+  /// The number of arguments the primitive needs before it can be computed.
+  pub fn arity(self) -> usize
+  {
+    match self {
+      | Primitive::Negate
+      | Primitive::NegateFloat
+      | Primitive::Float
+      | Primitive::Truncate
+      | Primitive::Not => 1,
+      | _ => 2,
+    }
+  }
+}
@@ -0,0 +1,153 @@
+use std::hash::{
+  Hash,
+  Hasher,
+};
+
+/// A position in the source text. The offset is counted in bytes, lines and
+/// columns start at one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Location
+{
+  pub offset: usize,
+  pub line: usize,
+  pub column: usize,
+}
+
+impl Location
+{
+  pub fn new(
+    offset: usize,
+    line: usize,
+    column: usize,
+  ) -> Self
+  {
+    Self {
+      offset,
+      line,
+      column,
+    }
+  }
+
+  pub fn advance(
+    &mut self,
+    char: char,
+  )
+  {
+    self.offset += char.len_utf8();
+    match char {
+      | '\n' => {
+        self.line += 1;
+        self.column = 1;
+      },
+      | _ => self.column += 1,
+    }
+  }
+}
+
+impl Default for Location
+{
+  fn default() -> Self
+  {
+    Self::new(0, 1, 1)
+  }
+}
+
+/// The region of source text a lexeme or syntax node was read from. The end
+/// location is exclusive.
+///
+/// Spans never take part in comparisons or hashing, so nodes built by hand
+/// compare equal to the ones produced by the parser.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Span
+{
+  pub start: Location,
+  pub end: Location,
+}
+
+impl Span
+{
+  pub fn new(
+    start: Location,
+    end: Location,
+  ) -> Self
+  {
+    Self {
+      start,
+      end,
+    }
+  }
+
+  /// Smallest span covering both `self` and `other`.
+  pub fn merge(
+    self,
+    other: Span,
+  ) -> Span
+  {
+    Span {
+      start: std::cmp::min(self.start, other.start),
+      end: std::cmp::max(self.end, other.end),
+    }
+  }
+}
+
+impl PartialEq for Span
+{
+  fn eq(
+    &self,
+    _other: &Self,
+  ) -> bool
+  {
+    true
+  }
+}
+
+impl Eq for Span
+{
+}
+
+impl Hash for Span
+{
+  fn hash<H>(
+    &self,
+    _state: &mut H,
+  ) where
+    H: Hasher,
+  {
+  }
+}
+
+#[cfg(test)]
+mod spec
+{
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn advance_tracks_lines_and_columns()
+  {
+    let mut location = Location::default();
+    for char in "ab\ncd".chars() {
+      location.advance(char);
+    }
+    assert_eq!(location, Location::new(5, 2, 3));
+  }
+
+  #[test]
+  fn advance_counts_bytes()
+  {
+    let mut location = Location::default();
+    location.advance('λ');
+    assert_eq!(location, Location::new(2, 1, 2));
+  }
+
+  #[test]
+  fn merge_covers_both_spans()
+  {
+    let left = Span::new(Location::new(0, 1, 1), Location::new(3, 1, 4));
+    let right = Span::new(Location::new(5, 1, 6), Location::new(7, 1, 8));
+    let merged = left.merge(right);
+    assert_eq!(merged.start, left.start);
+    assert_eq!(merged.end, right.end);
+  }
+}
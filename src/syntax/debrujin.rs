@@ -2,10 +2,12 @@ mod _specification;
 mod common;
 mod declaration;
 mod expression;
+mod program;
 mod top_level;
 pub mod transformations;
 
 pub use common::*;
 pub use declaration::*;
 pub use expression::*;
+pub use program::*;
 pub use top_level::*;
@@ -1,3 +1,5 @@
 mod identifier;
 
 pub use identifier::Identifier;
+
+pub use crate::syntax::common::Primitive;
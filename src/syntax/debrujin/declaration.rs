@@ -1 +1,15 @@
+mod data;
+mod def;
+mod structure;
 mod val;
+
+pub use data::{
+  Constructor,
+  Data,
+};
+pub use def::Def;
+pub use structure::{
+  Open,
+  Structure,
+};
+pub use val::Val;
@@ -0,0 +1,15 @@
+/// The constructors of a data type, each of them is bound in order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Data
+{
+  pub constructors: Vec<Constructor>,
+}
+
+/// The constructor at `tag` within its type, named for display.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Constructor
+{
+  pub tag: usize,
+  pub name: String,
+  pub takes_argument: bool,
+}
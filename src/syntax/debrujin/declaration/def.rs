@@ -0,0 +1,7 @@
+use super::super::Abstraction;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Def
+{
+  pub functions: Vec<Abstraction>,
+}
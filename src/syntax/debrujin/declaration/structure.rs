@@ -0,0 +1,19 @@
+use super::super::{
+  Expression,
+  TopLevel,
+};
+
+/// Evaluates `declarations` in order into a structure of every value they
+/// bind.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Structure
+{
+  pub declarations: Vec<TopLevel>,
+}
+
+/// Binds every member of `structure`, in order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Open
+{
+  pub structure: Expression,
+}
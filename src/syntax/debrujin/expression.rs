@@ -1,8 +1,43 @@
 mod abstraction;
 mod application;
+mod conditional;
+mod decision_tree;
+mod let_in;
+mod let_rec;
+mod list;
+mod member;
+mod pattern_match;
+mod record;
+mod tuple;
 
 pub use abstraction::Abstraction;
 pub use application::Application;
+pub use conditional::Conditional;
+pub use decision_tree::{
+  Case,
+  DecisionTree,
+};
+pub use let_in::Let;
+pub use let_rec::LetRec;
+pub use list::List;
+pub use member::Member;
+pub use pattern_match::{
+  Arm,
+  Match,
+  Path,
+  Pattern,
+};
+pub use record::{
+  Extension,
+  FieldAccess,
+  Record,
+  Restriction,
+  Update,
+};
+pub use tuple::{
+  Projection,
+  Tuple,
+};
 
 pub use super::common::Identifier;
 pub use crate::syntax::common::Literal;
@@ -14,6 +49,19 @@ pub enum Expression
   Identifier(Identifier),
   Abstraction(Box<Abstraction>),
   Application(Box<Application>),
+  Conditional(Box<Conditional>),
+  Let(Box<Let>),
+  LetRec(Box<LetRec>),
+  Tuple(Box<Tuple>),
+  Projection(Box<Projection>),
+  List(Box<List>),
+  Member(Box<Member>),
+  Match(Box<Match>),
+  Record(Box<Record>),
+  FieldAccess(Box<FieldAccess>),
+  Update(Box<Update>),
+  Extension(Box<Extension>),
+  Restriction(Box<Restriction>),
 }
 
 impl From<Literal> for Expression
@@ -47,3 +95,107 @@ impl From<Application> for Expression
     Self::Application(Box::new(application))
   }
 }
+
+impl From<Conditional> for Expression
+{
+  fn from(conditional: Conditional) -> Self
+  {
+    Self::Conditional(Box::new(conditional))
+  }
+}
+
+impl From<Let> for Expression
+{
+  fn from(let_in: Let) -> Self
+  {
+    Self::Let(Box::new(let_in))
+  }
+}
+
+impl From<LetRec> for Expression
+{
+  fn from(let_rec: LetRec) -> Self
+  {
+    Self::LetRec(Box::new(let_rec))
+  }
+}
+
+impl From<Tuple> for Expression
+{
+  fn from(tuple: Tuple) -> Self
+  {
+    Self::Tuple(Box::new(tuple))
+  }
+}
+
+impl From<Projection> for Expression
+{
+  fn from(projection: Projection) -> Self
+  {
+    Self::Projection(Box::new(projection))
+  }
+}
+
+impl From<List> for Expression
+{
+  fn from(list: List) -> Self
+  {
+    Self::List(Box::new(list))
+  }
+}
+
+impl From<Match> for Expression
+{
+  fn from(pattern_match: Match) -> Self
+  {
+    Self::Match(Box::new(pattern_match))
+  }
+}
+
+impl From<Record> for Expression
+{
+  fn from(record: Record) -> Self
+  {
+    Self::Record(Box::new(record))
+  }
+}
+
+impl From<FieldAccess> for Expression
+{
+  fn from(access: FieldAccess) -> Self
+  {
+    Self::FieldAccess(Box::new(access))
+  }
+}
+
+impl From<Update> for Expression
+{
+  fn from(update: Update) -> Self
+  {
+    Self::Update(Box::new(update))
+  }
+}
+
+impl From<Extension> for Expression
+{
+  fn from(extension: Extension) -> Self
+  {
+    Self::Extension(Box::new(extension))
+  }
+}
+
+impl From<Restriction> for Expression
+{
+  fn from(restriction: Restriction) -> Self
+  {
+    Self::Restriction(Box::new(restriction))
+  }
+}
+
+impl From<Member> for Expression
+{
+  fn from(member: Member) -> Self
+  {
+    Self::Member(Box::new(member))
+  }
+}
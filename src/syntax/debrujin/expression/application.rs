@@ -1,8 +1,13 @@
 use super::Expression;
+use crate::syntax::common::Span;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Application
 {
   pub abstraction: Expression,
   pub argument: Expression,
+  /// Where the application was written, to locate the runtime errors it
+  /// raises. The prelude's applications have none, so that errors raised in
+  /// it are located at the application of the user that reached it.
+  pub span: Option<Span>,
 }
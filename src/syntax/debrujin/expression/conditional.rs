@@ -0,0 +1,10 @@
+use super::Expression;
+
+/// Evaluates only the branch selected by `condition`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conditional
+{
+  pub condition: Expression,
+  pub consequence: Expression,
+  pub alternative: Expression,
+}
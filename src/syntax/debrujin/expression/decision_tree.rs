@@ -0,0 +1,327 @@
+use super::{
+  Literal,
+  Path,
+  Pattern,
+};
+
+/// What a part of a matched value is tested against.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Case
+{
+  Constructor(usize),
+  Literal(Literal),
+  Nil,
+  Cons,
+}
+
+/// Selects the arm of a match by testing each part of the value at most once.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecisionTree
+{
+  /// No arm matches the value.
+  Fail,
+  Leaf(usize),
+  /// Follows the first case that the part at `path` agrees with, or
+  /// `default` when there is none. Without a default the cases cover every
+  /// possible value.
+  Switch
+  {
+    path: Path,
+    cases: Vec<(Case, DecisionTree)>,
+    default: Option<Box<DecisionTree>>,
+  },
+}
+
+/// The parts of a value that an arm still has to test.
+type Row<'a> = (Vec<(Path, &'a Pattern)>, usize);
+
+impl DecisionTree
+{
+  /// Builds the tree selecting the first of `patterns` that matches.
+  pub fn compile(patterns: &[Pattern]) -> Self
+  {
+    let rows = patterns
+      .iter()
+      .enumerate()
+      .map(|(arm, pattern)| {
+        let mut tests = vec![];
+        refutable(vec![], pattern, &mut tests);
+        (tests, arm)
+      })
+      .collect();
+    Self::from_rows(rows)
+  }
+
+  fn from_rows(rows: Vec<Row>) -> Self
+  {
+    let Some((tests, arm)) = rows.first()
+    else {
+      return Self::Fail
+    };
+    let Some((path, pattern)) = tests.first()
+    else {
+      return Self::Leaf(*arm)
+    };
+    let path = path.clone();
+    let mut cases = vec![];
+    for (tests, _) in rows.iter() {
+      if let Some(case) = tests
+        .iter()
+        .find(|(test, _)| test == &path)
+        .map(|(_, pattern)| Case::of(pattern))
+      {
+        if !cases.contains(&case) {
+          cases.push(case);
+        }
+      }
+    }
+    let complete = match pattern {
+      | Pattern::Constructor {
+        constructors,
+        ..
+      } => cases.len() == *constructors,
+      | Pattern::Literal(Literal::Boolean(_)) => cases.len() == 2,
+      | Pattern::Literal(Literal::Unit) => true,
+      | Pattern::Nil | Pattern::Cons(..) => cases.len() == 2,
+      | _ => false,
+    };
+    let default = (!complete).then(|| {
+      let rows = rows
+        .iter()
+        .filter(|(tests, _)| {
+          tests
+            .iter()
+            .all(|(test, _)| test != &path)
+        })
+        .cloned()
+        .collect();
+      Box::new(Self::from_rows(rows))
+    });
+    let cases = cases
+      .into_iter()
+      .map(|case| {
+        let rows = rows
+          .iter()
+          .filter_map(|(tests, arm)| {
+            specialise(tests, &path, &case).map(|tests| (tests, *arm))
+          })
+          .collect();
+        (case, Self::from_rows(rows))
+      })
+      .collect();
+    Self::Switch {
+      path,
+      cases,
+      default,
+    }
+  }
+}
+
+impl Case
+{
+  fn of(pattern: &Pattern) -> Self
+  {
+    match pattern {
+      | Pattern::Constructor {
+        tag,
+        ..
+      } => Self::Constructor(*tag),
+      | Pattern::Literal(literal) => Self::Literal(literal.clone()),
+      | Pattern::Nil => Self::Nil,
+      | Pattern::Cons(..) => Self::Cons,
+      | _ => unreachable!("only constructors, literals and lists are tested"),
+    }
+  }
+}
+
+/// Collects the parts of `pattern` that do not match every value.
+fn refutable<'a>(
+  path: Path,
+  pattern: &'a Pattern,
+  tests: &mut Vec<(Path, &'a Pattern)>,
+)
+{
+  match pattern {
+    | Pattern::Binding | Pattern::Wildcard => (),
+    | Pattern::Tuple(elements) => {
+      for (index, element) in elements.iter().enumerate() {
+        let mut path = path.clone();
+        path.push(index);
+        refutable(path, element, tests);
+      }
+    },
+    | Pattern::Literal(_)
+    | Pattern::Constructor {
+      ..
+    }
+    | Pattern::Nil
+    | Pattern::Cons(..) => tests.push((path, pattern)),
+  }
+}
+
+/// The tests left once the part at `path` agrees with `case`, or `None` when
+/// the row cannot match anymore.
+fn specialise<'a>(
+  tests: &[(Path, &'a Pattern)],
+  path: &Path,
+  case: &Case,
+) -> Option<Vec<(Path, &'a Pattern)>>
+{
+  let mut specialised = vec![];
+  for (test, pattern) in tests {
+    if test != path {
+      specialised.push((test.clone(), *pattern));
+      continue
+    }
+    match (pattern, case) {
+      | (
+        Pattern::Constructor {
+          tag,
+          argument,
+          ..
+        },
+        Case::Constructor(expected),
+      ) if tag == expected =>
+        if let Some(argument) = argument {
+          let mut path = path.clone();
+          path.push(0);
+          refutable(path, argument, &mut specialised);
+        },
+      | (Pattern::Literal(literal), Case::Literal(expected))
+        if literal == expected => {},
+      | (Pattern::Nil, Case::Nil) => {},
+      | (Pattern::Cons(head, tail), Case::Cons) => {
+        let mut head_path = path.clone();
+        head_path.push(0);
+        refutable(head_path, head, &mut specialised);
+        let mut tail_path = path.clone();
+        tail_path.push(1);
+        refutable(tail_path, tail, &mut specialised);
+      },
+      | _ => return None,
+    }
+  }
+  Some(specialised)
+}
+
+#[cfg(test)]
+mod spec
+{
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  fn constructor(
+    tag: usize,
+    argument: Option<Pattern>,
+  ) -> Pattern
+  {
+    Pattern::Constructor {
+      tag,
+      constructors: 2,
+      argument: argument.map(Box::new),
+    }
+  }
+
+  #[test]
+  fn irrefutable_patterns_are_leaves()
+  {
+    assert_eq!(
+      DecisionTree::compile(&[
+        Pattern::Tuple(vec![Pattern::Binding, Pattern::Wildcard]),
+        Pattern::Binding,
+      ]),
+      DecisionTree::Leaf(0)
+    );
+  }
+
+  #[test]
+  fn covered_constructors_need_no_default()
+  {
+    assert_eq!(
+      DecisionTree::compile(&[
+        constructor(1, Some(Pattern::Binding)),
+        constructor(0, None),
+      ]),
+      DecisionTree::Switch {
+        path: vec![],
+        cases: vec![
+          (Case::Constructor(1), DecisionTree::Leaf(0)),
+          (Case::Constructor(0), DecisionTree::Leaf(1)),
+        ],
+        default: None,
+      }
+    );
+  }
+
+  #[test]
+  fn literals_fall_back_to_the_default()
+  {
+    assert_eq!(
+      DecisionTree::compile(&[Pattern::Literal(Literal::Integer(0))]),
+      DecisionTree::Switch {
+        path: vec![],
+        cases: vec![(
+          Case::Literal(Literal::Integer(0)),
+          DecisionTree::Leaf(0)
+        )],
+        default: Some(Box::new(DecisionTree::Fail)),
+      }
+    );
+  }
+
+  #[test]
+  fn lists_are_covered_by_nil_and_cons()
+  {
+    let cons = |head, tail| Pattern::Cons(Box::new(head), Box::new(tail));
+    assert_eq!(
+      DecisionTree::compile(&[
+        Pattern::Nil,
+        cons(Pattern::Binding, Pattern::Nil),
+        cons(Pattern::Wildcard, Pattern::Binding),
+      ]),
+      DecisionTree::Switch {
+        path: vec![],
+        cases: vec![
+          (Case::Nil, DecisionTree::Leaf(0)),
+          (Case::Cons, DecisionTree::Switch {
+            path: vec![1],
+            cases: vec![(Case::Nil, DecisionTree::Leaf(1))],
+            default: Some(Box::new(DecisionTree::Leaf(2))),
+          }),
+        ],
+        default: None,
+      }
+    );
+  }
+
+  #[test]
+  fn nested_patterns_test_each_part_once()
+  {
+    let tree = DecisionTree::compile(&[
+      Pattern::Tuple(vec![
+        constructor(1, Some(Pattern::Literal(Literal::Boolean(true)))),
+        Pattern::Binding,
+      ]),
+      Pattern::Tuple(vec![Pattern::Wildcard, constructor(0, None)]),
+      Pattern::Wildcard,
+    ]);
+    let second = |otherwise| DecisionTree::Switch {
+      path: vec![1],
+      cases: vec![(Case::Constructor(0), DecisionTree::Leaf(1))],
+      default: Some(Box::new(otherwise)),
+    };
+    assert_eq!(tree, DecisionTree::Switch {
+      path: vec![0],
+      cases: vec![(Case::Constructor(1), DecisionTree::Switch {
+        path: vec![0, 0],
+        cases: vec![(
+          Case::Literal(Literal::Boolean(true)),
+          DecisionTree::Leaf(0)
+        )],
+        default: Some(Box::new(second(DecisionTree::Leaf(2)))),
+      })],
+      default: Some(Box::new(second(DecisionTree::Leaf(2)))),
+    });
+  }
+}
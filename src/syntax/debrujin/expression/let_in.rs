@@ -0,0 +1,10 @@
+use super::Expression;
+
+/// Evaluates `values` in order and makes them available to `body`, the last
+/// value is bound to the identifier `0`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Let
+{
+  pub values: Vec<Expression>,
+  pub body: Expression,
+}
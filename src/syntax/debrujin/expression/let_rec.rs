@@ -0,0 +1,13 @@
+use super::{
+  Abstraction,
+  Expression,
+};
+
+/// Binds `functions` in order, each of them sees all of them, the last one is
+/// bound to the identifier `0`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LetRec
+{
+  pub functions: Vec<Abstraction>,
+  pub body: Expression,
+}
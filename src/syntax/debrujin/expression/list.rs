@@ -0,0 +1,8 @@
+use super::Expression;
+
+/// Evaluates `elements` in order into a list.
+#[derive(Debug, Clone, PartialEq)]
+pub struct List
+{
+  pub elements: Vec<Expression>,
+}
@@ -0,0 +1,10 @@
+use super::Expression;
+
+/// The member of `structure` at `index`, members are in the order they were
+/// bound in the structure.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Member
+{
+  pub structure: Expression,
+  pub index: usize,
+}
@@ -0,0 +1,96 @@
+use super::{
+  DecisionTree,
+  Expression,
+  Literal,
+};
+
+/// The position of a part of a matched value, each step is the index of a
+/// tuple element, `0` for the argument of a constructor, or `0` for the head
+/// and `1` for the tail of a list.
+pub type Path = Vec<usize>;
+
+/// Evaluates `scrutinee` and the arm selected for it by `tree`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Match
+{
+  pub scrutinee: Expression,
+  pub tree: DecisionTree,
+  pub arms: Vec<Arm>,
+}
+
+/// Evaluates `body` with the parts of the matched value at `bindings` in
+/// scope, the last one is bound to the identifier `0`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Arm
+{
+  pub bindings: Vec<Path>,
+  pub body: Expression,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pattern
+{
+  /// Matches anything and binds it.
+  Binding,
+  Wildcard,
+  Literal(Literal),
+  Tuple(Vec<Pattern>),
+  /// Matches a variant of `tag`, out of the `constructors` of its type.
+  Constructor
+  {
+    tag: usize,
+    constructors: usize,
+    argument: Option<Box<Pattern>>,
+  },
+  /// Matches the empty list.
+  Nil,
+  /// Matches a list that is not empty by its head and its tail.
+  Cons(Box<Pattern>, Box<Pattern>),
+}
+
+impl Pattern
+{
+  /// The paths of the parts bound by the pattern, from left to right.
+  pub fn bindings(&self) -> Vec<Path>
+  {
+    let mut bindings = vec![];
+    self.collect_bindings(vec![], &mut bindings);
+    bindings
+  }
+
+  fn collect_bindings(
+    &self,
+    path: Path,
+    bindings: &mut Vec<Path>,
+  )
+  {
+    match self {
+      | Self::Binding => bindings.push(path),
+      | Self::Wildcard | Self::Literal(_) | Self::Nil => (),
+      | Self::Tuple(elements) => {
+        for (index, element) in elements.iter().enumerate() {
+          let mut path = path.clone();
+          path.push(index);
+          element.collect_bindings(path, bindings);
+        }
+      },
+      | Self::Constructor {
+        argument,
+        ..
+      } =>
+        if let Some(argument) = argument {
+          let mut path = path;
+          path.push(0);
+          argument.collect_bindings(path, bindings);
+        },
+      | Self::Cons(head, tail) => {
+        let mut head_path = path.clone();
+        head_path.push(0);
+        head.collect_bindings(head_path, bindings);
+        let mut tail_path = path;
+        tail_path.push(1);
+        tail.collect_bindings(tail_path, bindings);
+      },
+    }
+  }
+}
@@ -0,0 +1,41 @@
+use super::Expression;
+
+/// Evaluates the values of `fields` in order into a record.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record
+{
+  pub fields: Vec<(String, Expression)>,
+}
+
+/// The value of the field `field` of `record`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldAccess
+{
+  pub record: Expression,
+  pub field: String,
+}
+
+/// A copy of `record` with the values of `fields` in place of its own,
+/// evaluated after `record`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Update
+{
+  pub record: Expression,
+  pub fields: Vec<(String, Expression)>,
+}
+
+/// `record` with the new fields `fields`, evaluated before `record`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Extension
+{
+  pub fields: Vec<(String, Expression)>,
+  pub record: Expression,
+}
+
+/// `record` without the fields `fields`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Restriction
+{
+  pub record: Expression,
+  pub fields: Vec<String>,
+}
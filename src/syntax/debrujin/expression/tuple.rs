@@ -0,0 +1,16 @@
+use super::Expression;
+
+/// Evaluates `elements` in order into a tuple.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tuple
+{
+  pub elements: Vec<Expression>,
+}
+
+/// The element of `tuple` at `index`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Projection
+{
+  pub tuple: Expression,
+  pub index: usize,
+}
@@ -0,0 +1,7 @@
+use super::TopLevel;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Program
+{
+  pub declarations: Vec<TopLevel>,
+}
@@ -1,15 +1,57 @@
-pub use crate::syntax::surface::ValBinding;
+use super::{
+  Data,
+  Def,
+  Open,
+  Structure,
+  Val,
+};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum TopLevel
 {
-  Val(Box<ValBinding>),
+  Val(Box<Val>),
+  Def(Box<Def>),
+  Data(Box<Data>),
+  Structure(Box<Structure>),
+  Open(Box<Open>),
 }
 
-impl From<ValBinding> for TopLevel
+impl From<Val> for TopLevel
 {
-  fn from(val: ValBinding) -> Self
+  fn from(val: Val) -> Self
   {
     Self::Val(Box::new(val))
   }
 }
+
+impl From<Def> for TopLevel
+{
+  fn from(def: Def) -> Self
+  {
+    Self::Def(Box::new(def))
+  }
+}
+
+impl From<Data> for TopLevel
+{
+  fn from(data: Data) -> Self
+  {
+    Self::Data(Box::new(data))
+  }
+}
+
+impl From<Structure> for TopLevel
+{
+  fn from(structure: Structure) -> Self
+  {
+    Self::Structure(Box::new(structure))
+  }
+}
+
+impl From<Open> for TopLevel
+{
+  fn from(open: Open) -> Self
+  {
+    Self::Open(Box::new(open))
+  }
+}
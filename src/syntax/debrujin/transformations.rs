@@ -1,4 +1,4 @@
-mod evaluation;
+pub mod evaluation;
 mod largest_free_variable;
 
 pub use evaluation::*;
@@ -1,23 +1,40 @@
+mod list;
+mod primitives;
+
+use std::rc::Rc;
+
+pub use list::List;
+use thiserror::Error;
+
 use super::LargestFreeVariable;
+use crate::diagnostics::{
+  Diagnostic,
+  ToDiagnostic,
+};
+use crate::syntax::common::Span;
 use crate::syntax::debrujin;
 use crate::transform_into::TransformInto;
 
-pub trait Evaluate<'a>
+pub trait Evaluate<'a, Representation>
 {
   fn evaluate(
     &self,
     context: &'a mut Context,
-  ) -> Value;
+  ) -> Result<Representation, RuntimeError>;
 }
 
-impl<'a, Representation> Evaluate<'a> for Representation
+impl<'a, SourceRepresentation, TargetRepresentation>
+  Evaluate<'a, TargetRepresentation> for SourceRepresentation
 where
-  Representation: TransformInto<Value, Context<'a> = &'a mut Context>,
+  SourceRepresentation: TransformInto<
+    Result<TargetRepresentation, RuntimeError>,
+    Context<'a> = &'a mut Context,
+  >,
 {
   fn evaluate(
     &self,
     context: &'a mut Context,
-  ) -> Value
+  ) -> Result<TargetRepresentation, RuntimeError>
   {
     self.transform(context)
   }
@@ -28,32 +45,266 @@ pub enum Value
 {
   String(String),
   Bool(bool),
-  Numeric(String),
+  Integer(i64),
+  Float(f64),
+  Unit,
+  Tuple(Vec<Value>),
+  List(List),
+  /// The values of the fields of a record, sorted by name.
+  Record(Vec<(String, Value)>),
+  /// A value built by the constructor `name`, the `tag`-th of its type.
+  Variant
+  {
+    tag: usize,
+    name: String,
+    argument: Option<Box<Value>>,
+  },
+  /// A constructor that is yet to be applied to its argument.
+  Constructor
+  {
+    tag: usize,
+    name: String,
+  },
+  /// Functions share what they capture, so that copying one does not copy
+  /// every function it closes over.
   Closure
   {
-    stack: Vec<Value>,
-    body: debrujin::Expression,
+    stack: Rc<[Value]>,
+    body: Rc<debrujin::Expression>,
+  },
+  /// The function at `index` of a group of mutually recursive `functions`,
+  /// which see the whole group on top of `stack`.
+  Recursive
+  {
+    stack: Rc<[Value]>,
+    functions: Rc<[debrujin::Abstraction]>,
+    index: usize,
+  },
+  /// A primitive applied to fewer `arguments` than it takes.
+  Primitive
+  {
+    primitive: debrujin::Primitive,
+    arguments: Vec<Value>,
+  },
+  /// The members of a structure, in the order they were bound.
+  Structure(Rc<[Value]>),
+}
+
+impl Value
+{
+  /// Every function of a recursive group, in order.
+  fn recursive(
+    stack: &Rc<[Value]>,
+    functions: &Rc<[debrujin::Abstraction]>,
+  ) -> Vec<Value>
+  {
+    (0 .. functions.len())
+      .map(|index| Value::Recursive {
+        stack: stack.clone(),
+        functions: functions.clone(),
+        index,
+      })
+      .collect()
+  }
+}
+
+impl std::fmt::Display for Value
+{
+  fn fmt(
+    &self,
+    f: &mut std::fmt::Formatter<'_>,
+  ) -> std::fmt::Result
+  {
+    match self {
+      | Value::String(value) => write!(f, "`{}`", value.replace('`', "\\`")),
+      | Value::Bool(value) => write!(f, "{}", value),
+      | Value::Integer(value) => write!(f, "{}", value),
+      // always with a decimal point or an exponent, unlike integers
+      | Value::Float(value) => write!(f, "{:?}", value),
+      | Value::Unit => write!(f, "()"),
+      | Value::Tuple(elements) => {
+        let elements = elements
+          .iter()
+          .map(Value::to_string)
+          .collect::<Vec<_>>();
+        write!(f, "({})", elements.join(", "))
+      },
+      | Value::List(list) => {
+        let elements = list
+          .iter()
+          .map(Value::to_string)
+          .collect::<Vec<_>>();
+        write!(f, "[{}]", elements.join(", "))
+      },
+      | Value::Record(fields) => {
+        let fields = fields
+          .iter()
+          .map(|(name, value)| format!("{} = {}", name, value))
+          .collect::<Vec<_>>();
+        write!(f, "{{ {} }}", fields.join("; "))
+      },
+      | Value::Variant {
+        name,
+        argument,
+        ..
+      } => match argument.as_deref() {
+        | None => write!(f, "{}", name),
+        | Some(
+          argument @ Value::Variant {
+            argument: Some(_),
+            ..
+          },
+        ) => write!(f, "{} ({})", name, argument),
+        | Some(argument) => write!(f, "{} {}", name, argument),
+      },
+      | Value::Constructor {
+        ..
+      }
+      | Value::Closure {
+        ..
+      }
+      | Value::Recursive {
+        ..
+      }
+      | Value::Primitive {
+        ..
+      } => write!(f, "<fun>"),
+      | Value::Structure(_) => write!(f, "<structure>"),
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum RuntimeError
+{
+  #[error("`{0}` is not a function")]
+  NotAFunction(Value),
+  #[error("`{0}` is not a boolean")]
+  NotABoolean(Value),
+  #[error("`{0}` is not a tuple")]
+  NotATuple(Value),
+  #[error("`{0}` has no element {1}")]
+  NoElement(Value, usize),
+  #[error("`{0}` is not a record")]
+  NotARecord(Value),
+  #[error("`{0}` has no field `{1}`")]
+  NoField(Value, String),
+  #[error("`{0}` is not a structure")]
+  NotAStructure(Value),
+  #[error("no pattern matches `{0}`")]
+  MatchFailure(Value),
+  #[error("`{0}` cannot be compared")]
+  NotComparable(Value),
+  #[error("division by zero")]
+  DivisionByZero,
+  #[error("integer overflow")]
+  Overflow,
+  #[error("stack overflow")]
+  StackOverflow,
+  /// A value of a type the program was not inferred to have, which the type
+  /// checker should have ruled out.
+  #[error("internal error: {0}")]
+  Internal(String),
+  /// `error`, raised while evaluating the application at `span`, the
+  /// innermost one that is located.
+  #[error("{error}")]
+  At
+  {
+    error: Box<RuntimeError>,
+    span: Span,
   },
 }
 
+impl RuntimeError
+{
+  /// Locates the error at the application at `span`, unless it already is.
+  fn at(
+    self,
+    span: Option<Span>,
+  ) -> Self
+  {
+    match span {
+      | Some(span) if !matches!(self, RuntimeError::At { .. }) =>
+        RuntimeError::At {
+          error: Box::new(self),
+          span,
+        },
+      | _ => self,
+    }
+  }
+}
+
+impl ToDiagnostic for RuntimeError
+{
+  fn to_diagnostic(&self) -> Diagnostic
+  {
+    let diagnostic = Diagnostic::error(self.to_string());
+    match self {
+      | RuntimeError::At {
+        error,
+        span,
+      } => diagnostic.with_primary(*span, match **error {
+        | RuntimeError::DivisionByZero => "the divisor is zero",
+        | RuntimeError::Overflow => "the result doesn't fit in 64 bits",
+        | RuntimeError::StackOverflow => "calls are nested too deeply here",
+        | _ => "while evaluating this application",
+      }),
+      | _ => diagnostic,
+    }
+  }
+}
+
 #[derive(Default)]
 pub struct Context
 {
   stack: Vec<Value>,
+  /// How many evaluations of expressions are nested in each other.
+  depth: usize,
 }
 
+/// How deep the evaluation of expressions nests before it fails with a stack
+/// overflow, rather than overflowing the stack of the interpreter. It takes a
+/// stack of `session::STACK_SIZE`.
+pub const MAXIMUM_DEPTH: usize = 100_000;
+
 impl Context
 {
+  /// A context where every primitive is bound to its name, in the order of
+  /// `Primitive::ALL`.
+  pub fn with_primitives() -> Self
+  {
+    Self {
+      stack: debrujin::Primitive::ALL
+        .into_iter()
+        .map(|primitive| Value::Primitive {
+          primitive,
+          arguments: vec![],
+        })
+        .collect(),
+      depth: 0,
+    }
+  }
+
   pub fn evaluate<'a, Representation>(
     &'a mut self,
     representation: Representation,
-  ) -> Value
+  ) -> Result<Value, RuntimeError>
   where
-    Representation: TransformInto<Value, Context<'a> = &'a mut Context>,
+    Representation:
+      TransformInto<Result<Value, RuntimeError>, Context<'a> = &'a mut Context>,
   {
     representation.transform(self)
   }
 
+  /// Pushes `value` onto the stack for everything evaluated afterwards.
+  pub fn bind(
+    &mut self,
+    value: Value,
+  )
+  {
+    self.stack.push(value);
+  }
+
   pub fn load<Result>(
     &mut self,
     closure: &[Value],
@@ -84,10 +335,24 @@ impl Context
       .cloned()
   }
 
+  /// Closes `functions` over the stack, each of them sees all of them.
+  pub fn recursive(
+    &self,
+    functions: &[debrujin::Abstraction],
+  ) -> Vec<Value>
+  {
+    let until = functions
+      .iter()
+      .map(|function| function.largest_free_variable(functions.len()))
+      .max()
+      .unwrap_or(0);
+    Value::recursive(&self.capture(until), &functions.into())
+  }
+
   pub fn capture(
     &self,
     until: usize,
-  ) -> Vec<Value>
+  ) -> Rc<[Value]>
   {
     self
       .stack
@@ -100,42 +365,128 @@ impl Context
   }
 }
 
-
-impl TransformInto<Value> for debrujin::Expression
+impl TransformInto<Result<Value, RuntimeError>> for debrujin::Expression
 {
   type Context<'a> = &'a mut Context;
 
   fn transform(
     &self,
     context: Self::Context<'_>,
-  ) -> Value
+  ) -> Result<Value, RuntimeError>
   {
-    match self {
-      | debrujin::Expression::Literal(literal) => literal.transform(context),
+    if context.depth == MAXIMUM_DEPTH {
+      return Err(RuntimeError::StackOverflow)
+    }
+    context.depth += 1;
+    let nested = &mut *context;
+    let value = match self {
+      | debrujin::Expression::Literal(literal) => literal.transform(nested),
       | debrujin::Expression::Identifier(identifier) =>
-        identifier.transform(context),
+        identifier.transform(nested),
       | debrujin::Expression::Abstraction(abstraction) =>
-        abstraction.transform(context),
-      | debrujin::Expression::Application(application) =>
-        application.transform(context),
-    }
+        abstraction.transform(nested),
+      | debrujin::Expression::Application(application) => application
+        .evaluate(nested)
+        .map_err(|error| error.at(application.span)),
+      | debrujin::Expression::Conditional(conditional) =>
+        conditional.transform(nested),
+      | debrujin::Expression::Let(let_in) => let_in.transform(nested),
+      | debrujin::Expression::LetRec(let_rec) => let_rec.transform(nested),
+      | debrujin::Expression::Tuple(tuple) => tuple.transform(nested),
+      | debrujin::Expression::Projection(projection) =>
+        projection.transform(nested),
+      | debrujin::Expression::List(list) => list.transform(nested),
+      | debrujin::Expression::Member(member) => member.transform(nested),
+      | debrujin::Expression::Match(pattern_match) =>
+        pattern_match.transform(nested),
+      | debrujin::Expression::Record(record) => record.transform(nested),
+      | debrujin::Expression::FieldAccess(access) => access.transform(nested),
+      | debrujin::Expression::Update(update) => update.transform(nested),
+      | debrujin::Expression::Extension(extension) =>
+        extension.transform(nested),
+      | debrujin::Expression::Restriction(restriction) =>
+        restriction.transform(nested),
+    };
+    context.depth -= 1;
+    value
   }
 }
 
-impl TransformInto<Value> for debrujin::Literal
+impl TransformInto<Result<Value, RuntimeError>> for debrujin::Literal
 {
   type Context<'a> = &'a mut Context;
 
   fn transform(
     &self,
     _context: Self::Context<'_>,
-  ) -> Value
+  ) -> Result<Value, RuntimeError>
   {
-    match self {
+    Ok(match self {
       | debrujin::Literal::String(value) => Value::String(value.clone()),
-      | debrujin::Literal::Numeric(value) => Value::Numeric(value.clone()),
+      | debrujin::Literal::Integer(value) => Value::Integer(*value),
+      | debrujin::Literal::Float(value) => Value::Float(*value),
       | debrujin::Literal::Boolean(value) => Value::Bool(*value),
-    }
+      | debrujin::Literal::Unit => Value::Unit,
+    })
+  }
+}
+
+#[cfg(test)]
+mod display
+{
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn values()
+  {
+    assert_eq!(Value::String("foo`bar".into()).to_string(), "`foo\\`bar`");
+    assert_eq!(Value::Float(2.5).to_string(), "2.5");
+    assert_eq!(Value::Float(2.0).to_string(), "2.0");
+    assert_eq!(Value::Integer(-2).to_string(), "-2");
+    assert_eq!(Value::Bool(false).to_string(), "false");
+    assert_eq!(Value::Unit.to_string(), "()");
+    let some = |argument| Value::Variant {
+      tag: 1,
+      name: "Some".into(),
+      argument: Some(Box::new(argument)),
+    };
+    assert_eq!(some(some(Value::Integer(1))).to_string(), "Some (Some 1)");
+    assert_eq!(
+      some(Value::Tuple(vec![Value::Unit, Value::Unit])).to_string(),
+      "Some ((), ())"
+    );
+    assert_eq!(
+      Value::Tuple(vec![
+        Value::Integer(1),
+        Value::Tuple(vec![Value::String("a".into()), Value::Unit]),
+      ])
+      .to_string(),
+      "(1, (`a`, ()))"
+    );
+    assert_eq!(
+      Value::Record(vec![
+        ("age".into(), Value::Integer(1)),
+        ("name".into(), Value::String("a".into())),
+      ])
+      .to_string(),
+      "{ age = 1; name = `a` }"
+    );
+    assert_eq!(
+      Value::List(List::from_iter([Value::Integer(1), Value::Integer(2)]))
+        .to_string(),
+      "[1, 2]"
+    );
+    assert_eq!(Value::List(List::nil()).to_string(), "[]");
+    assert_eq!(
+      Value::Closure {
+        stack: Rc::new([]),
+        body: Rc::new(debrujin::Literal::Boolean(true).into()),
+      }
+      .to_string(),
+      "<fun>"
+    );
   }
 }
 
@@ -151,15 +502,15 @@ mod literals
   {
     let literal = debrujin::Literal::String("hello".into());
     let mut context = Context::default();
-    assert_eq!(context.evaluate(literal), Value::String("hello".into()));
+    assert_eq!(context.evaluate(literal), Ok(Value::String("hello".into())));
   }
 
   #[test]
   fn number()
   {
-    let literal = debrujin::Literal::Numeric("3.14".into());
+    let literal = debrujin::Literal::Float(2.5);
     let mut context = Context::default();
-    assert_eq!(context.evaluate(literal), Value::Numeric("3.14".into()));
+    assert_eq!(context.evaluate(literal), Ok(Value::Float(2.5)));
   }
 
   #[test]
@@ -167,22 +518,24 @@ mod literals
   {
     let literal = debrujin::Literal::Boolean(true);
     let mut context = Context::default();
-    assert_eq!(context.evaluate(literal), Value::Bool(true));
+    assert_eq!(context.evaluate(literal), Ok(Value::Bool(true)));
   }
 }
 
-impl TransformInto<Value> for debrujin::Identifier
+impl TransformInto<Result<Value, RuntimeError>> for debrujin::Identifier
 {
   type Context<'a> = &'a mut Context;
 
   fn transform(
     &self,
     context: Self::Context<'_>,
-  ) -> Value
+  ) -> Result<Value, RuntimeError>
   {
-    context
-      .lookup(self.name)
-      .unwrap_or_else(|| panic!("unbound identifier: {}", self.name))
+    Ok(
+      context
+        .lookup(self.name)
+        .unwrap_or_else(|| panic!("unbound identifier: {}", self.name)),
+    )
   }
 }
 
@@ -201,8 +554,9 @@ mod identifiers
     };
     let mut context = Context {
       stack: vec![Value::String("hello".into())],
+      ..Context::default()
     };
-    assert_eq!(context.evaluate(identifier), Value::String("hello".into()),);
+    assert_eq!(context.evaluate(identifier), Ok(Value::String("hello".into())));
   }
 
   #[test]
@@ -213,24 +567,24 @@ mod identifiers
       name: 0,
     };
     let mut context = Context::default();
-    assert_eq!(context.evaluate(identifier), Value::String("hello".into()),);
+    assert_eq!(context.evaluate(identifier), Ok(Value::String("hello".into())));
   }
 }
 
-impl TransformInto<Value> for debrujin::Abstraction
+impl TransformInto<Result<Value, RuntimeError>> for debrujin::Abstraction
 {
   type Context<'a> = &'a mut Context;
 
   fn transform(
     &self,
     context: Self::Context<'_>,
-  ) -> Value
+  ) -> Result<Value, RuntimeError>
   {
     // todo: current implementation is not efficient
-    Value::Closure {
+    Ok(Value::Closure {
       stack: context.capture(self.body.largest_free_variable(1)),
-      body: self.body.clone(),
-    }
+      body: Rc::new(self.body.clone()),
+    })
   }
 }
 
@@ -252,10 +606,13 @@ mod abstractions
       body: body.clone(),
     };
     let mut context = Context::default();
-    assert_eq!(context.evaluate(abstraction), Value::Closure {
-      stack: vec![],
-      body,
-    });
+    assert_eq!(
+      context.evaluate(abstraction),
+      Ok(Value::Closure {
+        stack: Rc::new([]),
+        body: Rc::new(body),
+      })
+    );
   }
 
   #[test]
@@ -270,11 +627,15 @@ mod abstractions
     };
     let mut context = Context {
       stack: vec![Value::String("hello".into())],
+      ..Context::default()
     };
-    assert_eq!(context.evaluate(abstraction), Value::Closure {
-      stack: vec![Value::String("hello".into())],
-      body,
-    });
+    assert_eq!(
+      context.evaluate(abstraction),
+      Ok(Value::Closure {
+        stack: Rc::new([Value::String("hello".into())]),
+        body: Rc::new(body),
+      })
+    );
   }
 
   #[test]
@@ -288,34 +649,79 @@ mod abstractions
       body: body.clone(),
     };
     let mut context = Context::default();
-    assert_eq!(context.evaluate(abstraction), Value::Closure {
-      stack: vec![],
-      body,
-    });
+    assert_eq!(
+      context.evaluate(abstraction),
+      Ok(Value::Closure {
+        stack: Rc::new([]),
+        body: Rc::new(body),
+      })
+    );
   }
 }
 
-impl TransformInto<Value> for debrujin::Application
+impl TransformInto<Result<Value, RuntimeError>> for debrujin::Application
 {
   type Context<'a> = &'a mut Context;
 
   fn transform(
     &self,
     context: Self::Context<'_>,
-  ) -> Value
+  ) -> Result<Value, RuntimeError>
   {
     let abstraction = self
       .abstraction
-      .transform(&mut *context);
-    let argument = self.argument.transform(&mut *context);
+      .evaluate(&mut *context)?;
+    if let Value::Primitive {
+      primitive,
+      arguments,
+    } = &abstraction
+    {
+      if let Some(value) = primitives::short_circuit(*primitive, arguments) {
+        return Ok(value)
+      }
+    }
+    let argument = self.argument.evaluate(&mut *context)?;
     match abstraction {
       | Value::Closure {
         stack,
         body,
-      } => context.load(stack.as_slice(), |context| {
+      } => context.load(&stack, |context| {
         context.load(&[argument], |context| body.transform(context))
       }),
-      | _ => panic!("not a closure"),
+      | Value::Recursive {
+        stack,
+        functions,
+        index,
+      } => context.load(&stack, |context| {
+        let group = Value::recursive(&stack, &functions);
+        context.load(&group, |context| {
+          context.load(&[argument], |context| {
+            functions[index].body.transform(context)
+          })
+        })
+      }),
+      | Value::Primitive {
+        primitive,
+        mut arguments,
+      } => {
+        arguments.push(argument);
+        match arguments.len() == primitive.arity() {
+          | true => primitives::apply(primitive, &arguments),
+          | false => Ok(Value::Primitive {
+            primitive,
+            arguments,
+          }),
+        }
+      },
+      | Value::Constructor {
+        tag,
+        name,
+      } => Ok(Value::Variant {
+        tag,
+        name,
+        argument: Some(Box::new(argument)),
+      }),
+      | value => Err(RuntimeError::NotAFunction(value)),
     }
   }
 }
@@ -339,9 +745,13 @@ mod application
       }
       .into(),
       argument: debrujin::Literal::String("hello".into()).into(),
+      span: None,
     };
     let mut context = Context::default();
-    assert_eq!(context.evaluate(abstraction), Value::String("hello".into()));
+    assert_eq!(
+      context.evaluate(abstraction),
+      Ok(Value::String("hello".into()))
+    );
   }
 
   #[test]
@@ -356,11 +766,53 @@ mod application
       }
       .into(),
       argument: debrujin::Literal::String("hello".into()).into(),
+      span: None,
     };
     let mut context = Context {
       stack: vec![Value::String("foo".into())],
+      ..Context::default()
+    };
+    assert_eq!(context.evaluate(abstraction), Ok(Value::String("foo".into())));
+  }
+
+  #[test]
+  fn not_a_function()
+  {
+    let application = debrujin::Application {
+      abstraction: debrujin::Literal::Boolean(true).into(),
+      argument: debrujin::Literal::String("hello".into()).into(),
+      span: None,
+    };
+    let mut context = Context::default();
+    assert_eq!(
+      context.evaluate(application),
+      Err(RuntimeError::NotAFunction(Value::Bool(true)))
+    );
+  }
+
+  #[test]
+  fn primitives_wait_for_all_arguments()
+  {
+    // `-` is the second primitive, so the twenty-second from the top
+    let partial = debrujin::Application {
+      abstraction: debrujin::Identifier::new(21).into(),
+      argument: debrujin::Literal::Integer(10).into(),
+      span: None,
+    };
+    let mut context = Context::with_primitives();
+    assert_eq!(
+      context.evaluate(partial.clone()),
+      Ok(Value::Primitive {
+        primitive: debrujin::Primitive::Subtract,
+        arguments: vec![Value::Integer(10)],
+      })
+    );
+    let complete = debrujin::Application {
+      abstraction: partial.into(),
+      argument: debrujin::Literal::Integer(3).into(),
+      span: None,
     };
-    assert_eq!(context.evaluate(abstraction), Value::String("foo".into()));
+    assert_eq!(context.evaluate(complete), Ok(Value::Integer(7)));
   }
 
   #[test]
@@ -376,8 +828,1097 @@ mod application
       }
       .into(),
       argument: debrujin::Literal::String("hello".into()).into(),
+      span: None,
+    };
+    let mut context = Context::default();
+    assert_eq!(context.evaluate(abstraction), Ok(Value::String("foo".into())));
+  }
+}
+
+impl TransformInto<Result<Value, RuntimeError>> for debrujin::Conditional
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<Value, RuntimeError>
+  {
+    match self.condition.evaluate(&mut *context)? {
+      | Value::Bool(true) => self.consequence.evaluate(context),
+      | Value::Bool(false) => self.alternative.evaluate(context),
+      | value => Err(RuntimeError::NotABoolean(value)),
+    }
+  }
+}
+
+#[cfg(test)]
+mod conditional
+{
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  fn conditional(condition: debrujin::Expression) -> debrujin::Conditional
+  {
+    debrujin::Conditional {
+      condition,
+      consequence: debrujin::Literal::String("yes".into()).into(),
+      // evaluating this would panic, the branch must not be taken
+      alternative: debrujin::Identifier::new(0).into(),
+    }
+  }
+
+  #[test]
+  fn only_the_selected_branch_is_evaluated()
+  {
+    let mut context = Context::default();
+    assert_eq!(
+      context.evaluate(conditional(debrujin::Literal::Boolean(true).into())),
+      Ok(Value::String("yes".into()))
+    );
+  }
+
+  #[test]
+  #[should_panic = "unbound identifier: 0"]
+  fn false_selects_the_alternative()
+  {
+    let mut context = Context::default();
+    let _ =
+      context.evaluate(conditional(debrujin::Literal::Boolean(false).into()));
+  }
+
+  #[test]
+  fn condition_must_be_a_boolean()
+  {
+    let mut context = Context::default();
+    assert_eq!(
+      context.evaluate(conditional(debrujin::Literal::Integer(1).into())),
+      Err(RuntimeError::NotABoolean(Value::Integer(1)))
+    );
+  }
+}
+
+impl TransformInto<Result<Value, RuntimeError>> for debrujin::Let
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<Value, RuntimeError>
+  {
+    let values = self
+      .values
+      .iter()
+      .map(|value| value.evaluate(&mut *context))
+      .collect::<Result<Vec<_>, _>>()?;
+    context.load(&values, |context| self.body.evaluate(context))
+  }
+}
+
+#[cfg(test)]
+mod let_in
+{
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn last_value_is_innermost()
+  {
+    let let_in = debrujin::Let {
+      values: vec![
+        debrujin::Literal::Boolean(true).into(),
+        debrujin::Literal::String("hello".into()).into(),
+      ],
+      body: debrujin::Identifier::new(1).into(),
     };
     let mut context = Context::default();
-    assert_eq!(context.evaluate(abstraction), Value::String("foo".into()));
+    assert_eq!(context.evaluate(let_in), Ok(Value::Bool(true)));
+  }
+
+  #[test]
+  fn values_are_dropped_afterwards()
+  {
+    let let_in = debrujin::Let {
+      values: vec![debrujin::Literal::Boolean(true).into()],
+      body: debrujin::Identifier::new(1).into(),
+    };
+    let mut context = Context {
+      stack: vec![Value::String("foo".into())],
+      ..Context::default()
+    };
+    assert_eq!(context.evaluate(let_in), Ok(Value::String("foo".into())));
+    assert_eq!(context.stack, vec![Value::String("foo".into())]);
+  }
+}
+
+impl TransformInto<Result<Value, RuntimeError>> for debrujin::Tuple
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<Value, RuntimeError>
+  {
+    Ok(Value::Tuple(
+      self
+        .elements
+        .iter()
+        .map(|element| element.evaluate(&mut *context))
+        .collect::<Result<_, _>>()?,
+    ))
+  }
+}
+
+impl TransformInto<Result<Value, RuntimeError>> for debrujin::Projection
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<Value, RuntimeError>
+  {
+    match self.tuple.evaluate(&mut *context)? {
+      | Value::Tuple(mut elements) if self.index < elements.len() =>
+        Ok(elements.swap_remove(self.index)),
+      | tuple @ Value::Tuple(_) =>
+        Err(RuntimeError::NoElement(tuple, self.index)),
+      | value => Err(RuntimeError::NotATuple(value)),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tuple
+{
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  fn pair() -> debrujin::Expression
+  {
+    debrujin::Tuple {
+      elements: vec![
+        debrujin::Identifier::new(0).into(),
+        debrujin::Literal::Integer(2).into(),
+      ],
+    }
+    .into()
+  }
+
+  #[test]
+  fn elements_are_evaluated_in_order()
+  {
+    let mut context = Context {
+      stack: vec![Value::Unit],
+      ..Context::default()
+    };
+    assert_eq!(
+      context.evaluate(pair()),
+      Ok(Value::Tuple(vec![Value::Unit, Value::Integer(2)]))
+    );
+  }
+
+  #[test]
+  fn projection_selects_an_element()
+  {
+    let projection = debrujin::Projection {
+      tuple: pair(),
+      index: 1,
+    };
+    let mut context = Context {
+      stack: vec![Value::Unit],
+      ..Context::default()
+    };
+    assert_eq!(context.evaluate(projection), Ok(Value::Integer(2)));
+  }
+
+  #[test]
+  fn projection_out_of_the_tuple()
+  {
+    let projection = debrujin::Projection {
+      tuple: pair(),
+      index: 2,
+    };
+    let mut context = Context {
+      stack: vec![Value::Unit],
+      ..Context::default()
+    };
+    assert_eq!(
+      context.evaluate(projection),
+      Err(RuntimeError::NoElement(
+        Value::Tuple(vec![Value::Unit, Value::Integer(2)]),
+        2
+      ))
+    );
+  }
+}
+
+impl TransformInto<Result<Value, RuntimeError>> for debrujin::List
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<Value, RuntimeError>
+  {
+    Ok(Value::List(
+      self
+        .elements
+        .iter()
+        .map(|element| element.evaluate(&mut *context))
+        .collect::<Result<_, _>>()?,
+    ))
+  }
+}
+
+#[cfg(test)]
+mod lists
+{
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn elements_are_evaluated_in_order()
+  {
+    let list = debrujin::List {
+      elements: vec![
+        debrujin::Identifier::new(0).into(),
+        debrujin::Literal::Integer(2).into(),
+      ],
+    };
+    let mut context = Context {
+      stack: vec![Value::Unit],
+      ..Context::default()
+    };
+    assert_eq!(
+      context.evaluate(list),
+      Ok(Value::List(List::from_iter([Value::Unit, Value::Integer(2)])))
+    );
+  }
+
+  #[test]
+  fn cons_prepends_to_a_list()
+  {
+    // `::` is the last primitive to be bound
+    let application = debrujin::Application {
+      abstraction: debrujin::Application {
+        abstraction: debrujin::Identifier::new(0).into(),
+        argument: debrujin::Literal::Integer(1).into(),
+        span: None,
+      }
+      .into(),
+      argument: debrujin::List {
+        elements: vec![debrujin::Literal::Integer(2).into()],
+      }
+      .into(),
+      span: None,
+    };
+    let mut context = Context::with_primitives();
+    assert_eq!(
+      context.evaluate(application),
+      Ok(Value::List(List::from_iter([Value::Integer(1), Value::Integer(2)])))
+    );
+  }
+}
+
+/// Evaluates `fields` in order, the values are sorted by name afterwards.
+fn fields(
+  fields: &[(String, debrujin::Expression)],
+  context: &mut Context,
+) -> Result<Vec<(String, Value)>, RuntimeError>
+{
+  let mut values = fields
+    .iter()
+    .map(|(name, value)| Ok((name.clone(), value.evaluate(&mut *context)?)))
+    .collect::<Result<Vec<_>, _>>()?;
+  values.sort_by(|(left, _), (right, _)| left.cmp(right));
+  Ok(values)
+}
+
+impl TransformInto<Result<Value, RuntimeError>> for debrujin::Record
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<Value, RuntimeError>
+  {
+    Ok(Value::Record(fields(&self.fields, context)?))
+  }
+}
+
+impl TransformInto<Result<Value, RuntimeError>> for debrujin::FieldAccess
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<Value, RuntimeError>
+  {
+    match self.record.evaluate(&mut *context)? {
+      | Value::Record(mut fields) => {
+        let index = fields
+          .iter()
+          .position(|(name, _)| name == &self.field);
+        match index {
+          | Some(index) => Ok(fields.swap_remove(index).1),
+          | None => Err(RuntimeError::NoField(
+            Value::Record(fields),
+            self.field.clone(),
+          )),
+        }
+      },
+      | value => Err(RuntimeError::NotARecord(value)),
+    }
+  }
+}
+
+impl TransformInto<Result<Value, RuntimeError>> for debrujin::Update
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<Value, RuntimeError>
+  {
+    let mut record = match self.record.evaluate(&mut *context)? {
+      | Value::Record(fields) => fields,
+      | value => return Err(RuntimeError::NotARecord(value)),
+    };
+    for (name, value) in fields(&self.fields, context)? {
+      match record
+        .iter_mut()
+        .find(|(field, _)| field == &name)
+      {
+        | Some((_, field)) => *field = value,
+        | None =>
+          return Err(RuntimeError::NoField(Value::Record(record), name)),
+      }
+    }
+    Ok(Value::Record(record))
+  }
+}
+
+impl TransformInto<Result<Value, RuntimeError>> for debrujin::Extension
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<Value, RuntimeError>
+  {
+    let mut fields = fields(&self.fields, context)?;
+    match self.record.evaluate(&mut *context)? {
+      | Value::Record(record) => fields.extend(record),
+      | value => return Err(RuntimeError::NotARecord(value)),
+    }
+    fields.sort_by(|(left, _), (right, _)| left.cmp(right));
+    Ok(Value::Record(fields))
+  }
+}
+
+impl TransformInto<Result<Value, RuntimeError>> for debrujin::Restriction
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<Value, RuntimeError>
+  {
+    let mut record = match self.record.evaluate(&mut *context)? {
+      | Value::Record(record) => record,
+      | value => return Err(RuntimeError::NotARecord(value)),
+    };
+    for field in self.fields.iter() {
+      match record
+        .iter()
+        .position(|(name, _)| name == field)
+      {
+        | Some(index) => {
+          record.remove(index);
+        },
+        | None =>
+          return Err(RuntimeError::NoField(
+            Value::Record(record),
+            field.clone(),
+          )),
+      }
+    }
+    Ok(Value::Record(record))
+  }
+}
+
+#[cfg(test)]
+mod record
+{
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  fn person() -> debrujin::Expression
+  {
+    debrujin::Record {
+      fields: vec![
+        ("name".into(), debrujin::Identifier::new(0).into()),
+        ("age".into(), debrujin::Literal::Integer(2).into()),
+      ],
+    }
+    .into()
+  }
+
+  #[test]
+  fn fields_are_sorted_by_name()
+  {
+    let mut context = Context {
+      stack: vec![Value::Unit],
+      ..Context::default()
+    };
+    assert_eq!(
+      context.evaluate(person()),
+      Ok(Value::Record(vec![
+        ("age".into(), Value::Integer(2)),
+        ("name".into(), Value::Unit),
+      ]))
+    );
+  }
+
+  #[test]
+  fn field_access_selects_a_field()
+  {
+    let access = debrujin::FieldAccess {
+      record: person(),
+      field: "name".into(),
+    };
+    let mut context = Context {
+      stack: vec![Value::Unit],
+      ..Context::default()
+    };
+    assert_eq!(context.evaluate(access), Ok(Value::Unit));
+  }
+
+  #[test]
+  fn update_replaces_fields()
+  {
+    let update = debrujin::Update {
+      record: person(),
+      fields: vec![("age".into(), debrujin::Literal::Integer(3).into())],
+    };
+    let mut context = Context {
+      stack: vec![Value::Unit],
+      ..Context::default()
+    };
+    assert_eq!(
+      context.evaluate(update),
+      Ok(Value::Record(vec![
+        ("age".into(), Value::Integer(3)),
+        ("name".into(), Value::Unit),
+      ]))
+    );
+  }
+
+  #[test]
+  fn extension_adds_fields_and_restriction_removes_them()
+  {
+    let restriction = debrujin::Restriction {
+      record: debrujin::Extension {
+        fields: vec![("id".into(), debrujin::Literal::Integer(7).into())],
+        record: person(),
+      }
+      .into(),
+      fields: vec!["name".into()],
+    };
+    let mut context = Context {
+      stack: vec![Value::Unit],
+      ..Context::default()
+    };
+    assert_eq!(
+      context.evaluate(restriction),
+      Ok(Value::Record(vec![
+        ("age".into(), Value::Integer(2)),
+        ("id".into(), Value::Integer(7)),
+      ]))
+    );
+  }
+
+  #[test]
+  fn missing_fields()
+  {
+    let access = debrujin::FieldAccess {
+      record: debrujin::Record {
+        fields: vec![],
+      }
+      .into(),
+      field: "name".into(),
+    };
+    let mut context = Context::default();
+    assert_eq!(
+      context.evaluate(access),
+      Err(RuntimeError::NoField(Value::Record(vec![]), "name".into()))
+    );
+  }
+}
+
+impl TransformInto<Result<Value, RuntimeError>> for debrujin::Match
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<Value, RuntimeError>
+  {
+    let scrutinee = self.scrutinee.evaluate(&mut *context)?;
+    let Some(arm) = select(&self.tree, &scrutinee)
+    else {
+      return Err(RuntimeError::MatchFailure(scrutinee))
+    };
+    let arm = &self.arms[arm];
+    let values = arm
+      .bindings
+      .iter()
+      .map(|path| part(&scrutinee, path).clone())
+      .collect::<Vec<_>>();
+    context.load(&values, |context| arm.body.evaluate(context))
+  }
+}
+
+/// The arm that `tree` selects for `value`.
+fn select(
+  tree: &debrujin::DecisionTree,
+  value: &Value,
+) -> Option<usize>
+{
+  match tree {
+    | debrujin::DecisionTree::Fail => None,
+    | debrujin::DecisionTree::Leaf(arm) => Some(*arm),
+    | debrujin::DecisionTree::Switch {
+      path,
+      cases,
+      default,
+    } => {
+      let part = part(value, path);
+      cases
+        .iter()
+        .find(|(case, _)| agrees(part, case))
+        .map(|(_, tree)| tree)
+        .or(default.as_deref())
+        .and_then(|tree| select(tree, value))
+    },
+  }
+}
+
+fn part<'a>(
+  value: &'a Value,
+  path: &[usize],
+) -> &'a Value
+{
+  path
+    .iter()
+    .fold(value, |value, index| match value {
+      | Value::Tuple(elements) => &elements[*index],
+      | Value::Variant {
+        argument: Some(argument),
+        ..
+      } => argument,
+      | Value::List(list) => match (list.split(), index) {
+        | (Some((head, _)), 0) => head,
+        | (Some((_, tail)), 1) => tail,
+        | _ => panic!("`{}` has no part {}", value, index),
+      },
+      | value => panic!("`{}` has no part {}", value, index),
+    })
+}
+
+fn agrees(
+  value: &Value,
+  case: &debrujin::Case,
+) -> bool
+{
+  match (value, case) {
+    | (
+      Value::Variant {
+        tag,
+        ..
+      },
+      debrujin::Case::Constructor(expected),
+    ) => tag == expected,
+    | (
+      Value::String(value),
+      debrujin::Case::Literal(debrujin::Literal::String(literal)),
+    ) => value == literal,
+    | (
+      Value::Integer(value),
+      debrujin::Case::Literal(debrujin::Literal::Integer(literal)),
+    ) => value == literal,
+    | (
+      Value::Float(value),
+      debrujin::Case::Literal(debrujin::Literal::Float(literal)),
+    ) => value == literal,
+    | (
+      Value::Bool(value),
+      debrujin::Case::Literal(debrujin::Literal::Boolean(literal)),
+    ) => value == literal,
+    | (Value::Unit, debrujin::Case::Literal(debrujin::Literal::Unit)) => true,
+    | (Value::List(list), debrujin::Case::Nil) => list.is_empty(),
+    | (Value::List(list), debrujin::Case::Cons) => !list.is_empty(),
+    | _ => false,
+  }
+}
+
+#[cfg(test)]
+mod pattern_match
+{
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  fn some(argument: Value) -> Value
+  {
+    Value::Variant {
+      tag: 1,
+      name: "Some".into(),
+      argument: Some(Box::new(argument)),
+    }
+  }
+
+  /// `match x with | Some (y, 0) -> y | _ -> x`
+  fn pattern_match() -> debrujin::Match
+  {
+    let patterns = [
+      debrujin::Pattern::Constructor {
+        tag: 1,
+        constructors: 2,
+        argument: Some(Box::new(debrujin::Pattern::Tuple(vec![
+          debrujin::Pattern::Binding,
+          debrujin::Pattern::Literal(debrujin::Literal::Integer(0)),
+        ]))),
+      },
+      debrujin::Pattern::Wildcard,
+    ];
+    debrujin::Match {
+      scrutinee: debrujin::Identifier::new(0).into(),
+      tree: debrujin::DecisionTree::compile(&patterns),
+      arms: vec![
+        debrujin::Arm {
+          bindings: patterns[0].bindings(),
+          body: debrujin::Identifier::new(0).into(),
+        },
+        debrujin::Arm {
+          bindings: patterns[1].bindings(),
+          body: debrujin::Identifier::new(0).into(),
+        },
+      ],
+    }
+  }
+
+  #[test]
+  fn the_first_matching_arm_is_taken()
+  {
+    let mut context = Context {
+      stack: vec![some(Value::Tuple(vec![Value::Unit, Value::Integer(0)]))],
+      ..Context::default()
+    };
+    assert_eq!(context.evaluate(pattern_match()), Ok(Value::Unit));
+    assert_eq!(context.stack.len(), 1);
+
+    let value = some(Value::Tuple(vec![Value::Unit, Value::Integer(1)]));
+    let mut context = Context {
+      stack: vec![value.clone()],
+      ..Context::default()
+    };
+    assert_eq!(context.evaluate(pattern_match()), Ok(value));
+  }
+
+  #[test]
+  fn no_matching_arm_is_a_failure()
+  {
+    let pattern_match = debrujin::Match {
+      scrutinee: debrujin::Literal::Integer(2).into(),
+      tree: debrujin::DecisionTree::compile(&[debrujin::Pattern::Literal(
+        debrujin::Literal::Integer(1),
+      )]),
+      arms: vec![debrujin::Arm {
+        bindings: vec![],
+        body: debrujin::Literal::Unit.into(),
+      }],
+    };
+    let mut context = Context::default();
+    assert_eq!(
+      context.evaluate(pattern_match),
+      Err(RuntimeError::MatchFailure(Value::Integer(2)))
+    );
+  }
+}
+
+impl TransformInto<Result<Value, RuntimeError>> for debrujin::LetRec
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<Value, RuntimeError>
+  {
+    let functions = context.recursive(&self.functions);
+    context.load(&functions, |context| self.body.evaluate(context))
+  }
+}
+
+#[cfg(test)]
+mod let_rec
+{
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn functions_can_call_each_other()
+  {
+    // let rec f x = g x and g y = y in f `hello`
+    let let_rec = debrujin::LetRec {
+      functions: vec![
+        debrujin::Abstraction {
+          body: debrujin::Application {
+            abstraction: debrujin::Identifier::new(1).into(),
+            argument: debrujin::Identifier::new(0).into(),
+            span: None,
+          }
+          .into(),
+        },
+        debrujin::Abstraction {
+          body: debrujin::Identifier::new(0).into(),
+        },
+      ],
+      body: debrujin::Application {
+        abstraction: debrujin::Identifier::new(1).into(),
+        argument: debrujin::Literal::String("hello".into()).into(),
+        span: None,
+      }
+      .into(),
+    };
+    let mut context = Context::default();
+    assert_eq!(context.evaluate(let_rec), Ok(Value::String("hello".into())));
+    assert!(context.stack.is_empty());
+  }
+
+  #[test]
+  fn functions_capture_the_stack()
+  {
+    let let_rec = debrujin::LetRec {
+      functions: vec![debrujin::Abstraction {
+        body: debrujin::Identifier::new(2).into(),
+      }],
+      body: debrujin::Identifier::new(0).into(),
+    };
+    let mut context = Context {
+      stack: vec![Value::Bool(true), Value::String("foo".into())],
+      ..Context::default()
+    };
+    assert_eq!(
+      context.evaluate(let_rec),
+      Ok(Value::Recursive {
+        stack: Rc::new([Value::String("foo".into())]),
+        functions: Rc::new([debrujin::Abstraction {
+          body: debrujin::Identifier::new(2).into(),
+        }]),
+        index: 0,
+      })
+    );
+  }
+}
+
+impl TransformInto<Result<Vec<Value>, RuntimeError>> for debrujin::Def
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<Vec<Value>, RuntimeError>
+  {
+    let functions = context.recursive(&self.functions);
+    for function in functions.iter() {
+      context.bind(function.clone());
+    }
+    Ok(functions)
+  }
+}
+
+impl TransformInto<Result<Value, RuntimeError>> for debrujin::Val
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<Value, RuntimeError>
+  {
+    let value = self.value.evaluate(&mut *context)?;
+    context.bind(value.clone());
+    Ok(value)
+  }
+}
+
+impl TransformInto<Result<Vec<Value>, RuntimeError>> for debrujin::Data
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<Vec<Value>, RuntimeError>
+  {
+    let constructors = self
+      .constructors
+      .iter()
+      .map(|constructor| {
+        let tag = constructor.tag;
+        let name = constructor.name.clone();
+        match constructor.takes_argument {
+          | true => Value::Constructor {
+            tag,
+            name,
+          },
+          | false => Value::Variant {
+            tag,
+            name,
+            argument: None,
+          },
+        }
+      })
+      .collect::<Vec<_>>();
+    for constructor in constructors.iter() {
+      context.bind(constructor.clone());
+    }
+    Ok(constructors)
+  }
+}
+
+impl TransformInto<Result<Value, RuntimeError>> for debrujin::Member
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<Value, RuntimeError>
+  {
+    match self.structure.evaluate(&mut *context)? {
+      | Value::Structure(members) => members
+        .get(self.index)
+        .cloned()
+        .ok_or_else(|| {
+          RuntimeError::NoElement(Value::Structure(members.clone()), self.index)
+        }),
+      | value => Err(RuntimeError::NotAStructure(value)),
+    }
+  }
+}
+
+/// Binds the structure of every value its declarations bind, and returns the
+/// values of their names.
+impl TransformInto<Result<Vec<Value>, RuntimeError>> for debrujin::Structure
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<Vec<Value>, RuntimeError>
+  {
+    let depth = context.stack.len();
+    let values = self
+      .declarations
+      .iter()
+      .map(|declaration| declaration.evaluate(&mut *context))
+      .collect::<Result<Vec<Vec<Value>>, _>>();
+    let members = context.stack.split_off(depth);
+    let values = values?;
+    context.bind(Value::Structure(members.into()));
+    Ok(values.concat())
+  }
+}
+
+/// Binds the members of the structure, which are not the values of any name
+/// of the declaration.
+impl TransformInto<Result<Vec<Value>, RuntimeError>> for debrujin::Open
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<Vec<Value>, RuntimeError>
+  {
+    match self.structure.evaluate(&mut *context)? {
+      | Value::Structure(members) => {
+        for member in members.iter() {
+          context.bind(member.clone());
+        }
+        Ok(vec![])
+      },
+      | value => Err(RuntimeError::NotAStructure(value)),
+    }
+  }
+}
+
+/// The values of every name the declaration binds, in order.
+impl TransformInto<Result<Vec<Value>, RuntimeError>> for debrujin::TopLevel
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<Vec<Value>, RuntimeError>
+  {
+    match self {
+      | debrujin::TopLevel::Val(val) => Ok(vec![val.evaluate(context)?]),
+      | debrujin::TopLevel::Def(def) => def.evaluate(context),
+      | debrujin::TopLevel::Data(data) => data.evaluate(context),
+      | debrujin::TopLevel::Structure(structure) => structure.evaluate(context),
+      | debrujin::TopLevel::Open(open) => open.evaluate(context),
+    }
+  }
+}
+
+#[cfg(test)]
+mod declarations
+{
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn declarations_extend_the_stack()
+  {
+    let mut context = Context::default();
+    let first: debrujin::TopLevel = debrujin::Val {
+      value: debrujin::Literal::Boolean(true).into(),
+    }
+    .into();
+    let second: debrujin::TopLevel = debrujin::Val {
+      value: debrujin::Identifier::new(0).into(),
+    }
+    .into();
+    assert_eq!(first.evaluate(&mut context), Ok(vec![Value::Bool(true)]));
+    assert_eq!(second.evaluate(&mut context), Ok(vec![Value::Bool(true)]));
+    assert_eq!(context.lookup(1), Some(Value::Bool(true)));
+  }
+
+  #[test]
+  fn structures_bind_their_members_together()
+  {
+    let mut context = Context::default();
+    let structure: debrujin::TopLevel = debrujin::Structure {
+      declarations: vec![
+        debrujin::Val {
+          value: debrujin::Literal::Boolean(true).into(),
+        }
+        .into(),
+        debrujin::Val {
+          value: debrujin::Literal::Integer(1).into(),
+        }
+        .into(),
+      ],
+    }
+    .into();
+    assert_eq!(
+      structure.evaluate(&mut context),
+      Ok(vec![Value::Bool(true), Value::Integer(1)])
+    );
+    assert_eq!(
+      context.evaluate(debrujin::Member {
+        structure: debrujin::Identifier::new(0).into(),
+        index: 1,
+      }),
+      Ok(Value::Integer(1))
+    );
+    let open: debrujin::TopLevel = debrujin::Open {
+      structure: debrujin::Identifier::new(0).into(),
+    }
+    .into();
+    assert_eq!(open.evaluate(&mut context), Ok(vec![]));
+    assert_eq!(context.lookup(0), Some(Value::Integer(1)));
+    assert_eq!(context.lookup(1), Some(Value::Bool(true)));
+  }
+
+  #[test]
+  fn constructors_build_tagged_values()
+  {
+    let mut context = Context::default();
+    let option: debrujin::TopLevel = debrujin::Data {
+      constructors: vec![
+        debrujin::Constructor {
+          tag: 0,
+          name: "None".into(),
+          takes_argument: false,
+        },
+        debrujin::Constructor {
+          tag: 1,
+          name: "Some".into(),
+          takes_argument: true,
+        },
+      ],
+    }
+    .into();
+    let none = Value::Variant {
+      tag: 0,
+      name: "None".into(),
+      argument: None,
+    };
+    assert_eq!(
+      option.evaluate(&mut context),
+      Ok(vec![none.clone(), Value::Constructor {
+        tag: 1,
+        name: "Some".into(),
+      }])
+    );
+    let some_none = debrujin::Application {
+      abstraction: debrujin::Identifier::new(0).into(),
+      argument: debrujin::Identifier::new(1).into(),
+      span: None,
+    };
+    assert_eq!(
+      context.evaluate(some_none),
+      Ok(Value::Variant {
+        tag: 1,
+        name: "Some".into(),
+        argument: Some(Box::new(none)),
+      })
+    );
+  }
+
+  #[test]
+  fn def_binds_every_function()
+  {
+    let mut context = Context::default();
+    let def: debrujin::TopLevel = debrujin::Def {
+      functions: vec![
+        debrujin::Abstraction {
+          body: debrujin::Identifier::new(0).into(),
+        },
+        debrujin::Abstraction {
+          body: debrujin::Identifier::new(2).into(),
+        },
+      ],
+    }
+    .into();
+    let values: Vec<Value> = def.evaluate(&mut context).unwrap();
+    assert_eq!(values.len(), 2);
+    assert_eq!(context.lookup(0), Some(values[1].clone()));
+    assert_eq!(context.lookup(1), Some(values[0].clone()));
   }
 }
@@ -0,0 +1,162 @@
+use std::rc::Rc;
+
+use super::Value;
+
+/// An immutable list of values. Consing onto a list shares it as the tail of
+/// the new cell rather than copying it, so lists built from the same tail
+/// share their cells.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct List
+{
+  first: Option<Rc<Cell>>,
+}
+
+#[derive(Debug, PartialEq)]
+struct Cell
+{
+  head: Value,
+  /// Always a `Value::List`, so that matching can refer to it as a value.
+  tail: Value,
+}
+
+impl List
+{
+  pub fn nil() -> Self
+  {
+    Self::default()
+  }
+
+  pub fn cons(
+    head: Value,
+    tail: List,
+  ) -> Self
+  {
+    Self {
+      first: Some(Rc::new(Cell {
+        head,
+        tail: Value::List(tail),
+      })),
+    }
+  }
+
+  pub fn is_empty(&self) -> bool
+  {
+    self.first.is_none()
+  }
+
+  /// The first element and the list of the others, unless the list is empty.
+  pub fn split(&self) -> Option<(&Value, &Value)>
+  {
+    self
+      .first
+      .as_deref()
+      .map(|cell| (&cell.head, &cell.tail))
+  }
+
+  pub fn iter(&self) -> Iter<'_>
+  {
+    Iter {
+      list: self,
+    }
+  }
+}
+
+impl FromIterator<Value> for List
+{
+  fn from_iter<Values: IntoIterator<Item = Value>>(values: Values) -> Self
+  {
+    let values = values.into_iter().collect::<Vec<_>>();
+    values
+      .into_iter()
+      .rev()
+      .fold(List::nil(), |tail, head| List::cons(head, tail))
+  }
+}
+
+/// Frees the cells one after the other, dropping them recursively would
+/// overflow the stack on long lists.
+impl Drop for List
+{
+  fn drop(&mut self)
+  {
+    let mut next = self.first.take();
+    while let Some(cell) = next {
+      next = match Rc::try_unwrap(cell) {
+        | Ok(Cell {
+          tail: Value::List(mut tail),
+          ..
+        }) => tail.first.take(),
+        // the rest of the list is still shared by another one
+        | _ => None,
+      };
+    }
+  }
+}
+
+pub struct Iter<'a>
+{
+  list: &'a List,
+}
+
+impl<'a> Iterator for Iter<'a>
+{
+  type Item = &'a Value;
+
+  fn next(&mut self) -> Option<Self::Item>
+  {
+    let (head, tail) = self.list.split()?;
+    if let Value::List(tail) = tail {
+      self.list = tail;
+    }
+    Some(head)
+  }
+}
+
+#[cfg(test)]
+mod spec
+{
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  fn integers(values: impl IntoIterator<Item = i64>) -> List
+  {
+    values
+      .into_iter()
+      .map(Value::Integer)
+      .collect()
+  }
+
+  #[test]
+  fn lists_are_built_from_the_last_element()
+  {
+    let list = integers([1, 2, 3]);
+    assert_eq!(list.iter().cloned().collect::<Vec<_>>(), vec![
+      Value::Integer(1),
+      Value::Integer(2),
+      Value::Integer(3)
+    ]);
+    assert_eq!(list, List::cons(Value::Integer(1), integers([2, 3])));
+  }
+
+  #[test]
+  fn consing_shares_the_tail()
+  {
+    let tail = integers([2, 3]);
+    let list = List::cons(Value::Integer(1), tail.clone());
+    let Some((_, Value::List(shared))) = list.split()
+    else {
+      panic!("expected a list that is not empty")
+    };
+    assert!(Rc::ptr_eq(
+      shared.first.as_ref().unwrap(),
+      tail.first.as_ref().unwrap()
+    ));
+  }
+
+  #[test]
+  fn long_lists_are_dropped_without_recursion()
+  {
+    drop(integers(0 .. 1_000_000));
+  }
+}
@@ -0,0 +1,480 @@
+use std::cmp::Ordering;
+
+use super::{
+  List,
+  RuntimeError,
+  Value,
+};
+use crate::syntax::debrujin::Primitive;
+
+/// Computes `primitive` once all of its `arguments` are known.
+pub fn apply(
+  primitive: Primitive,
+  arguments: &[Value],
+) -> Result<Value, RuntimeError>
+{
+  match (primitive, arguments) {
+    | (
+      Primitive::Add
+      | Primitive::Subtract
+      | Primitive::Multiply
+      | Primitive::Divide
+      | Primitive::Modulo,
+      [Value::Integer(left), Value::Integer(right)],
+    ) => integer_arithmetic(primitive, *left, *right).map(Value::Integer),
+    | (
+      Primitive::AddFloat
+      | Primitive::SubtractFloat
+      | Primitive::MultiplyFloat
+      | Primitive::DivideFloat,
+      [Value::Float(left), Value::Float(right)],
+    ) => float_arithmetic(primitive, *left, *right).map(Value::Float),
+    | (Primitive::Negate, [Value::Integer(value)]) => value
+      .checked_neg()
+      .map(Value::Integer)
+      .ok_or(RuntimeError::Overflow),
+    | (Primitive::NegateFloat, [Value::Float(value)]) =>
+      Ok(Value::Float(-value)),
+    | (Primitive::Float, [Value::Integer(value)]) =>
+      Ok(Value::Float(*value as f64)),
+    | (Primitive::Truncate, [Value::Float(value)]) => truncate(*value),
+    | (Primitive::Equal, [left, right]) =>
+      Ok(Value::Bool(matches!(compare(left, right)?, Some(Ordering::Equal)))),
+    | (Primitive::Less, [left, right]) =>
+      Ok(Value::Bool(matches!(compare(left, right)?, Some(Ordering::Less)))),
+    | (Primitive::LessOrEqual, [left, right]) => Ok(Value::Bool(matches!(
+      compare(left, right)?,
+      Some(Ordering::Less | Ordering::Equal)
+    ))),
+    | (Primitive::Greater, [left, right]) =>
+      Ok(Value::Bool(matches!(compare(left, right)?, Some(Ordering::Greater)))),
+    | (Primitive::GreaterOrEqual, [left, right]) => Ok(Value::Bool(matches!(
+      compare(left, right)?,
+      Some(Ordering::Greater | Ordering::Equal)
+    ))),
+    | (Primitive::Concatenate, [Value::String(left), Value::String(right)]) =>
+      Ok(Value::String(format!("{}{}", left, right))),
+    | (Primitive::Not, [Value::Bool(value)]) => Ok(Value::Bool(!value)),
+    | (Primitive::And, [Value::Bool(left), Value::Bool(right)]) =>
+      Ok(Value::Bool(*left && *right)),
+    | (Primitive::Or, [Value::Bool(left), Value::Bool(right)]) =>
+      Ok(Value::Bool(*left || *right)),
+    | (Primitive::Cons, [head, Value::List(tail)]) =>
+      Ok(Value::List(List::cons(head.clone(), tail.clone()))),
+    | _ => Err(RuntimeError::Internal(format!(
+      "ill-typed application of `{}`",
+      primitive.name()
+    ))),
+  }
+}
+
+/// The result of `primitive` once the `arguments` it got so far decide it,
+/// `&&` and `||` leave their second operand unevaluated if the first is
+/// `false` or `true`.
+pub fn short_circuit(
+  primitive: Primitive,
+  arguments: &[Value],
+) -> Option<Value>
+{
+  match (primitive, arguments) {
+    | (Primitive::And, [Value::Bool(false)]) => Some(Value::Bool(false)),
+    | (Primitive::Or, [Value::Bool(true)]) => Some(Value::Bool(true)),
+    | _ => None,
+  }
+}
+
+fn integer_arithmetic(
+  primitive: Primitive,
+  left: i64,
+  right: i64,
+) -> Result<i64, RuntimeError>
+{
+  if matches!(primitive, Primitive::Divide | Primitive::Modulo) && right == 0 {
+    return Err(RuntimeError::DivisionByZero)
+  }
+  match primitive {
+    | Primitive::Add => left.checked_add(right),
+    | Primitive::Subtract => left.checked_sub(right),
+    | Primitive::Multiply => left.checked_mul(right),
+    | Primitive::Divide => left.checked_div(right),
+    | _ => left.checked_rem(right),
+  }
+  .ok_or(RuntimeError::Overflow)
+}
+
+fn float_arithmetic(
+  primitive: Primitive,
+  left: f64,
+  right: f64,
+) -> Result<f64, RuntimeError>
+{
+  match primitive {
+    | Primitive::AddFloat => Ok(left + right),
+    | Primitive::SubtractFloat => Ok(left - right),
+    | Primitive::MultiplyFloat => Ok(left * right),
+    | _ if right == 0.0 => Err(RuntimeError::DivisionByZero),
+    | _ => Ok(left / right),
+  }
+}
+
+/// Rounds towards zero, the result must be a whole number that fits.
+fn truncate(value: f64) -> Result<Value, RuntimeError>
+{
+  let truncated = value.trunc();
+  // `i64::MAX as f64` rounds up to 2^63, which no longer fits
+  match truncated >= i64::MIN as f64 && truncated < i64::MAX as f64 {
+    | true => Ok(Value::Integer(truncated as i64)),
+    | false => Err(RuntimeError::Overflow),
+  }
+}
+
+/// Orders values of the same type, `None` if they are unordered as a NaN is
+/// with every float, itself included. Floats follow IEEE 754, so `0.0` and
+/// `-0.0` are equal and every comparison with a NaN is `false`.
+fn compare(
+  left: &Value,
+  right: &Value,
+) -> Result<Option<Ordering>, RuntimeError>
+{
+  match (left, right) {
+    | (Value::Integer(left), Value::Integer(right)) =>
+      Ok(Some(left.cmp(right))),
+    | (Value::Float(left), Value::Float(right)) => Ok(left.partial_cmp(right)),
+    | (Value::String(left), Value::String(right)) => Ok(Some(left.cmp(right))),
+    | (Value::Bool(left), Value::Bool(right)) => Ok(Some(left.cmp(right))),
+    | (Value::Unit, Value::Unit) => Ok(Some(Ordering::Equal)),
+    // lexicographically, the element types are the same on both sides
+    | (Value::Tuple(left), Value::Tuple(right)) => {
+      for (left, right) in left.iter().zip(right.iter()) {
+        match compare(left, right)? {
+          | Some(Ordering::Equal) => (),
+          | ordering => return Ok(ordering),
+        }
+      }
+      Ok(Some(left.len().cmp(&right.len())))
+    },
+    // lexicographically, a list comes before the lists it is a prefix of
+    | (Value::List(left), Value::List(right)) => {
+      let mut left = left.iter();
+      let mut right = right.iter();
+      loop {
+        match (left.next(), right.next()) {
+          | (Some(left), Some(right)) => match compare(left, right)? {
+            | Some(Ordering::Equal) => (),
+            | ordering => return Ok(ordering),
+          },
+          | (left, right) =>
+            return Ok(Some(left.is_some().cmp(&right.is_some()))),
+        }
+      }
+    },
+    // field by field, both sides have the same fields in the same order
+    | (Value::Record(left), Value::Record(right)) => {
+      for ((_, left), (_, right)) in left.iter().zip(right.iter()) {
+        match compare(left, right)? {
+          | Some(Ordering::Equal) => (),
+          | ordering => return Ok(ordering),
+        }
+      }
+      Ok(Some(Ordering::Equal))
+    },
+    // by the order of the constructors in the type, then by argument
+    | (
+      Value::Variant {
+        tag: left_tag,
+        argument: left,
+        ..
+      },
+      Value::Variant {
+        tag: right_tag,
+        argument: right,
+        ..
+      },
+    ) => match (left_tag.cmp(right_tag), left, right) {
+      | (Ordering::Equal, Some(left), Some(right)) => compare(left, right),
+      | (ordering, ..) => Ok(Some(ordering)),
+    },
+    | (
+      Value::Constructor {
+        ..
+      }
+      | Value::Closure {
+        ..
+      }
+      | Value::Recursive {
+        ..
+      }
+      | Value::Primitive {
+        ..
+      },
+      _,
+    ) => Err(RuntimeError::NotComparable(left.clone())),
+    | _ => Err(RuntimeError::Internal(format!(
+      "ill-typed comparison of `{}` and `{}`",
+      left, right
+    ))),
+  }
+}
+
+#[cfg(test)]
+mod spec
+{
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  fn integer(value: i64) -> Value
+  {
+    Value::Integer(value)
+  }
+
+  fn float(value: f64) -> Value
+  {
+    Value::Float(value)
+  }
+
+  #[test]
+  fn integer_arithmetic_is_exact()
+  {
+    assert_eq!(
+      apply(Primitive::Add, &[integer(1_000), integer(234)]),
+      Ok(integer(1234))
+    );
+    assert_eq!(
+      apply(Primitive::Divide, &[integer(7), integer(2)]),
+      Ok(integer(3))
+    );
+    assert_eq!(
+      apply(Primitive::Modulo, &[integer(7), integer(2)]),
+      Ok(integer(1))
+    );
+  }
+
+  #[test]
+  fn negation()
+  {
+    assert_eq!(apply(Primitive::Negate, &[integer(3)]), Ok(integer(-3)));
+    assert_eq!(apply(Primitive::NegateFloat, &[float(-2.5)]), Ok(float(2.5)));
+    assert_eq!(
+      apply(Primitive::Negate, &[integer(i64::MIN)]),
+      Err(RuntimeError::Overflow)
+    );
+  }
+
+  #[test]
+  fn ill_typed_arguments_are_internal_errors()
+  {
+    assert_eq!(
+      apply(Primitive::Add, &[integer(1), float(2.0)]),
+      Err(RuntimeError::Internal("ill-typed application of `+`".to_string()))
+    );
+    assert_eq!(
+      apply(Primitive::Less, &[integer(1), Value::Bool(true)]),
+      Err(RuntimeError::Internal(
+        "ill-typed comparison of `1` and `true`".to_string()
+      ))
+    );
+  }
+
+  #[test]
+  fn float_arithmetic_is_approximate()
+  {
+    assert_eq!(
+      apply(Primitive::MultiplyFloat, &[float(1.5), float(3.0)]),
+      Ok(float(4.5))
+    );
+    assert_eq!(
+      apply(Primitive::DivideFloat, &[float(7.0), float(2.0)]),
+      Ok(float(3.5))
+    );
+    assert_eq!(
+      apply(Primitive::AddFloat, &[float(0.1), float(0.2)]),
+      Ok(float(0.30000000000000004))
+    );
+  }
+
+  #[test]
+  fn conversions()
+  {
+    assert_eq!(apply(Primitive::Float, &[integer(3)]), Ok(float(3.0)));
+    assert_eq!(apply(Primitive::Truncate, &[float(-3.7)]), Ok(integer(-3)));
+    assert_eq!(
+      apply(Primitive::Truncate, &[float(1e19)]),
+      Err(RuntimeError::Overflow)
+    );
+    assert_eq!(
+      apply(Primitive::Truncate, &[float(f64::NAN)]),
+      Err(RuntimeError::Overflow)
+    );
+  }
+
+  #[test]
+  fn division_by_zero_is_an_error()
+  {
+    assert_eq!(
+      apply(Primitive::Divide, &[integer(1), integer(0)]),
+      Err(RuntimeError::DivisionByZero)
+    );
+    assert_eq!(
+      apply(Primitive::Modulo, &[integer(1), integer(0)]),
+      Err(RuntimeError::DivisionByZero)
+    );
+    assert_eq!(
+      apply(Primitive::DivideFloat, &[float(1.5), float(0.0)]),
+      Err(RuntimeError::DivisionByZero)
+    );
+  }
+
+  #[test]
+  fn overflow_is_an_error()
+  {
+    assert_eq!(
+      apply(Primitive::Multiply, &[integer(i64::MAX), integer(2)]),
+      Err(RuntimeError::Overflow)
+    );
+  }
+
+  #[test]
+  fn tuples_compare_element_by_element()
+  {
+    let pair = |left, right| Value::Tuple(vec![integer(left), integer(right)]);
+    assert_eq!(
+      apply(Primitive::Less, &[pair(1, 5), pair(2, 0)]),
+      Ok(Value::Bool(true))
+    );
+    assert_eq!(
+      apply(Primitive::Equal, &[pair(1, 5), pair(1, 5)]),
+      Ok(Value::Bool(true))
+    );
+    assert_eq!(
+      apply(Primitive::Equal, &[Value::Unit, Value::Unit]),
+      Ok(Value::Bool(true))
+    );
+  }
+
+  #[test]
+  fn lists_compare_element_by_element()
+  {
+    let list = |values: &[i64]| {
+      Value::List(
+        values
+          .iter()
+          .copied()
+          .map(integer)
+          .collect(),
+      )
+    };
+    assert_eq!(
+      apply(Primitive::Less, &[list(&[1, 5]), list(&[2])]),
+      Ok(Value::Bool(true))
+    );
+    assert_eq!(
+      apply(Primitive::Less, &[list(&[1]), list(&[1, 0])]),
+      Ok(Value::Bool(true))
+    );
+    assert_eq!(
+      apply(Primitive::Equal, &[list(&[1, 5]), list(&[1, 5])]),
+      Ok(Value::Bool(true))
+    );
+    assert_eq!(
+      apply(Primitive::Cons, &[integer(1), list(&[2])]),
+      Ok(list(&[1, 2]))
+    );
+  }
+
+  #[test]
+  fn comparisons()
+  {
+    assert_eq!(
+      apply(Primitive::Equal, &[float(1.0), float(1.0)]),
+      Ok(Value::Bool(true))
+    );
+    assert_eq!(
+      apply(Primitive::Less, &[integer(2), integer(10)]),
+      Ok(Value::Bool(true))
+    );
+    assert_eq!(
+      apply(Primitive::GreaterOrEqual, &[
+        Value::String("abc".into()),
+        Value::String("abd".into())
+      ]),
+      Ok(Value::Bool(false))
+    );
+    assert_eq!(
+      apply(Primitive::LessOrEqual, &[Value::Bool(false), Value::Bool(true)]),
+      Ok(Value::Bool(true))
+    );
+  }
+
+  #[test]
+  fn floats_compare_as_in_ieee_754()
+  {
+    let nan = float(f64::NAN);
+    assert_eq!(
+      apply(Primitive::Equal, &[float(0.0), float(-0.0)]),
+      Ok(Value::Bool(true))
+    );
+    for primitive in [
+      Primitive::Equal,
+      Primitive::Less,
+      Primitive::LessOrEqual,
+      Primitive::Greater,
+      Primitive::GreaterOrEqual,
+    ] {
+      assert_eq!(
+        apply(primitive, &[nan.clone(), nan.clone()]),
+        Ok(Value::Bool(false))
+      );
+      assert_eq!(
+        apply(primitive, &[float(1.0), nan.clone()]),
+        Ok(Value::Bool(false))
+      );
+    }
+    let pair = |left| Value::Tuple(vec![float(left), integer(1)]);
+    assert_eq!(
+      apply(Primitive::Equal, &[pair(f64::NAN), pair(f64::NAN)]),
+      Ok(Value::Bool(false))
+    );
+    assert_eq!(
+      apply(Primitive::Less, &[pair(-0.0), pair(0.0)]),
+      Ok(Value::Bool(false))
+    );
+  }
+
+  #[test]
+  fn functions_cannot_be_compared()
+  {
+    let function = Value::Primitive {
+      primitive: Primitive::Not,
+      arguments: vec![],
+    };
+    assert_eq!(
+      apply(Primitive::Equal, &[function.clone(), function.clone()]),
+      Err(RuntimeError::NotComparable(function))
+    );
+  }
+
+  #[test]
+  fn strings_and_booleans()
+  {
+    assert_eq!(
+      apply(Primitive::Concatenate, &[
+        Value::String("foo".into()),
+        Value::String("bar".into())
+      ]),
+      Ok(Value::String("foobar".into()))
+    );
+    assert_eq!(
+      apply(Primitive::Not, &[Value::Bool(true)]),
+      Ok(Value::Bool(false))
+    );
+    assert_eq!(
+      apply(Primitive::And, &[Value::Bool(true), Value::Bool(false)]),
+      Ok(Value::Bool(false))
+    );
+    assert_eq!(
+      apply(Primitive::Or, &[Value::Bool(true), Value::Bool(false)]),
+      Ok(Value::Bool(true))
+    );
+  }
+}
@@ -42,6 +42,26 @@ impl TransformInto<Lfv> for debrujin::Expression
         abstraction.transform(context),
       | debrujin::Expression::Application(application) =>
         application.transform(context),
+      | debrujin::Expression::Conditional(conditional) =>
+        conditional.transform(context),
+      | debrujin::Expression::Let(let_in) => let_in.transform(context),
+      | debrujin::Expression::LetRec(let_rec) => let_rec.transform(context),
+      | debrujin::Expression::Tuple(tuple) => tuple.transform(context),
+      | debrujin::Expression::List(list) => list.transform(context),
+      | debrujin::Expression::Projection(projection) =>
+        projection.tuple.transform(context),
+      | debrujin::Expression::Match(pattern_match) =>
+        pattern_match.transform(context),
+      | debrujin::Expression::Record(record) => record.transform(context),
+      | debrujin::Expression::FieldAccess(access) =>
+        access.record.transform(context),
+      | debrujin::Expression::Member(member) =>
+        member.structure.transform(context),
+      | debrujin::Expression::Update(update) => update.transform(context),
+      | debrujin::Expression::Extension(extension) =>
+        extension.transform(context),
+      | debrujin::Expression::Restriction(restriction) =>
+        restriction.record.transform(context),
     }
   }
 }
@@ -127,6 +147,7 @@ mod application
         name: 0,
       }
       .into(),
+      span: None,
     };
     let Lfv(largest) = application.transform(0);
     assert_eq!(largest, 2);
@@ -144,12 +165,355 @@ mod application
         name: 1,
       }
       .into(),
+      span: None,
     };
     let Lfv(largest) = application.transform(0);
     assert_eq!(largest, 2);
   }
 }
 
+impl TransformInto<Lfv> for debrujin::Conditional
+{
+  type Context<'a> = usize;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Lfv
+  {
+    let Lfv(condition) = self.condition.transform(context);
+    let Lfv(consequence) = self.consequence.transform(context);
+    let Lfv(alternative) = self.alternative.transform(context);
+    Lfv(
+      condition
+        .max(consequence)
+        .max(alternative),
+    )
+  }
+}
+
+impl TransformInto<Lfv> for debrujin::Let
+{
+  type Context<'a> = usize;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Lfv
+  {
+    let Lfv(body) = self
+      .body
+      .transform(context + self.values.len());
+    Lfv(
+      self
+        .values
+        .iter()
+        .map(|value| {
+          let Lfv(value) = value.transform(context);
+          value
+        })
+        .fold(body, std::cmp::max),
+    )
+  }
+}
+
+#[cfg(test)]
+mod let_in
+{
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn body_sees_values()
+  {
+    let let_in = debrujin::Let {
+      values: vec![debrujin::Identifier::new(0).into()],
+      body: debrujin::Identifier::new(0).into(),
+    };
+    let Lfv(largest) = let_in.transform(0);
+    assert_eq!(largest, 1);
+  }
+
+  #[test]
+  fn body_is_greater()
+  {
+    let let_in = debrujin::Let {
+      values: vec![debrujin::Literal::Boolean(true).into()],
+      body: debrujin::Identifier::new(2).into(),
+    };
+    let Lfv(largest) = let_in.transform(0);
+    assert_eq!(largest, 2);
+  }
+}
+
+impl TransformInto<Lfv> for debrujin::LetRec
+{
+  type Context<'a> = usize;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Lfv
+  {
+    let context = context + self.functions.len();
+    let Lfv(body) = self.body.transform(context);
+    Lfv(
+      self
+        .functions
+        .iter()
+        .map(|function| {
+          let Lfv(function) = function.transform(context);
+          function
+        })
+        .fold(body, std::cmp::max),
+    )
+  }
+}
+
+#[cfg(test)]
+mod let_rec
+{
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn functions_see_each_other()
+  {
+    let let_rec = debrujin::LetRec {
+      functions: vec![
+        debrujin::Abstraction {
+          body: debrujin::Identifier::new(1).into(),
+        },
+        debrujin::Abstraction {
+          body: debrujin::Identifier::new(2).into(),
+        },
+      ],
+      body: debrujin::Identifier::new(0).into(),
+    };
+    let Lfv(largest) = let_rec.transform(0);
+    assert_eq!(largest, 0);
+  }
+
+  #[test]
+  fn function_is_greater()
+  {
+    let let_rec = debrujin::LetRec {
+      functions: vec![debrujin::Abstraction {
+        body: debrujin::Identifier::new(3).into(),
+      }],
+      body: debrujin::Identifier::new(0).into(),
+    };
+    let Lfv(largest) = let_rec.transform(0);
+    assert_eq!(largest, 2);
+  }
+}
+
+impl TransformInto<Lfv> for debrujin::Tuple
+{
+  type Context<'a> = usize;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Lfv
+  {
+    Lfv(
+      self
+        .elements
+        .iter()
+        .map(|element| {
+          let Lfv(element) = element.transform(context);
+          element
+        })
+        .fold(0, std::cmp::max),
+    )
+  }
+}
+
+#[cfg(test)]
+mod tuple
+{
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn largest_element()
+  {
+    let tuple = debrujin::Tuple {
+      elements: vec![
+        debrujin::Identifier::new(2).into(),
+        debrujin::Literal::Unit.into(),
+        debrujin::Identifier::new(0).into(),
+      ],
+    };
+    let Lfv(largest) = tuple.transform(1);
+    assert_eq!(largest, 2);
+  }
+}
+
+impl TransformInto<Lfv> for debrujin::List
+{
+  type Context<'a> = usize;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Lfv
+  {
+    Lfv(
+      self
+        .elements
+        .iter()
+        .map(|element| {
+          let Lfv(element) = element.transform(context);
+          element
+        })
+        .fold(0, std::cmp::max),
+    )
+  }
+}
+
+impl TransformInto<Lfv> for debrujin::Record
+{
+  type Context<'a> = usize;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Lfv
+  {
+    Lfv(
+      self
+        .fields
+        .iter()
+        .map(|(_, value)| {
+          let Lfv(value) = value.transform(context);
+          value
+        })
+        .fold(0, std::cmp::max),
+    )
+  }
+}
+
+impl TransformInto<Lfv> for debrujin::Update
+{
+  type Context<'a> = usize;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Lfv
+  {
+    let Lfv(record) = self.record.transform(context);
+    Lfv(
+      self
+        .fields
+        .iter()
+        .map(|(_, value)| {
+          let Lfv(value) = value.transform(context);
+          value
+        })
+        .fold(record, std::cmp::max),
+    )
+  }
+}
+
+impl TransformInto<Lfv> for debrujin::Extension
+{
+  type Context<'a> = usize;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Lfv
+  {
+    let Lfv(record) = self.record.transform(context);
+    Lfv(
+      self
+        .fields
+        .iter()
+        .map(|(_, value)| {
+          let Lfv(value) = value.transform(context);
+          value
+        })
+        .fold(record, std::cmp::max),
+    )
+  }
+}
+
+#[cfg(test)]
+mod record
+{
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn largest_of_the_record_and_its_fields()
+  {
+    let update = debrujin::Update {
+      record: debrujin::Identifier::new(1).into(),
+      fields: vec![
+        ("a".into(), debrujin::Identifier::new(3).into()),
+        ("b".into(), debrujin::Literal::Unit.into()),
+      ],
+    };
+    let Lfv(largest) = update.transform(1);
+    assert_eq!(largest, 3);
+  }
+}
+
+impl TransformInto<Lfv> for debrujin::Match
+{
+  type Context<'a> = usize;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Lfv
+  {
+    let Lfv(scrutinee) = self.scrutinee.transform(context);
+    Lfv(
+      self
+        .arms
+        .iter()
+        .map(|arm| {
+          let Lfv(body) = arm
+            .body
+            .transform(context + arm.bindings.len());
+          body
+        })
+        .fold(scrutinee, std::cmp::max),
+    )
+  }
+}
+
+#[cfg(test)]
+mod pattern_match
+{
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn arms_see_their_bindings()
+  {
+    let pattern_match = debrujin::Match {
+      scrutinee: debrujin::Identifier::new(0).into(),
+      tree: debrujin::DecisionTree::Leaf(0),
+      arms: vec![debrujin::Arm {
+        bindings: vec![vec![0], vec![1]],
+        body: debrujin::Identifier::new(3).into(),
+      }],
+    };
+    let Lfv(largest) = pattern_match.transform(0);
+    assert_eq!(largest, 2);
+  }
+}
+
 impl TransformInto<Lfv> for debrujin::Identifier
 {
   type Context<'a> = usize;
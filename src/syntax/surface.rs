@@ -1,11 +1,13 @@
 mod common;
 mod declaration;
 mod expression;
+mod program;
 mod top_level;
 pub mod transformations;
-mod types;
+pub mod types;
 
 pub use common::*;
 pub use declaration::*;
 pub use expression::*;
+pub use program::*;
 pub use top_level::*;
@@ -1,3 +1,9 @@
 mod identifier;
 
 pub use identifier::Identifier;
+
+pub use crate::syntax::common::{
+  Location,
+  Primitive,
+  Span,
+};
@@ -1,7 +1,10 @@
+use super::Span;
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Identifier
 {
   pub name: String,
+  pub span: Span,
 }
 
 impl Identifier
@@ -12,6 +15,7 @@ impl Identifier
   {
     Self {
       name: name.into(),
+      span: Span::default(),
     }
   }
 }
@@ -1,3 +1,14 @@
+mod def_binding;
+mod open;
+mod structure;
+mod type_declaration;
 mod val_binding;
 
+pub use def_binding::DefBinding;
+pub use open::Open;
+pub use structure::Structure;
+pub use type_declaration::{
+  Constructor,
+  TypeDeclaration,
+};
 pub use val_binding::ValBinding;
@@ -0,0 +1,11 @@
+use crate::syntax::surface::{
+  Function,
+  Span,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DefBinding
+{
+  pub functions: Vec<Function>,
+  pub span: Span,
+}
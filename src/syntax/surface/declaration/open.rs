@@ -0,0 +1,13 @@
+use crate::syntax::surface::{
+  Identifier,
+  Span,
+};
+
+/// `open Foo.Bar ;`, binds every member of the structure `Bar` nested in
+/// `Foo` under its own name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Open
+{
+  pub path: Vec<Identifier>,
+  pub span: Span,
+}
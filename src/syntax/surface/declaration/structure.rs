@@ -0,0 +1,15 @@
+use crate::syntax::surface::{
+  Identifier,
+  Span,
+  TopLevel,
+};
+
+/// `structure Foo = struct ... end ;`, declares the structure `name` whose
+/// members are the names `declarations` bind, referred to as `Foo.bar`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Structure
+{
+  pub name: Identifier,
+  pub declarations: Vec<TopLevel>,
+  pub span: Span,
+}
@@ -0,0 +1,45 @@
+use crate::syntax::surface::{
+  types,
+  Identifier,
+  Span,
+};
+
+/// `type 'a option = None | Some of 'a ;`, declares the type `name` over
+/// the type variables `parameters` along with its constructors.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeDeclaration
+{
+  pub name: Identifier,
+  pub parameters: Vec<Identifier>,
+  pub constructors: Vec<Constructor>,
+  pub span: Span,
+}
+
+impl TypeDeclaration
+{
+  /// The type `constant` that the declaration declares, applied to its own
+  /// parameters, `'a option`.
+  pub fn typ(
+    &self,
+    constant: types::Constant,
+  ) -> types::Type
+  {
+    types::Type::Concrete(
+      constant,
+      self
+        .parameters
+        .iter()
+        .map(|parameter| types::Variable::Named(parameter.clone()).into())
+        .collect(),
+    )
+  }
+}
+
+/// `Some of 'a`, a constructor without an argument is a value of the type
+/// on its own.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Constructor
+{
+  pub name: Identifier,
+  pub argument: Option<types::Type>,
+}
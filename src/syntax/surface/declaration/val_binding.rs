@@ -1,6 +1,7 @@
 use crate::syntax::surface::{
   Expression,
   Identifier,
+  Span,
 };
 
 #[derive(Debug, Clone, PartialEq)]
@@ -8,4 +9,5 @@ pub struct ValBinding
 {
   pub name: Identifier,
   pub value: Expression,
+  pub span: Span,
 }
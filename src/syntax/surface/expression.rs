@@ -1,27 +1,113 @@
 mod abstraction;
+mod annotation;
 mod application;
+mod conditional;
+mod let_in;
+mod let_rec;
+mod list;
+mod pattern;
+mod pattern_match;
+mod qualified;
+mod record;
+mod tuple;
 
-pub use abstraction::Abstraction;
+pub use abstraction::{
+  Abstraction,
+  Parameter,
+};
+pub use annotation::Annotation;
 pub use application::Application;
+pub use conditional::Conditional;
+pub use let_in::{
+  Binding,
+  Let,
+};
+pub use let_rec::{
+  Function,
+  LetRec,
+};
+pub use list::List;
+pub use pattern::Pattern;
+pub use pattern_match::{
+  Arm,
+  Match,
+};
+pub use qualified::Qualified;
+pub use record::{
+  Extension,
+  Field,
+  FieldAccess,
+  Record,
+  Restriction,
+  Update,
+};
+pub use tuple::{
+  Projection,
+  Tuple,
+};
 
-pub use super::common::Identifier;
+pub use super::common::{
+  Identifier,
+  Span,
+};
 pub use crate::syntax::common::Literal;
 
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Expression
 {
-  Literal(Literal),
+  Literal(Literal, Span),
   Identifier(Identifier),
+  Qualified(Box<Qualified>),
   Abstraction(Box<Abstraction>),
   Application(Box<Application>),
+  Conditional(Box<Conditional>),
+  Annotation(Box<Annotation>),
+  Let(Box<Let>),
+  LetRec(Box<LetRec>),
+  Tuple(Box<Tuple>),
+  Projection(Box<Projection>),
+  List(Box<List>),
+  Match(Box<Match>),
+  Record(Box<Record>),
+  FieldAccess(Box<FieldAccess>),
+  Update(Box<Update>),
+  Extension(Box<Extension>),
+  Restriction(Box<Restriction>),
+}
+
+impl Expression
+{
+  pub fn span(&self) -> Span
+  {
+    match self {
+      | Self::Literal(_, span) => *span,
+      | Self::Identifier(identifier) => identifier.span,
+      | Self::Qualified(qualified) => qualified.span,
+      | Self::Abstraction(abstraction) => abstraction.span,
+      | Self::Application(application) => application.span,
+      | Self::Conditional(conditional) => conditional.span,
+      | Self::Annotation(annotation) => annotation.span,
+      | Self::Let(let_in) => let_in.span,
+      | Self::LetRec(let_rec) => let_rec.span,
+      | Self::Tuple(tuple) => tuple.span,
+      | Self::Projection(projection) => projection.span,
+      | Self::List(list) => list.span,
+      | Self::Match(pattern_match) => pattern_match.span,
+      | Self::Record(record) => record.span,
+      | Self::FieldAccess(access) => access.span,
+      | Self::Update(update) => update.span,
+      | Self::Extension(extension) => extension.span,
+      | Self::Restriction(restriction) => restriction.span,
+    }
+  }
 }
 
 impl From<Literal> for Expression
 {
   fn from(literal: Literal) -> Self
   {
-    Self::Literal(literal)
+    Self::Literal(literal, Span::default())
   }
 }
 
@@ -33,6 +119,14 @@ impl From<Identifier> for Expression
   }
 }
 
+impl From<Qualified> for Expression
+{
+  fn from(qualified: Qualified) -> Self
+  {
+    Self::Qualified(Box::new(qualified))
+  }
+}
+
 impl From<Abstraction> for Expression
 {
   fn from(abstraction: Abstraction) -> Self
@@ -48,3 +142,107 @@ impl From<Application> for Expression
     Self::Application(Box::new(application))
   }
 }
+
+impl From<Annotation> for Expression
+{
+  fn from(annotation: Annotation) -> Self
+  {
+    Self::Annotation(Box::new(annotation))
+  }
+}
+
+impl From<Conditional> for Expression
+{
+  fn from(conditional: Conditional) -> Self
+  {
+    Self::Conditional(Box::new(conditional))
+  }
+}
+
+impl From<Let> for Expression
+{
+  fn from(let_in: Let) -> Self
+  {
+    Self::Let(Box::new(let_in))
+  }
+}
+
+impl From<LetRec> for Expression
+{
+  fn from(let_rec: LetRec) -> Self
+  {
+    Self::LetRec(Box::new(let_rec))
+  }
+}
+
+impl From<Tuple> for Expression
+{
+  fn from(tuple: Tuple) -> Self
+  {
+    Self::Tuple(Box::new(tuple))
+  }
+}
+
+impl From<Projection> for Expression
+{
+  fn from(projection: Projection) -> Self
+  {
+    Self::Projection(Box::new(projection))
+  }
+}
+
+impl From<List> for Expression
+{
+  fn from(list: List) -> Self
+  {
+    Self::List(Box::new(list))
+  }
+}
+
+impl From<Match> for Expression
+{
+  fn from(pattern_match: Match) -> Self
+  {
+    Self::Match(Box::new(pattern_match))
+  }
+}
+
+impl From<Record> for Expression
+{
+  fn from(record: Record) -> Self
+  {
+    Self::Record(Box::new(record))
+  }
+}
+
+impl From<FieldAccess> for Expression
+{
+  fn from(access: FieldAccess) -> Self
+  {
+    Self::FieldAccess(Box::new(access))
+  }
+}
+
+impl From<Update> for Expression
+{
+  fn from(update: Update) -> Self
+  {
+    Self::Update(Box::new(update))
+  }
+}
+
+impl From<Extension> for Expression
+{
+  fn from(extension: Extension) -> Self
+  {
+    Self::Extension(Box::new(extension))
+  }
+}
+
+impl From<Restriction> for Expression
+{
+  fn from(restriction: Restriction) -> Self
+  {
+    Self::Restriction(Box::new(restriction))
+  }
+}
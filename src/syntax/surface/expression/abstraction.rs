@@ -1,11 +1,43 @@
 use super::{
   Expression,
   Identifier,
+  Pattern,
+  Span,
 };
+use crate::syntax::surface::types;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Abstraction
 {
-  pub parameters: Vec<Identifier>,
+  pub parameters: Vec<Parameter>,
   pub body: Expression,
+  pub span: Span,
+}
+
+/// A parameter of an abstraction, optionally annotated as in
+/// `fun (x : Int) -> x`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Parameter
+{
+  pub pattern: Pattern,
+  pub annotation: Option<types::Type>,
+}
+
+impl From<Pattern> for Parameter
+{
+  fn from(pattern: Pattern) -> Self
+  {
+    Self {
+      pattern,
+      annotation: None,
+    }
+  }
+}
+
+impl From<Identifier> for Parameter
+{
+  fn from(name: Identifier) -> Self
+  {
+    Pattern::from(name).into()
+  }
 }
@@ -0,0 +1,14 @@
+use super::{
+  Expression,
+  Span,
+};
+use crate::syntax::surface::types;
+
+/// An expression with the type it is declared to have, `(x : Int)`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Annotation
+{
+  pub expression: Expression,
+  pub annotation: types::Type,
+  pub span: Span,
+}
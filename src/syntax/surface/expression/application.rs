@@ -1,4 +1,7 @@
-use super::Expression;
+use super::{
+  Expression,
+  Span,
+};
 
 
 #[derive(Debug, Clone, PartialEq)]
@@ -6,4 +9,5 @@ pub struct Application
 {
   pub abstraction: Expression,
   pub arguments: Vec<Expression>,
+  pub span: Span,
 }
@@ -0,0 +1,14 @@
+use super::{
+  Expression,
+  Span,
+};
+
+/// `if condition then consequence else alternative`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conditional
+{
+  pub condition: Expression,
+  pub consequence: Expression,
+  pub alternative: Expression,
+  pub span: Span,
+}
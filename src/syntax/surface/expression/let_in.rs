@@ -0,0 +1,22 @@
+use super::{
+  Expression,
+  Identifier,
+  Span,
+};
+
+/// `let x = e1 and y = e2 in body`, the bindings are simultaneous, so no value
+/// can refer to the names bound alongside it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Let
+{
+  pub bindings: Vec<Binding>,
+  pub body: Expression,
+  pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Binding
+{
+  pub name: Identifier,
+  pub value: Expression,
+}
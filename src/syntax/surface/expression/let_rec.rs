@@ -0,0 +1,24 @@
+use super::{
+  Abstraction,
+  Expression,
+  Identifier,
+  Span,
+};
+
+/// `let rec f x = e1 and g y = e2 in body`, every function is in scope
+/// within all of the functions as well as within `body`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LetRec
+{
+  pub functions: Vec<Function>,
+  pub body: Expression,
+  pub span: Span,
+}
+
+/// `f x y = e`, an abstraction that is named so that it can call itself.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Function
+{
+  pub name: Identifier,
+  pub abstraction: Abstraction,
+}
@@ -0,0 +1,12 @@
+use super::{
+  Expression,
+  Span,
+};
+
+/// `[a, b, c]`, the empty list `[]` has no elements.
+#[derive(Debug, Clone, PartialEq)]
+pub struct List
+{
+  pub elements: Vec<Expression>,
+  pub span: Span,
+}
@@ -0,0 +1,55 @@
+use super::{
+  Identifier,
+  Literal,
+  Span,
+};
+
+/// The shape of a value, binding names to its parts.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pattern
+{
+  Identifier(Identifier),
+  /// `_`, matches anything without binding it.
+  Wildcard(Span),
+  Literal(Literal, Span),
+  /// `(x, y)`, matches a tuple of as many elements.
+  Tuple(Vec<Pattern>, Span),
+  /// `Some x`, matches the values built by the constructor. The constructor
+  /// of `Option.Some x` is a member of the structure `Option`, along the path
+  /// of structures before its name.
+  Constructor(Vec<Identifier>, Identifier, Option<Box<Pattern>>),
+  /// `[x, y]`, matches a list of as many elements, `[]` the empty list.
+  List(Vec<Pattern>, Span),
+  /// `x :: xs`, matches a list that is not empty by its head and tail.
+  Cons(Box<Pattern>, Box<Pattern>),
+}
+
+impl Pattern
+{
+  pub fn span(&self) -> Span
+  {
+    match self {
+      | Self::Identifier(identifier) => identifier.span,
+      | Self::Wildcard(span)
+      | Self::Literal(_, span)
+      | Self::Tuple(_, span)
+      | Self::List(_, span) => *span,
+      | Self::Constructor(path, name, argument) => {
+        let start = path.first().unwrap_or(name).span;
+        match argument {
+          | Some(argument) => start.merge(argument.span()),
+          | None => start.merge(name.span),
+        }
+      },
+      | Self::Cons(head, tail) => head.span().merge(tail.span()),
+    }
+  }
+}
+
+impl From<Identifier> for Pattern
+{
+  fn from(identifier: Identifier) -> Self
+  {
+    Self::Identifier(identifier)
+  }
+}
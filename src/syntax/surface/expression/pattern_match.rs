@@ -0,0 +1,22 @@
+use super::{
+  Expression,
+  Pattern,
+  Span,
+};
+
+/// `match scrutinee with | pattern -> body`, the first arm whose pattern
+/// matches is taken.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Match
+{
+  pub scrutinee: Expression,
+  pub arms: Vec<Arm>,
+  pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Arm
+{
+  pub pattern: Pattern,
+  pub body: Expression,
+}
@@ -0,0 +1,13 @@
+use super::{
+  Identifier,
+  Span,
+};
+
+/// `Foo.Bar.baz`, the member `name` of the structure `Bar` nested in `Foo`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Qualified
+{
+  pub path: Vec<Identifier>,
+  pub name: Identifier,
+  pub span: Span,
+}
@@ -0,0 +1,58 @@
+use super::{
+  Expression,
+  Identifier,
+  Span,
+};
+
+/// `{ name = e; age = e }`, a record of distinct fields.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record
+{
+  pub fields: Vec<Field>,
+  pub span: Span,
+}
+
+/// `name = value`, a field of a record or of an update.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field
+{
+  pub name: Identifier,
+  pub value: Expression,
+}
+
+/// `record.name`, the value of the field `field`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldAccess
+{
+  pub record: Expression,
+  pub field: Identifier,
+  pub span: Span,
+}
+
+/// `{ record with age = e }`, a copy of `record` with new values for
+/// `fields`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Update
+{
+  pub record: Expression,
+  pub fields: Vec<Field>,
+  pub span: Span,
+}
+
+/// `{ age = e | record }`, `record` with the new fields `fields`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Extension
+{
+  pub fields: Vec<Field>,
+  pub record: Expression,
+  pub span: Span,
+}
+
+/// `{ record without age }`, `record` without the fields `fields`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Restriction
+{
+  pub record: Expression,
+  pub fields: Vec<Identifier>,
+  pub span: Span,
+}
@@ -0,0 +1,21 @@
+use super::{
+  Expression,
+  Span,
+};
+
+/// `(a, b, c)`, a tuple has at least two elements.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tuple
+{
+  pub elements: Vec<Expression>,
+  pub span: Span,
+}
+
+/// `tuple.0`, the element of `tuple` at `index`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Projection
+{
+  pub tuple: Expression,
+  pub index: usize,
+  pub span: Span,
+}
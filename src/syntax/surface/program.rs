@@ -0,0 +1,8 @@
+use super::TopLevel;
+
+/// The declarations of a source file, in the order they appear.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Program
+{
+  pub declarations: Vec<TopLevel>,
+}
@@ -1,9 +1,53 @@
-use super::ValBinding;
+use super::{
+  DefBinding,
+  Identifier,
+  Open,
+  Structure,
+  TypeDeclaration,
+  ValBinding,
+};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum TopLevel
 {
   ValBinding(Box<ValBinding>),
+  DefBinding(Box<DefBinding>),
+  TypeDeclaration(Box<TypeDeclaration>),
+  Structure(Box<Structure>),
+  Open(Box<Open>),
+}
+
+impl TopLevel
+{
+  /// The names the declaration brings into scope, in order. The members of a
+  /// structure are named as `Foo.bar`, `open` has none as the names it
+  /// brings into scope are only known once the structure is resolved.
+  pub fn names(&self) -> Vec<Identifier>
+  {
+    match self {
+      | Self::ValBinding(binding) => vec![binding.name.clone()],
+      | Self::DefBinding(binding) => binding
+        .functions
+        .iter()
+        .map(|function| function.name.clone())
+        .collect(),
+      | Self::TypeDeclaration(declaration) => declaration
+        .constructors
+        .iter()
+        .map(|constructor| constructor.name.clone())
+        .collect(),
+      | Self::Structure(structure) => structure
+        .declarations
+        .iter()
+        .flat_map(TopLevel::names)
+        .map(|member| Identifier {
+          name: format!("{}.{}", structure.name.name, member.name),
+          span: member.span,
+        })
+        .collect(),
+      | Self::Open(_) => vec![],
+    }
+  }
 }
 
 impl From<ValBinding> for TopLevel
@@ -13,3 +57,35 @@ impl From<ValBinding> for TopLevel
     Self::ValBinding(Box::new(val))
   }
 }
+
+impl From<DefBinding> for TopLevel
+{
+  fn from(def: DefBinding) -> Self
+  {
+    Self::DefBinding(Box::new(def))
+  }
+}
+
+impl From<TypeDeclaration> for TopLevel
+{
+  fn from(declaration: TypeDeclaration) -> Self
+  {
+    Self::TypeDeclaration(Box::new(declaration))
+  }
+}
+
+impl From<Structure> for TopLevel
+{
+  fn from(structure: Structure) -> Self
+  {
+    Self::Structure(Box::new(structure))
+  }
+}
+
+impl From<Open> for TopLevel
+{
+  fn from(open: Open) -> Self
+  {
+    Self::Open(Box::new(open))
+  }
+}
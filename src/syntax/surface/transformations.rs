@@ -1,2 +1,3 @@
 pub mod debrujin_encoding;
+pub mod exhaustiveness;
 pub mod infer_type;
@@ -1,19 +1,114 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+
 use thiserror::Error;
 
+use crate::diagnostics::{
+  Diagnostic,
+  ToDiagnostic,
+};
 use crate::syntax::{
   debrujin,
   surface,
 };
 use crate::transform_into::TransformInto;
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct Context
 {
-  stack: Vec<String>,
+  stack: Vec<Binding>,
+  /// The tag of each constructor and the number of constructors of its type.
+  constructors: HashMap<String, (usize, usize)>,
+  /// Whether applications are encoded without their spans, as the prelude's
+  /// are.
+  unlocated: bool,
+}
+
+/// A name on the stack, structures carry their members along to resolve the
+/// names qualified by them.
+#[derive(Clone)]
+struct Binding
+{
+  name: String,
+  structure: Option<Rc<Module>>,
+}
+
+impl Binding
+{
+  fn value(name: impl Into<String>) -> Self
+  {
+    Self {
+      name: name.into(),
+      structure: None,
+    }
+  }
+
+  fn is_value(
+    &self,
+    name: &str,
+  ) -> bool
+  {
+    self.structure.is_none() && self.name == name
+  }
+
+  fn structure(
+    &self,
+    name: &str,
+  ) -> Option<&Rc<Module>>
+  {
+    self
+      .structure
+      .as_ref()
+      .filter(|_| self.name == name)
+  }
+}
+
+/// The names a structure binds, in the order of its members at runtime, and
+/// the constructors it declares.
+#[derive(Default)]
+struct Module
+{
+  members: Vec<Binding>,
+  constructors: HashMap<String, (usize, usize)>,
 }
 
 impl Context
 {
+  /// A context where every primitive is bound to its name, in the order of
+  /// `Primitive::ALL`.
+  pub fn with_primitives() -> Self
+  {
+    Self {
+      stack: surface::Primitive::ALL
+        .into_iter()
+        .map(|primitive| Binding::value(primitive.name()))
+        .collect(),
+      constructors: HashMap::new(),
+      unlocated: false,
+    }
+  }
+
+  /// Encodes the applications after it without their spans if `unlocated`,
+  /// see `debrujin::Application::span`.
+  pub fn unlocated(
+    &mut self,
+    unlocated: bool,
+  )
+  {
+    self.unlocated = unlocated;
+  }
+
+  /// Brings `binding` into scope for everything encoded afterwards.
+  pub fn bind(
+    &mut self,
+    binding: &surface::Identifier,
+  )
+  {
+    self
+      .stack
+      .push(Binding::value(binding.name.clone()));
+  }
+
   fn with_bindings<TResult>(
     &mut self,
     bindings: &[surface::Identifier],
@@ -21,7 +116,7 @@ impl Context
   ) -> TResult
   {
     for binding in bindings {
-      self.stack.push(binding.name.clone());
+      self.bind(binding);
     }
     let result = computation(self);
     for _ in bindings {
@@ -30,17 +125,213 @@ impl Context
     result
   }
 
+  /// Encodes `functions` with all of their names in scope.
+  fn recursive(
+    &mut self,
+    functions: &[surface::Function],
+  ) -> Result<
+    (Vec<surface::Identifier>, Vec<debrujin::Abstraction>),
+    TransformError,
+  >
+  {
+    let names = functions
+      .iter()
+      .map(|function| function.name.clone())
+      .collect::<Vec<_>>();
+    let functions = self.with_bindings(&names, |context| {
+      functions
+        .iter()
+        .map(|function| {
+          function
+            .abstraction
+            .debrujin_encoding(context)
+        })
+        .collect::<Result<_, _>>()
+    })?;
+    Ok((names, functions))
+  }
+
+  /// The name a parameter is bound to, any other pattern is bound to a name
+  /// that no identifier can refer to and matched by `destructure`.
+  fn pattern_name(
+    &self,
+    pattern: &surface::Pattern,
+  ) -> String
+  {
+    match pattern {
+      | surface::Pattern::Identifier(identifier) => identifier.name.clone(),
+      | _ => format!("#{}", self.stack.len()),
+    }
+  }
+
+  /// Encodes `body` with the names of `patterns` in scope, given the name
+  /// each pattern is bound to. Every pattern but identifiers and wildcards
+  /// becomes a `Match` of its name.
+  fn destructure(
+    &mut self,
+    patterns: &[(String, &surface::Pattern)],
+    body: &surface::Expression,
+  ) -> Result<debrujin::Expression, TransformError>
+  {
+    let Some(((name, pattern), patterns)) = patterns.split_first()
+    else {
+      return body.debrujin_encoding(self)
+    };
+    if let surface::Pattern::Identifier(_) | surface::Pattern::Wildcard(_) =
+      pattern
+    {
+      return self.destructure(patterns, body)
+    }
+    let scrutinee = self
+      .stack
+      .iter()
+      .rev()
+      .position(|binding| binding.is_value(name))
+      .expect("patterns are bound before being matched");
+    let (pattern, names) = self.pattern(pattern)?;
+    let body = self
+      .with_bindings(&names, |context| context.destructure(patterns, body))?;
+    Ok(
+      debrujin::Match {
+        scrutinee: debrujin::Identifier::new(scrutinee).into(),
+        tree: debrujin::DecisionTree::compile(std::slice::from_ref(&pattern)),
+        arms: vec![debrujin::Arm {
+          bindings: pattern.bindings(),
+          body,
+        }],
+      }
+      .into(),
+    )
+  }
+
+  /// Resolves the constructors of `pattern`, along with the names it binds
+  /// from left to right.
+  fn pattern(
+    &self,
+    pattern: &surface::Pattern,
+  ) -> Result<(debrujin::Pattern, Vec<surface::Identifier>), TransformError>
+  {
+    let mut names = vec![];
+    let pattern = self.collect_pattern(pattern, &mut names)?;
+    Ok((pattern, names))
+  }
+
+  fn collect_pattern(
+    &self,
+    pattern: &surface::Pattern,
+    names: &mut Vec<surface::Identifier>,
+  ) -> Result<debrujin::Pattern, TransformError>
+  {
+    Ok(match pattern {
+      | surface::Pattern::Identifier(identifier) => {
+        names.push(identifier.clone());
+        debrujin::Pattern::Binding
+      },
+      | surface::Pattern::Wildcard(_) => debrujin::Pattern::Wildcard,
+      | surface::Pattern::Literal(literal, _) =>
+        debrujin::Pattern::Literal(literal.clone()),
+      | surface::Pattern::Tuple(elements, _) => debrujin::Pattern::Tuple(
+        elements
+          .iter()
+          .map(|element| self.collect_pattern(element, names))
+          .collect::<Result<_, _>>()?,
+      ),
+      | surface::Pattern::Constructor(path, name, argument) => {
+        let (tag, constructors) = match path.last() {
+          | None => *self
+            .constructors
+            .get(&name.name)
+            .ok_or_else(|| TransformError::UnknownConstructor(name.clone()))?,
+          | Some(structure) => *self
+            .structure(path)?
+            .1
+            .constructors
+            .get(&name.name)
+            .ok_or_else(|| TransformError::NoMember {
+              structure: structure.name.clone(),
+              name: name.clone(),
+            })?,
+        };
+        debrujin::Pattern::Constructor {
+          tag,
+          constructors,
+          argument: argument
+            .as_deref()
+            .map(|argument| self.collect_pattern(argument, names))
+            .transpose()?
+            .map(Box::new),
+        }
+      },
+      | surface::Pattern::List(elements, _) => {
+        let elements = elements
+          .iter()
+          .map(|element| self.collect_pattern(element, names))
+          .collect::<Result<Vec<_>, _>>()?;
+        elements
+          .into_iter()
+          .rev()
+          .fold(debrujin::Pattern::Nil, |tail, head| {
+            debrujin::Pattern::Cons(Box::new(head), Box::new(tail))
+          })
+      },
+      | surface::Pattern::Cons(head, tail) => debrujin::Pattern::Cons(
+        Box::new(self.collect_pattern(head, names)?),
+        Box::new(self.collect_pattern(tail, names)?),
+      ),
+    })
+  }
+
   fn lookup(
     &self,
-    name: &str,
+    identifier: &surface::Identifier,
   ) -> std::result::Result<usize, TransformError>
   {
     self
       .stack
       .iter()
       .rev()
-      .position(|binding| name == binding)
-      .ok_or_else(|| TransformError::free_variable(name))
+      .position(|binding| binding.is_value(&identifier.name))
+      .ok_or_else(|| TransformError::FreeVariable(identifier.clone()))
+  }
+
+  /// Resolves the structure `path` names, to an expression evaluating to it
+  /// and its members.
+  fn structure(
+    &self,
+    path: &[surface::Identifier],
+  ) -> Result<(debrujin::Expression, Rc<Module>), TransformError>
+  {
+    let (first, nested) = path
+      .split_first()
+      .expect("a path names at least one structure");
+    let (index, mut module) = self
+      .stack
+      .iter()
+      .rev()
+      .enumerate()
+      .find_map(|(index, binding)| {
+        Some((index, binding.structure(&first.name)?.clone()))
+      })
+      .ok_or_else(|| TransformError::UnknownStructure(first.clone()))?;
+    let mut structure = debrujin::Identifier::new(index).into();
+    for name in nested {
+      let (index, inner) = module
+        .members
+        .iter()
+        .enumerate()
+        .rev()
+        .find_map(|(index, member)| {
+          Some((index, member.structure(&name.name)?.clone()))
+        })
+        .ok_or_else(|| TransformError::UnknownStructure(name.clone()))?;
+      structure = debrujin::Member {
+        structure,
+        index,
+      }
+      .into();
+      module = inner;
+    }
+    Ok((structure, module))
   }
 }
 
@@ -73,17 +364,40 @@ where
 #[derive(Debug, Clone, PartialEq, Error)]
 pub enum TransformError
 {
-  #[error("free variable")]
-  FreeVariable(String),
+  #[error("unbound variable `{}`", .0.name)]
+  FreeVariable(surface::Identifier),
+  #[error("unknown constructor `{}`", .0.name)]
+  UnknownConstructor(surface::Identifier),
+  #[error("unknown structure `{}`", .0.name)]
+  UnknownStructure(surface::Identifier),
+  #[error("the structure `{}` has no member `{}`", .structure, .name.name)]
+  NoMember
+  {
+    structure: String,
+    name: surface::Identifier,
+  },
 }
 
-impl TransformError
+impl ToDiagnostic for TransformError
 {
-  pub fn free_variable<IntoString>(name: IntoString) -> Self
-  where
-    IntoString: Into<String>,
+  fn to_diagnostic(&self) -> Diagnostic
   {
-    Self::FreeVariable(name.into())
+    match self {
+      | TransformError::FreeVariable(identifier) =>
+        Diagnostic::error(self.to_string())
+          .with_primary(identifier.span, "not found in this scope"),
+      | TransformError::UnknownConstructor(identifier) =>
+        Diagnostic::error(self.to_string())
+          .with_primary(identifier.span, "no type declares it"),
+      | TransformError::UnknownStructure(identifier) =>
+        Diagnostic::error(self.to_string())
+          .with_primary(identifier.span, "no structure of this name in scope"),
+      | TransformError::NoMember {
+        name,
+        ..
+      } => Diagnostic::error(self.to_string())
+        .with_primary(name.span, "not declared by the structure"),
+    }
   }
 }
 
@@ -98,11 +412,42 @@ impl TransformInto<Result<debrujin::Expression, TransformError>>
   ) -> Result<debrujin::Expression, TransformError>
   {
     context
-      .lookup(self.name.as_str())
+      .lookup(self)
       .map(|name| debrujin::Identifier::new(name).into())
   }
 }
 
+impl TransformInto<Result<debrujin::Expression, TransformError>>
+  for surface::Qualified
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<debrujin::Expression, TransformError>
+  {
+    let (structure, module) = context.structure(&self.path)?;
+    let index = module
+      .members
+      .iter()
+      .rposition(|member| member.is_value(&self.name.name))
+      .ok_or_else(|| TransformError::NoMember {
+        structure: self.path[self.path.len() - 1]
+          .name
+          .clone(),
+        name: self.name.clone(),
+      })?;
+    Ok(
+      debrujin::Member {
+        structure,
+        index,
+      }
+      .into(),
+    )
+  }
+}
+
 impl TransformInto<Result<debrujin::Expression, TransformError>>
   for surface::Expression
 {
@@ -114,17 +459,120 @@ impl TransformInto<Result<debrujin::Expression, TransformError>>
   ) -> Result<debrujin::Expression, TransformError>
   {
     match self {
-      | surface::Expression::Literal(literal) => Ok(literal.clone().into()),
+      | surface::Expression::Literal(literal, _) => Ok(literal.clone().into()),
       | surface::Expression::Identifier(identifier) =>
         identifier.transform(context),
+      | surface::Expression::Qualified(qualified) =>
+        qualified.transform(context),
       | surface::Expression::Abstraction(abstraction) =>
-        abstraction.transform(context),
+        DebrujinEncoding::<debrujin::Abstraction>::debrujin_encoding(
+          abstraction.as_ref(),
+          context,
+        )
+        .map(Into::into),
       | surface::Expression::Application(application) =>
         application.transform(context),
+      | surface::Expression::Conditional(conditional) =>
+        conditional.transform(context),
+      | surface::Expression::Annotation(annotation) =>
+        annotation.expression.transform(context),
+      | surface::Expression::Let(let_in) => let_in.transform(context),
+      | surface::Expression::LetRec(let_rec) => let_rec.transform(context),
+      | surface::Expression::Tuple(tuple) => Ok(
+        debrujin::Tuple {
+          elements: tuple
+            .elements
+            .iter()
+            .map(|element| element.debrujin_encoding(context))
+            .collect::<Result<_, _>>()?,
+        }
+        .into(),
+      ),
+      | surface::Expression::List(list) => Ok(
+        debrujin::List {
+          elements: list
+            .elements
+            .iter()
+            .map(|element| element.debrujin_encoding(context))
+            .collect::<Result<_, _>>()?,
+        }
+        .into(),
+      ),
+      | surface::Expression::Projection(projection) => Ok(
+        debrujin::Projection {
+          tuple: projection
+            .tuple
+            .debrujin_encoding(context)?,
+          index: projection.index,
+        }
+        .into(),
+      ),
+      | surface::Expression::Match(pattern_match) =>
+        pattern_match.transform(context),
+      | surface::Expression::Record(record) => Ok(
+        debrujin::Record {
+          fields: fields(&record.fields, context)?,
+        }
+        .into(),
+      ),
+      | surface::Expression::FieldAccess(access) => Ok(
+        debrujin::FieldAccess {
+          record: access
+            .record
+            .debrujin_encoding(context)?,
+          field: access.field.name.clone(),
+        }
+        .into(),
+      ),
+      | surface::Expression::Update(update) => Ok(
+        debrujin::Update {
+          record: update
+            .record
+            .debrujin_encoding(context)?,
+          fields: fields(&update.fields, context)?,
+        }
+        .into(),
+      ),
+      | surface::Expression::Extension(extension) => Ok(
+        debrujin::Extension {
+          fields: fields(&extension.fields, context)?,
+          record: extension
+            .record
+            .debrujin_encoding(context)?,
+        }
+        .into(),
+      ),
+      | surface::Expression::Restriction(restriction) => Ok(
+        debrujin::Restriction {
+          record: restriction
+            .record
+            .debrujin_encoding(context)?,
+          fields: restriction
+            .fields
+            .iter()
+            .map(|field| field.name.clone())
+            .collect(),
+        }
+        .into(),
+      ),
     }
   }
 }
 
+/// Encodes the values of `fields`, keeping their names.
+fn fields(
+  fields: &[surface::Field],
+  context: &mut Context,
+) -> Result<Vec<(String, debrujin::Expression)>, TransformError>
+{
+  fields
+    .iter()
+    .map(|field| {
+      Ok((field.name.name.clone(), field.value.debrujin_encoding(context)?))
+    })
+    .collect()
+}
+
 impl TransformInto<Result<debrujin::Expression, TransformError>>
   for surface::Application
 {
@@ -142,6 +590,7 @@ impl TransformInto<Result<debrujin::Expression, TransformError>>
       abstraction = debrujin::Application {
         abstraction,
         argument: argument.debrujin_encoding(context)?,
+        span: Some(self.span).filter(|_| !context.unlocated),
       }
       .into();
     }
@@ -150,24 +599,285 @@ impl TransformInto<Result<debrujin::Expression, TransformError>>
 }
 
 impl TransformInto<Result<debrujin::Expression, TransformError>>
+  for surface::Conditional
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<debrujin::Expression, TransformError>
+  {
+    Ok(
+      debrujin::Conditional {
+        condition: self
+          .condition
+          .debrujin_encoding(context)?,
+        consequence: self
+          .consequence
+          .debrujin_encoding(context)?,
+        alternative: self
+          .alternative
+          .debrujin_encoding(context)?,
+      }
+      .into(),
+    )
+  }
+}
+
+impl TransformInto<Result<debrujin::Abstraction, TransformError>>
   for surface::Abstraction
 {
   type Context<'a> = &'a mut Context;
 
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<debrujin::Abstraction, TransformError>
+  {
+    let depth = context.stack.len();
+    let mut patterns = vec![];
+    for parameter in self.parameters.iter() {
+      let name = context.pattern_name(&parameter.pattern);
+      context
+        .stack
+        .push(Binding::value(name.clone()));
+      patterns.push((name, &parameter.pattern));
+    }
+    let body = context.destructure(&patterns, &self.body);
+    context.stack.truncate(depth);
+    let mut abstraction = debrujin::Abstraction {
+      body: body?,
+    };
+    for _ in self.parameters.iter().skip(1) {
+      abstraction = debrujin::Abstraction {
+        body: abstraction.into(),
+      };
+    }
+    Ok(abstraction)
+  }
+}
+
+impl TransformInto<Result<debrujin::Expression, TransformError>>
+  for surface::Match
+{
+  type Context<'a> = &'a mut Context;
+
   fn transform(
     &self,
     context: Self::Context<'_>,
   ) -> Result<debrujin::Expression, TransformError>
   {
-    context.with_bindings(&self.parameters, |context| {
-      let mut body = self.body.debrujin_encoding(context)?;
-      for _ in self.parameters.iter() {
-        body = debrujin::Abstraction {
-          body,
-        }
-        .into();
+    let scrutinee = self
+      .scrutinee
+      .debrujin_encoding(context)?;
+    let mut patterns = vec![];
+    let mut arms = vec![];
+    for arm in self.arms.iter() {
+      let (pattern, names) = context.pattern(&arm.pattern)?;
+      let body = context
+        .with_bindings(&names, |context| arm.body.debrujin_encoding(context))?;
+      arms.push(debrujin::Arm {
+        bindings: pattern.bindings(),
+        body,
+      });
+      patterns.push(pattern);
+    }
+    Ok(
+      debrujin::Match {
+        scrutinee,
+        tree: debrujin::DecisionTree::compile(&patterns),
+        arms,
+      }
+      .into(),
+    )
+  }
+}
+
+impl TransformInto<Result<debrujin::Expression, TransformError>>
+  for surface::Let
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<debrujin::Expression, TransformError>
+  {
+    let values = self
+      .bindings
+      .iter()
+      .map(|binding| binding.value.debrujin_encoding(context))
+      .collect::<Result<_, _>>()?;
+    let names = self
+      .bindings
+      .iter()
+      .map(|binding| binding.name.clone())
+      .collect::<Vec<_>>();
+    let body = context
+      .with_bindings(&names, |context| self.body.debrujin_encoding(context))?;
+    Ok(
+      debrujin::Let {
+        values,
+        body,
       }
-      Ok(body)
+      .into(),
+    )
+  }
+}
+
+impl TransformInto<Result<debrujin::Expression, TransformError>>
+  for surface::LetRec
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<debrujin::Expression, TransformError>
+  {
+    let (names, functions) = context.recursive(&self.functions)?;
+    let body = context
+      .with_bindings(&names, |context| self.body.debrujin_encoding(context))?;
+    Ok(
+      debrujin::LetRec {
+        functions,
+        body,
+      }
+      .into(),
+    )
+  }
+}
+
+impl TransformInto<Result<debrujin::Val, TransformError>>
+  for surface::ValBinding
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<debrujin::Val, TransformError>
+  {
+    let value = self.value.debrujin_encoding(context)?;
+    context.bind(&self.name);
+    Ok(debrujin::Val {
+      value,
+    })
+  }
+}
+
+impl TransformInto<Result<debrujin::Def, TransformError>>
+  for surface::DefBinding
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<debrujin::Def, TransformError>
+  {
+    let (names, functions) = context.recursive(&self.functions)?;
+    for name in names.iter() {
+      context.bind(name);
+    }
+    Ok(debrujin::Def {
+      functions,
+    })
+  }
+}
+
+impl TransformInto<Result<debrujin::Data, TransformError>>
+  for surface::TypeDeclaration
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<debrujin::Data, TransformError>
+  {
+    let count = self.constructors.len();
+    let constructors = self
+      .constructors
+      .iter()
+      .enumerate()
+      .map(|(tag, constructor)| {
+        context.bind(&constructor.name);
+        context
+          .constructors
+          .insert(constructor.name.name.clone(), (tag, count));
+        debrujin::Constructor {
+          tag,
+          name: constructor.name.name.clone(),
+          takes_argument: constructor.argument.is_some(),
+        }
+      })
+      .collect();
+    Ok(debrujin::Data {
+      constructors,
+    })
+  }
+}
+
+/// Encodes the declarations of the structure in the scope around it,
+/// afterwards its members and the constructors it declares are only in scope
+/// through its name.
+impl TransformInto<Result<debrujin::Structure, TransformError>>
+  for surface::Structure
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<debrujin::Structure, TransformError>
+  {
+    let depth = context.stack.len();
+    let constructors = context.constructors.clone();
+    let declarations = self
+      .declarations
+      .iter()
+      .map(|declaration| declaration.debrujin_encoding(&mut *context))
+      .collect::<Result<_, _>>();
+    let members = context.stack.split_off(depth);
+    let mut declared =
+      std::mem::replace(&mut context.constructors, constructors);
+    declared.retain(|name, tag| context.constructors.get(name) != Some(tag));
+    context.stack.push(Binding {
+      name: self.name.name.clone(),
+      structure: Some(Rc::new(Module {
+        members,
+        constructors: declared,
+      })),
+    });
+    Ok(debrujin::Structure {
+      declarations: declarations?,
+    })
+  }
+}
+
+impl TransformInto<Result<debrujin::Open, TransformError>> for surface::Open
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<debrujin::Open, TransformError>
+  {
+    let (structure, module) = context.structure(&self.path)?;
+    context
+      .stack
+      .extend(module.members.iter().cloned());
+    context.constructors.extend(
+      module
+        .constructors
+        .iter()
+        .map(|(name, tag)| (name.clone(), *tag)),
+    );
+    Ok(debrujin::Open {
+      structure,
     })
   }
 }
@@ -175,14 +885,66 @@ impl TransformInto<Result<debrujin::Expression, TransformError>>
 impl TransformInto<Result<debrujin::TopLevel, TransformError>>
   for surface::TopLevel
 {
-  type Context<'a> = &'a mut Vec<String>;
+  type Context<'a> = &'a mut Context;
 
   fn transform(
     &self,
-    _context: Self::Context<'_>,
+    context: Self::Context<'_>,
   ) -> Result<debrujin::TopLevel, TransformError>
   {
-    todo!()
+    match self {
+      | surface::TopLevel::ValBinding(binding) => DebrujinEncoding::<
+        debrujin::Val,
+      >::debrujin_encoding(
+        binding.as_ref(), context
+      )
+      .map(Into::into),
+      | surface::TopLevel::DefBinding(binding) => DebrujinEncoding::<
+        debrujin::Def,
+      >::debrujin_encoding(
+        binding.as_ref(), context
+      )
+      .map(Into::into),
+      | surface::TopLevel::TypeDeclaration(declaration) =>
+        DebrujinEncoding::<debrujin::Data>::debrujin_encoding(
+          declaration.as_ref(),
+          context,
+        )
+        .map(Into::into),
+      | surface::TopLevel::Structure(structure) =>
+        DebrujinEncoding::<debrujin::Structure>::debrujin_encoding(
+          structure.as_ref(),
+          context,
+        )
+        .map(Into::into),
+      | surface::TopLevel::Open(open) =>
+        DebrujinEncoding::<debrujin::Open>::debrujin_encoding(
+          open.as_ref(),
+          context,
+        )
+        .map(Into::into),
+    }
+  }
+}
+
+impl TransformInto<Result<debrujin::Program, TransformError>>
+  for surface::Program
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<debrujin::Program, TransformError>
+  {
+    let declarations = self
+      .declarations
+      .iter()
+      .map(|declaration| declaration.debrujin_encoding(context))
+      .collect::<Result<_, _>>()?;
+    Ok(debrujin::Program {
+      declarations,
+    })
   }
 }
 
@@ -213,11 +975,10 @@ mod expressions
     );
 
     let mut context = Context::default();
-    let expression: surface::Expression =
-      surface::Literal::Numeric("10.0".into()).into();
+    let expression: surface::Expression = surface::Literal::Float(10.0).into();
     assert_eq!(
       expression.debrujin_encoding(&mut context),
-      Ok(debrujin::Literal::Numeric("10.0".into()).into())
+      Ok(debrujin::Literal::Float(10.0).into())
     );
 
     let mut context = Context::default();
@@ -237,7 +998,7 @@ mod expressions
       surface::Identifier::new("foo").into();
     assert_eq!(
       expression.debrujin_encoding(&mut context),
-      Err(TransformError::free_variable("foo"))
+      Err(TransformError::FreeVariable(surface::Identifier::new("foo")))
     );
   }
 
@@ -247,15 +1008,16 @@ mod expressions
     let mut context = Context::default();
     let expression: surface::Expression = surface::Abstraction {
       parameters: vec![
-        surface::Identifier::new("x"),
-        surface::Identifier::new("y"),
+        surface::Identifier::new("x").into(),
+        surface::Identifier::new("y").into(),
       ],
       body: surface::Identifier::new("foo").into(),
+      span: Default::default(),
     }
     .into();
     assert_eq!(
       expression.debrujin_encoding(&mut context),
-      Err(TransformError::free_variable("foo"))
+      Err(TransformError::FreeVariable(surface::Identifier::new("foo")))
     );
   }
 
@@ -265,10 +1027,11 @@ mod expressions
     let mut context = Context::default();
     let expression: surface::Expression = surface::Abstraction {
       parameters: vec![
-        surface::Identifier::new("x"),
-        surface::Identifier::new("y"),
+        surface::Identifier::new("x").into(),
+        surface::Identifier::new("y").into(),
       ],
       body: surface::Identifier::new("x").into(),
+      span: Default::default(),
     }
     .into();
     assert_eq!(
@@ -291,10 +1054,11 @@ mod expressions
     let mut context = Context::default();
     let expression: surface::Expression = surface::Abstraction {
       parameters: vec![
-        surface::Identifier::new("x"),
-        surface::Identifier::new("y"),
+        surface::Identifier::new("x").into(),
+        surface::Identifier::new("y").into(),
       ],
       body: surface::Identifier::new("y").into(),
+      span: Default::default(),
     }
     .into();
     assert_eq!(
@@ -310,4 +1074,403 @@ mod expressions
       )
     );
   }
+
+  #[test]
+  fn tuple_parameters_are_taken_apart()
+  {
+    let mut context = Context::default();
+    let name =
+      |name: &str| surface::Pattern::from(surface::Identifier::new(name));
+    let expression: surface::Expression = surface::Abstraction {
+      parameters: vec![
+        surface::Pattern::Tuple(
+          vec![
+            name("x"),
+            surface::Pattern::Tuple(
+              vec![name("y"), name("z")],
+              Default::default(),
+            ),
+          ],
+          Default::default(),
+        )
+        .into(),
+        surface::Identifier::new("w").into(),
+      ],
+      body: surface::Tuple {
+        elements: vec![
+          surface::Identifier::new("x").into(),
+          surface::Identifier::new("z").into(),
+        ],
+        span: Default::default(),
+      }
+      .into(),
+      span: Default::default(),
+    }
+    .into();
+    assert_eq!(
+      expression.debrujin_encoding(&mut context),
+      Ok(
+        debrujin::Abstraction {
+          body: debrujin::Abstraction {
+            body: debrujin::Match {
+              scrutinee: debrujin::Identifier::new(1).into(),
+              tree: debrujin::DecisionTree::Leaf(0),
+              arms: vec![debrujin::Arm {
+                bindings: vec![vec![0], vec![1, 0], vec![1, 1]],
+                body: debrujin::Tuple {
+                  elements: vec![
+                    debrujin::Identifier::new(2).into(),
+                    debrujin::Identifier::new(0).into(),
+                  ],
+                }
+                .into(),
+              }],
+            }
+            .into(),
+          }
+          .into()
+        }
+        .into()
+      )
+    );
+    assert!(context.stack.is_empty());
+  }
+
+  #[test]
+  fn match_arms_see_their_bindings()
+  {
+    let mut context = Context::default();
+    let declaration = surface::TypeDeclaration {
+      name: surface::Identifier::new("option"),
+      parameters: vec![surface::Identifier::new("a")],
+      constructors: vec![
+        surface::Constructor {
+          name: surface::Identifier::new("None"),
+          argument: None,
+        },
+        surface::Constructor {
+          name: surface::Identifier::new("Some"),
+          argument: Some(
+            surface::types::Variable::Named(surface::Identifier::new("a"))
+              .into(),
+          ),
+        },
+      ],
+      span: Default::default(),
+    };
+    DebrujinEncoding::<debrujin::Data>::debrujin_encoding(
+      &declaration,
+      &mut context,
+    )
+    .unwrap();
+    context.bind(&surface::Identifier::new("x"));
+    let expression: surface::Expression = surface::Match {
+      scrutinee: surface::Identifier::new("x").into(),
+      arms: vec![
+        surface::Arm {
+          pattern: surface::Pattern::Constructor(
+            vec![],
+            surface::Identifier::new("Some"),
+            Some(Box::new(surface::Identifier::new("y").into())),
+          ),
+          body: surface::Identifier::new("y").into(),
+        },
+        surface::Arm {
+          pattern: surface::Pattern::Wildcard(Default::default()),
+          body: surface::Identifier::new("x").into(),
+        },
+      ],
+      span: Default::default(),
+    }
+    .into();
+    let patterns = [
+      debrujin::Pattern::Constructor {
+        tag: 1,
+        constructors: 2,
+        argument: Some(Box::new(debrujin::Pattern::Binding)),
+      },
+      debrujin::Pattern::Wildcard,
+    ];
+    assert_eq!(
+      expression.debrujin_encoding(&mut context),
+      Ok(
+        debrujin::Match {
+          scrutinee: debrujin::Identifier::new(0).into(),
+          tree: debrujin::DecisionTree::compile(&patterns),
+          arms: vec![
+            debrujin::Arm {
+              bindings: vec![vec![0]],
+              body: debrujin::Identifier::new(0).into(),
+            },
+            debrujin::Arm {
+              bindings: vec![],
+              body: debrujin::Identifier::new(0).into(),
+            },
+          ],
+        }
+        .into()
+      )
+    );
+  }
+
+  #[test]
+  fn unknown_constructors_are_rejected()
+  {
+    let mut context = Context::default();
+    let expression: surface::Expression = surface::Match {
+      scrutinee: surface::Literal::Unit.into(),
+      arms: vec![surface::Arm {
+        pattern: surface::Pattern::Constructor(
+          vec![],
+          surface::Identifier::new("Nope"),
+          None,
+        ),
+        body: surface::Literal::Unit.into(),
+      }],
+      span: Default::default(),
+    }
+    .into();
+    assert_eq!(
+      expression.debrujin_encoding(&mut context),
+      Err(TransformError::UnknownConstructor(surface::Identifier::new("Nope")))
+    );
+  }
+
+  #[test]
+  fn let_bindings_are_simultaneous()
+  {
+    let mut context = Context::default();
+    context.bind(&surface::Identifier::new("x"));
+    let expression: surface::Expression = surface::Let {
+      bindings: vec![
+        surface::Binding {
+          name: surface::Identifier::new("x"),
+          value: surface::Literal::Boolean(true).into(),
+        },
+        surface::Binding {
+          name: surface::Identifier::new("y"),
+          value: surface::Identifier::new("x").into(),
+        },
+      ],
+      body: surface::Identifier::new("x").into(),
+      span: Default::default(),
+    }
+    .into();
+    assert_eq!(
+      expression.debrujin_encoding(&mut context),
+      Ok(
+        debrujin::Let {
+          values: vec![
+            debrujin::Literal::Boolean(true).into(),
+            debrujin::Identifier::new(0).into(),
+          ],
+          body: debrujin::Identifier::new(1).into(),
+        }
+        .into()
+      )
+    );
+  }
+
+  #[test]
+  fn let_rec_functions_see_each_other()
+  {
+    let mut context = Context::default();
+    let function = |name: &str, other: &str| surface::Function {
+      name: surface::Identifier::new(name),
+      abstraction: surface::Abstraction {
+        parameters: vec![surface::Identifier::new("x").into()],
+        body: surface::Identifier::new(other).into(),
+        span: Default::default(),
+      },
+    };
+    let expression: surface::Expression = surface::LetRec {
+      functions: vec![function("f", "g"), function("g", "f")],
+      body: surface::Identifier::new("f").into(),
+      span: Default::default(),
+    }
+    .into();
+    assert_eq!(
+      expression.debrujin_encoding(&mut context),
+      Ok(
+        debrujin::LetRec {
+          functions: vec![
+            debrujin::Abstraction {
+              body: debrujin::Identifier::new(1).into(),
+            },
+            debrujin::Abstraction {
+              body: debrujin::Identifier::new(2).into(),
+            },
+          ],
+          body: debrujin::Identifier::new(1).into(),
+        }
+        .into()
+      )
+    );
+  }
+}
+
+#[cfg(test)]
+mod declarations
+{
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn later_declarations_see_earlier_names()
+  {
+    let mut context = Context::default();
+    let program = surface::Program {
+      declarations: vec![
+        surface::ValBinding {
+          name: surface::Identifier::new("x"),
+          value: surface::Literal::Boolean(true).into(),
+          span: Default::default(),
+        }
+        .into(),
+        surface::ValBinding {
+          name: surface::Identifier::new("y"),
+          value: surface::Identifier::new("x").into(),
+          span: Default::default(),
+        }
+        .into(),
+      ],
+    };
+    assert_eq!(
+      program.debrujin_encoding(&mut context),
+      Ok(debrujin::Program {
+        declarations: vec![
+          debrujin::Val {
+            value: debrujin::Literal::Boolean(true).into(),
+          }
+          .into(),
+          debrujin::Val {
+            value: debrujin::Identifier::new(0).into(),
+          }
+          .into(),
+        ],
+      })
+    );
+  }
+
+  #[test]
+  fn declarations_do_not_see_themselves()
+  {
+    let mut context = Context::default();
+    let declaration: surface::TopLevel = surface::ValBinding {
+      name: surface::Identifier::new("x"),
+      value: surface::Identifier::new("x").into(),
+      span: Default::default(),
+    }
+    .into();
+    assert_eq!(
+      DebrujinEncoding::<debrujin::TopLevel>::debrujin_encoding(
+        &declaration,
+        &mut context
+      ),
+      Err(TransformError::FreeVariable(surface::Identifier::new("x")))
+    );
+  }
+
+  fn encode(input: &str) -> Result<Vec<debrujin::TopLevel>, TransformError>
+  {
+    use crate::frontend::{
+      DeclarationParser,
+      Lexer,
+      WithBacktracking,
+    };
+    let program = Lexer::from_str(input)
+      .with_backtracking()
+      .expect_program()
+      .unwrap();
+    let encoded: debrujin::Program =
+      program.debrujin_encoding(&mut Context::default())?;
+    Ok(encoded.declarations)
+  }
+
+  #[test]
+  fn qualified_names_refer_to_members()
+  {
+    assert_eq!(
+      encode(
+        "structure A = struct val x = true ; structure B = struct val y = x ; \
+         end ; end ;\nval z = A.B.y ;\n"
+      ),
+      Ok(vec![
+        debrujin::Structure {
+          declarations: vec![
+            debrujin::Val {
+              value: debrujin::Literal::Boolean(true).into(),
+            }
+            .into(),
+            debrujin::Structure {
+              declarations: vec![debrujin::Val {
+                value: debrujin::Identifier::new(0).into(),
+              }
+              .into(),],
+            }
+            .into(),
+          ],
+        }
+        .into(),
+        debrujin::Val {
+          value: debrujin::Member {
+            structure: debrujin::Member {
+              structure: debrujin::Identifier::new(0).into(),
+              index: 1,
+            }
+            .into(),
+            index: 0,
+          }
+          .into(),
+        }
+        .into(),
+      ])
+    );
+  }
+
+  #[test]
+  fn members_are_only_in_scope_through_their_structure()
+  {
+    assert_eq!(
+      encode("structure A = struct val x = true ; end ;\nval y = x ;\n"),
+      Err(TransformError::FreeVariable(surface::Identifier::new("x")))
+    );
+    assert_eq!(
+      encode(
+        "structure A = struct type t = K ; end ;\nval f = fun k -> match k \
+         with K -> () ;\n"
+      ),
+      Err(TransformError::UnknownConstructor(surface::Identifier::new("K")))
+    );
+    assert_eq!(
+      encode("structure A = struct val x = true ; end ;\nval y = A.y ;\n"),
+      Err(TransformError::NoMember {
+        structure: "A".into(),
+        name: surface::Identifier::new("y"),
+      })
+    );
+    assert_eq!(
+      encode("val A = true ;\nval y = A.x ;\n"),
+      Err(TransformError::UnknownStructure(surface::Identifier::new("A")))
+    );
+  }
+
+  #[test]
+  fn open_binds_every_member()
+  {
+    assert_eq!(
+      encode(
+        "structure A = struct type t = K ; end ;\nopen A ;\nval f = fun k -> \
+         match k with K -> K ;\n"
+      )
+      .map(|declarations| declarations[1].clone()),
+      Ok(
+        debrujin::Open {
+          structure: debrujin::Identifier::new(0).into(),
+        }
+        .into()
+      )
+    );
+  }
 }
@@ -0,0 +1,802 @@
+use thiserror::Error;
+
+use super::infer_type::Context;
+use crate::diagnostics::{
+  Diagnostic,
+  ToDiagnostic,
+};
+use crate::syntax::surface::{
+  self,
+  types,
+};
+use crate::transform_into::TransformInto;
+
+pub trait CheckPatterns<'a>
+{
+  /// Warns about the matches that miss some values and the arms that can
+  /// never be taken, expects the program to be well typed in `context`.
+  fn check_patterns(
+    &self,
+    context: &'a Context,
+  ) -> Vec<PatternWarning>;
+}
+
+impl<'a, Representation> CheckPatterns<'a> for Representation
+where
+  Representation: TransformInto<Vec<PatternWarning>, Context<'a> = &'a Context>,
+{
+  fn check_patterns(
+    &self,
+    context: &'a Context,
+  ) -> Vec<PatternWarning>
+  {
+    self.transform(context)
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum PatternWarning
+{
+  #[error("non-exhaustive patterns: `{example}` is not matched")]
+  NonExhaustive
+  {
+    example: String,
+    span: surface::Span,
+  },
+  #[error("unreachable pattern")]
+  Unreachable(surface::Span),
+}
+
+impl ToDiagnostic for PatternWarning
+{
+  fn to_diagnostic(&self) -> Diagnostic
+  {
+    let diagnostic = Diagnostic::warning(self.to_string());
+    match self {
+      | PatternWarning::NonExhaustive {
+        example,
+        span,
+      } => diagnostic
+        .with_primary(*span, format!("`{}` is not matched", example))
+        .with_note("add an arm for it, or a wildcard `_` matching anything"),
+      | PatternWarning::Unreachable(span) => diagnostic
+        .with_primary(*span, "every value it matches is matched above"),
+    }
+  }
+}
+
+/// What a pattern tests a value against, with the number of its parts.
+#[derive(Debug, Clone, PartialEq)]
+enum Head
+{
+  Tuple(usize),
+  Constructor(String, usize),
+  Literal(surface::Literal),
+  /// The empty list.
+  Nil,
+  /// A list that is not empty, with its head and tail as parts.
+  Cons,
+}
+
+impl Head
+{
+  fn arity(&self) -> usize
+  {
+    match self {
+      | Head::Tuple(arity) | Head::Constructor(_, arity) => *arity,
+      | Head::Literal(_) | Head::Nil => 0,
+      | Head::Cons => 2,
+    }
+  }
+}
+
+/// A pattern reduced to what matching depends on. It also describes the
+/// values no pattern matches, where `Any` stands for any value.
+#[derive(Debug, Clone, PartialEq)]
+enum Shape
+{
+  Any,
+  Head(Head, Vec<Shape>),
+}
+
+impl From<&surface::Pattern> for Shape
+{
+  fn from(pattern: &surface::Pattern) -> Self
+  {
+    match pattern {
+      | surface::Pattern::Identifier(_) | surface::Pattern::Wildcard(_) =>
+        Shape::Any,
+      | surface::Pattern::Literal(literal, _) =>
+        Shape::Head(Head::Literal(literal.clone()), vec![]),
+      | surface::Pattern::Tuple(elements, _) => Shape::Head(
+        Head::Tuple(elements.len()),
+        elements
+          .iter()
+          .map(Shape::from)
+          .collect(),
+      ),
+      | surface::Pattern::Constructor(_, name, argument) => {
+        let arguments = argument
+          .iter()
+          .map(|argument| Shape::from(argument.as_ref()))
+          .collect::<Vec<_>>();
+        Shape::Head(
+          Head::Constructor(name.name.clone(), arguments.len()),
+          arguments,
+        )
+      },
+      | surface::Pattern::List(elements, _) => elements
+        .iter()
+        .rev()
+        .fold(Shape::Head(Head::Nil, vec![]), |tail, head| {
+          Shape::Head(Head::Cons, vec![Shape::from(head), tail])
+        }),
+      | surface::Pattern::Cons(head, tail) => Shape::Head(Head::Cons, vec![
+        Shape::from(head.as_ref()),
+        Shape::from(tail.as_ref()),
+      ]),
+    }
+  }
+}
+
+impl std::fmt::Display for Shape
+{
+  fn fmt(
+    &self,
+    f: &mut std::fmt::Formatter<'_>,
+  ) -> std::fmt::Result
+  {
+    match self {
+      | Shape::Any => write!(f, "_"),
+      | Shape::Head(Head::Literal(literal), _) => match literal {
+        // two backticks would run into those around the example
+        | surface::Literal::String(value) if value.is_empty() =>
+          write!(f, "\"\""),
+        | surface::Literal::String(value) => write!(f, "`{}`", value),
+        | surface::Literal::Integer(value) => write!(f, "{}", value),
+        | surface::Literal::Float(value) => write!(f, "{:?}", value),
+        | surface::Literal::Boolean(value) => write!(f, "{}", value),
+        | surface::Literal::Unit => write!(f, "()"),
+      },
+      | Shape::Head(Head::Tuple(_), elements) => {
+        let elements = elements
+          .iter()
+          .map(Shape::to_string)
+          .collect::<Vec<_>>();
+        write!(f, "({})", elements.join(", "))
+      },
+      | Shape::Head(Head::Constructor(name, _), arguments) =>
+        match arguments.as_slice() {
+          | [argument @ Shape::Head(Head::Constructor(_, 1) | Head::Cons, _)] =>
+            write!(f, "{} ({})", name, argument),
+          | [argument] => write!(f, "{} {}", name, argument),
+          | _ => write!(f, "{}", name),
+        },
+      | Shape::Head(Head::Nil, _) => write!(f, "[]"),
+      | Shape::Head(Head::Cons, parts) => match parts.as_slice() {
+        | [head @ Shape::Head(Head::Cons, _), tail] =>
+          write!(f, "({}) :: {}", head, tail),
+        | [head, tail] => write!(f, "{} :: {}", head, tail),
+        | _ => unreachable!("a list that is not empty has a head and a tail"),
+      },
+    }
+  }
+}
+
+/// Every head a value of `typ` can have, which `head` is one of, unless
+/// there are too many to list.
+fn all_heads(
+  context: &Context,
+  head: &Head,
+  typ: &types::Type,
+) -> Option<Vec<Head>>
+{
+  match head {
+    | Head::Tuple(_) => Some(vec![head.clone()]),
+    | Head::Constructor(..) => Some(
+      context
+        .constructors(typ)?
+        .iter()
+        .map(|constructor| {
+          Head::Constructor(
+            constructor.name.name.clone(),
+            usize::from(constructor.argument.is_some()),
+          )
+        })
+        .collect(),
+    ),
+    | Head::Literal(surface::Literal::Boolean(_)) => Some(
+      [true, false]
+        .into_iter()
+        .map(|value| Head::Literal(surface::Literal::Boolean(value)))
+        .collect(),
+    ),
+    | Head::Literal(surface::Literal::Unit) =>
+      Some(vec![Head::Literal(surface::Literal::Unit)]),
+    | Head::Nil | Head::Cons => Some(vec![Head::Nil, Head::Cons]),
+    | Head::Literal(_) => None,
+  }
+}
+
+/// A head of `typ` but none of `heads`, `None` when `heads` cover the whole
+/// type.
+fn missing_head(
+  context: &Context,
+  heads: &[Head],
+  typ: &types::Type,
+) -> Option<Head>
+{
+  let unused = |head: &Head| !heads.contains(head);
+  match heads.first()? {
+    | Head::Literal(surface::Literal::Integer(_)) => (0 ..)
+      .map(|value| Head::Literal(surface::Literal::Integer(value)))
+      .find(unused),
+    | Head::Literal(surface::Literal::Float(_)) => (0 ..)
+      .map(|value| Head::Literal(surface::Literal::Float(f64::from(value))))
+      .find(unused),
+    | Head::Literal(surface::Literal::String(_)) => (0 ..)
+      .map(|length| Head::Literal(surface::Literal::String("a".repeat(length))))
+      .find(unused),
+    | head => all_heads(context, head, typ)?
+      .into_iter()
+      .find(unused),
+  }
+}
+
+/// A type nothing is known about, which a value of any type has.
+fn unknown() -> types::Type
+{
+  types::Variable::Unnamed(0).into()
+}
+
+/// The types of the parts of a value of `typ` with `head`.
+fn part_types(
+  context: &Context,
+  head: &Head,
+  typ: &types::Type,
+) -> Vec<types::Type>
+{
+  match (head, typ) {
+    | (Head::Tuple(_), types::Type::Tuple(elements)) => elements.clone(),
+    | (Head::Constructor(name, arity), typ) => match context
+      .constructors(typ)
+      .and_then(|constructors| {
+        constructors
+          .into_iter()
+          .find(|constructor| &constructor.name.name == name)
+      }) {
+      | Some(constructor) => constructor
+        .argument
+        .into_iter()
+        .collect(),
+      | None => vec![unknown(); *arity],
+    },
+    | (Head::Cons, types::Type::List(element)) =>
+      vec![element.as_ref().clone(), typ.clone()],
+    | (head, _) => vec![unknown(); head.arity()],
+  }
+}
+
+/// The distinct heads of the first column of `rows`, in order.
+fn heads(rows: &[Vec<Shape>]) -> Vec<Head>
+{
+  let mut heads = vec![];
+  for row in rows {
+    if let Some(Shape::Head(head, _)) = row.first() {
+      if !heads.contains(head) {
+        heads.push(head.clone());
+      }
+    }
+  }
+  heads
+}
+
+/// The rows that can match a value with `head` first, with its parts in
+/// place of the first column.
+fn specialise(
+  rows: &[Vec<Shape>],
+  head: &Head,
+) -> Vec<Vec<Shape>>
+{
+  rows
+    .iter()
+    .filter_map(|row| {
+      let (first, rest) = row.split_first()?;
+      let parts = match first {
+        | Shape::Any => vec![Shape::Any; head.arity()],
+        | Shape::Head(other, parts) if other == head => parts.clone(),
+        | Shape::Head(..) => return None,
+      };
+      Some([parts, rest.to_vec()].concat())
+    })
+    .collect()
+}
+
+/// The rows that match any first value, without the first column.
+fn default(rows: &[Vec<Shape>]) -> Vec<Vec<Shape>>
+{
+  rows
+    .iter()
+    .filter(|row| row.first() == Some(&Shape::Any))
+    .map(|row| row[1 ..].to_vec())
+    .collect()
+}
+
+/// Whether some values of the shape of `vector` are not matched by `rows`,
+/// where the columns are of `types`.
+fn useful(
+  context: &Context,
+  rows: &[Vec<Shape>],
+  vector: &[Shape],
+  types: &[types::Type],
+) -> bool
+{
+  let (Some((first, rest)), Some((typ, rest_types))) =
+    (vector.split_first(), types.split_first())
+  else {
+    return rows.is_empty()
+  };
+  let specialised = |head: &Head| {
+    [part_types(context, head, typ), rest_types.to_vec()].concat()
+  };
+  match first {
+    | Shape::Head(head, parts) => useful(
+      context,
+      &specialise(rows, head),
+      &[parts.clone(), rest.to_vec()].concat(),
+      &specialised(head),
+    ),
+    | Shape::Any => {
+      let heads = heads(rows);
+      match heads.first() {
+        | Some(head) if missing_head(context, &heads, typ).is_none() =>
+          all_heads(context, head, typ)
+            .unwrap_or_default()
+            .iter()
+            .any(|head| {
+              useful(
+                context,
+                &specialise(rows, head),
+                &[vec![Shape::Any; head.arity()], rest.to_vec()].concat(),
+                &specialised(head),
+              )
+            }),
+        | _ => useful(context, &default(rows), rest, rest_types),
+      }
+    },
+  }
+}
+
+/// Values with parts of `types` that none of `rows` match, if any.
+fn missing(
+  context: &Context,
+  rows: &[Vec<Shape>],
+  types: &[types::Type],
+) -> Option<Vec<Shape>>
+{
+  let Some((typ, rest_types)) = types.split_first()
+  else {
+    return rows.is_empty().then(Vec::new)
+  };
+  let heads = heads(rows);
+  match (heads.first(), missing_head(context, &heads, typ)) {
+    | (Some(head), None) => all_heads(context, head, typ)
+      .unwrap_or_default()
+      .into_iter()
+      .find_map(|head| {
+        let arity = head.arity();
+        let types =
+          [part_types(context, &head, typ), rest_types.to_vec()].concat();
+        let mut values = missing(context, &specialise(rows, &head), &types)?;
+        let rest = values.split_off(arity);
+        Some([vec![Shape::Head(head, values)], rest].concat())
+      }),
+    | (_, head) => {
+      let rest = missing(context, &default(rows), rest_types)?;
+      let first = match head {
+        | Some(head) =>
+          Shape::Head(head.clone(), vec![Shape::Any; head.arity()]),
+        | None => Shape::Any,
+      };
+      Some([vec![first], rest].concat())
+    },
+  }
+}
+
+/// Checks the arms of a match, or the single pattern of a parameter, as
+/// a whole that `span` covers and which starts where the value they test is
+/// known from.
+fn check_arms<'a>(
+  context: &Context,
+  patterns: impl IntoIterator<Item = &'a surface::Pattern>,
+  span: surface::Span,
+) -> Vec<PatternWarning>
+{
+  let types = [context
+    .scrutinee(span)
+    .unwrap_or_else(unknown)];
+  let mut warnings = vec![];
+  let mut rows = vec![];
+  for pattern in patterns {
+    let row = vec![Shape::from(pattern)];
+    if !useful(context, &rows, &row, &types) {
+      warnings.push(PatternWarning::Unreachable(pattern.span()));
+    }
+    rows.push(row);
+  }
+  if let Some([example]) = missing(context, &rows, &types).as_deref() {
+    warnings.push(PatternWarning::NonExhaustive {
+      example: example.to_string(),
+      span,
+    });
+  }
+  warnings
+}
+
+impl TransformInto<Vec<PatternWarning>> for surface::Expression
+{
+  type Context<'a> = &'a Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Vec<PatternWarning>
+  {
+    match self {
+      | surface::Expression::Literal(..)
+      | surface::Expression::Identifier(_)
+      | surface::Expression::Qualified(_) => vec![],
+      | surface::Expression::Abstraction(abstraction) =>
+        abstraction.check_patterns(context),
+      | surface::Expression::Application(application) => application
+        .arguments
+        .iter()
+        .chain([&application.abstraction])
+        .flat_map(|expression| expression.check_patterns(context))
+        .collect(),
+      | surface::Expression::Conditional(conditional) => [
+        &conditional.condition,
+        &conditional.consequence,
+        &conditional.alternative,
+      ]
+      .into_iter()
+      .flat_map(|expression| expression.check_patterns(context))
+      .collect(),
+      | surface::Expression::Annotation(annotation) => annotation
+        .expression
+        .check_patterns(context),
+      | surface::Expression::Let(let_in) => let_in
+        .bindings
+        .iter()
+        .map(|binding| &binding.value)
+        .chain([&let_in.body])
+        .flat_map(|expression| expression.check_patterns(context))
+        .collect(),
+      | surface::Expression::LetRec(let_rec) => let_rec
+        .functions
+        .iter()
+        .flat_map(|function| {
+          function
+            .abstraction
+            .check_patterns(context)
+        })
+        .chain(let_rec.body.check_patterns(context))
+        .collect(),
+      | surface::Expression::Tuple(tuple) => tuple
+        .elements
+        .iter()
+        .flat_map(|element| element.check_patterns(context))
+        .collect(),
+      | surface::Expression::List(list) => list
+        .elements
+        .iter()
+        .flat_map(|element| element.check_patterns(context))
+        .collect(),
+      | surface::Expression::Projection(projection) =>
+        projection.tuple.check_patterns(context),
+      | surface::Expression::Match(pattern_match) =>
+        pattern_match.check_patterns(context),
+      | surface::Expression::Record(record) => record
+        .fields
+        .iter()
+        .flat_map(|field| field.value.check_patterns(context))
+        .collect(),
+      | surface::Expression::FieldAccess(access) =>
+        access.record.check_patterns(context),
+      | surface::Expression::Update(update) => update
+        .fields
+        .iter()
+        .map(|field| &field.value)
+        .chain([&update.record])
+        .flat_map(|expression| expression.check_patterns(context))
+        .collect(),
+      | surface::Expression::Extension(extension) => extension
+        .fields
+        .iter()
+        .map(|field| &field.value)
+        .chain([&extension.record])
+        .flat_map(|expression| expression.check_patterns(context))
+        .collect(),
+      | surface::Expression::Restriction(restriction) => restriction
+        .record
+        .check_patterns(context),
+    }
+  }
+}
+
+impl TransformInto<Vec<PatternWarning>> for surface::Abstraction
+{
+  type Context<'a> = &'a Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Vec<PatternWarning>
+  {
+    self
+      .parameters
+      .iter()
+      .flat_map(|parameter| {
+        check_arms(context, [&parameter.pattern], parameter.pattern.span())
+      })
+      .chain(self.body.check_patterns(context))
+      .collect()
+  }
+}
+
+impl TransformInto<Vec<PatternWarning>> for surface::Match
+{
+  type Context<'a> = &'a Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Vec<PatternWarning>
+  {
+    let mut warnings = self.scrutinee.check_patterns(context);
+    warnings.extend(check_arms(
+      context,
+      self.arms.iter().map(|arm| &arm.pattern),
+      self.span,
+    ));
+    for arm in self.arms.iter() {
+      warnings.extend(arm.body.check_patterns(context));
+    }
+    warnings
+  }
+}
+
+impl TransformInto<Vec<PatternWarning>> for surface::TopLevel
+{
+  type Context<'a> = &'a Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Vec<PatternWarning>
+  {
+    match self {
+      | surface::TopLevel::ValBinding(binding) =>
+        binding.value.check_patterns(context),
+      | surface::TopLevel::DefBinding(binding) => binding
+        .functions
+        .iter()
+        .flat_map(|function| {
+          function
+            .abstraction
+            .check_patterns(context)
+        })
+        .collect(),
+      | surface::TopLevel::Structure(structure) => structure
+        .declarations
+        .iter()
+        .flat_map(|declaration| declaration.check_patterns(context))
+        .collect(),
+      | surface::TopLevel::TypeDeclaration(_) | surface::TopLevel::Open(_) =>
+        vec![],
+    }
+  }
+}
+
+impl TransformInto<Vec<PatternWarning>> for surface::Program
+{
+  type Context<'a> = &'a Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Vec<PatternWarning>
+  {
+    self
+      .declarations
+      .iter()
+      .flat_map(|declaration| declaration.check_patterns(context))
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod spec
+{
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+  use crate::frontend::{
+    DeclarationParser,
+    Lexer,
+    WithBacktracking,
+  };
+  use crate::syntax::surface::transformations::infer_type::InferType;
+
+  const OPTION: &str = "type 'a option = None | Some of 'a ;\n";
+
+  /// The messages of the warnings about `input`, once it is well typed.
+  fn check(input: &str) -> Vec<String>
+  {
+    let program = Lexer::from_str(input)
+      .with_backtracking()
+      .expect_program()
+      .unwrap();
+    let mut context = Context::default();
+    let _: Vec<(surface::Identifier, types::Scheme)> = program
+      .infer_type(&mut context)
+      .unwrap();
+    program
+      .check_patterns(&context)
+      .iter()
+      .map(ToString::to_string)
+      .collect()
+  }
+
+  #[test]
+  fn exhaustive_matches_are_quiet()
+  {
+    assert_eq!(
+      check(&format!(
+        "{}val f = fun o -> match o with None -> 0 | Some 1 -> 1 | Some _ -> \
+         2 ;\n",
+        OPTION
+      )),
+      Vec::<String>::new()
+    );
+    assert_eq!(
+      check(
+        "val f = fun p -> match p with (true, _) -> 0 | (false, true) -> 1 | \
+         (_, false) -> 2 ;\n"
+      ),
+      Vec::<String>::new()
+    );
+  }
+
+  #[test]
+  fn missing_constructors_are_named()
+  {
+    assert_eq!(
+      check(&format!(
+        "{}val f = fun o -> match o with Some x -> x ;\n",
+        OPTION
+      )),
+      vec!["non-exhaustive patterns: `None` is not matched"]
+    );
+    assert_eq!(
+      check(&format!(
+        "{}val f = fun o -> match o with None -> 0 | Some None -> 1 ;\n",
+        OPTION
+      )),
+      vec!["non-exhaustive patterns: `Some (Some _)` is not matched"]
+    );
+  }
+
+  #[test]
+  fn matches_in_structures_are_checked()
+  {
+    assert_eq!(
+      check(&format!(
+        "structure A = struct {}val f = fun o -> match o with Some x -> x ; \
+         end ;\n",
+        OPTION
+      )),
+      vec!["non-exhaustive patterns: `None` is not matched"]
+    );
+  }
+
+  #[test]
+  fn constructors_are_those_of_the_matched_type()
+  {
+    assert_eq!(
+      check(
+        "type t = A | B ;\nstructure S = struct type u = A | C ; end ;\nval f \
+         = fun x -> match x with A -> 0 | B -> 1 ;\n"
+      ),
+      Vec::<String>::new()
+    );
+    assert_eq!(
+      check(
+        "type t = A | B ;\nval f = fun x -> match x with A -> 0 ;\ntype u = A \
+         ;\nval g = fun x -> match x with A -> 0 ;\n"
+      ),
+      vec!["non-exhaustive patterns: `B` is not matched"]
+    );
+  }
+
+  #[test]
+  fn missing_values_of_infinite_types_are_made_up()
+  {
+    assert_eq!(
+      check("val f = fun n -> match n with 0 -> 1 | 1 -> 1 ;\n"),
+      vec!["non-exhaustive patterns: `2` is not matched"]
+    );
+    assert_eq!(check("val f = fun s -> match s with `` -> 1 ;\n"), vec![
+      "non-exhaustive patterns: ``a`` is not matched"
+    ]);
+    assert_eq!(check("val f = fun s -> match s with `a` -> 1 ;\n"), vec![
+      "non-exhaustive patterns: `\"\"` is not matched"
+    ]);
+  }
+
+  #[test]
+  fn examples_combine_the_missing_parts()
+  {
+    assert_eq!(
+      check(
+        "val f = fun p -> match p with (true, _) -> 0 | (_, true) -> 1 ;\n"
+      ),
+      vec!["non-exhaustive patterns: `(false, false)` is not matched"]
+    );
+  }
+
+  #[test]
+  fn lists_are_empty_or_not()
+  {
+    assert_eq!(
+      check("val f = fun l -> match l with [] -> 0 | _ :: xs -> 1 ;\n"),
+      Vec::<String>::new()
+    );
+    assert_eq!(
+      check(
+        "val f = fun l -> match l with [] -> 0 | [x] -> x | [x, y] -> y ;\n"
+      ),
+      vec!["non-exhaustive patterns: `_ :: _ :: _ :: _` is not matched"]
+    );
+    assert_eq!(
+      check(
+        "val f = fun l -> match l with x :: _ -> x | [_] -> 0 | [] -> 1 ;\n"
+      ),
+      vec!["unreachable pattern"]
+    );
+  }
+
+  #[test]
+  fn arms_after_a_catch_all_are_unreachable()
+  {
+    assert_eq!(
+      check(&format!(
+        "{}val f = fun o -> match o with _ -> 0 | None -> 1 | Some (1, x) -> \
+         x ;\n",
+        OPTION
+      )),
+      vec!["unreachable pattern", "unreachable pattern"]
+    );
+    assert_eq!(
+      check(
+        "val f = fun b -> match b with true -> 0 | false -> 1 | _ -> 2 ;\n"
+      ),
+      vec!["unreachable pattern"]
+    );
+  }
+
+  #[test]
+  fn parameters_and_nested_matches_are_checked()
+  {
+    assert_eq!(
+      check(&format!(
+        "{}def get (Some x) = match x with 0 -> 0 | 0 -> 1 | _ -> 2 ;\n",
+        OPTION
+      )),
+      vec![
+        "non-exhaustive patterns: `None` is not matched",
+        "unreachable pattern",
+      ]
+    );
+  }
+}
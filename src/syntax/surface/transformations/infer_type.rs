@@ -1,6 +1,14 @@
-use core::panic;
+mod substitution;
+
 use std::collections::HashMap;
 
+use substitution::Substitution;
+use thiserror::Error;
+
+use crate::diagnostics::{
+  Diagnostic,
+  ToDiagnostic,
+};
 use crate::syntax::surface::{
   self,
   types,
@@ -10,53 +18,897 @@ use crate::transform_into::TransformInto;
 #[derive(Debug, Clone, Default)]
 pub struct Context
 {
-  stack: Vec<(surface::Identifier, types::Variable)>,
+  stack: Vec<(surface::Identifier, types::Scheme)>,
   constraints: Vec<types::Constraint>,
-  assumptions: HashMap<types::Variable, types::Type>,
-  free_name: usize,
+  substitution: Substitution,
+  /// The parameters and the constructors of each declared type by its
+  /// declaration, with the types of the arguments resolved. They stay after
+  /// the structure declaring them, for the checks of its patterns.
+  constructors:
+    HashMap<usize, (Vec<types::Variable>, Vec<surface::Constructor>)>,
+  structures: Vec<(surface::Identifier, Signature)>,
+  /// The types the variables named in annotations stand for, throughout the
+  /// top-level binding being inferred.
+  annotations: HashMap<types::Variable, types::Type>,
+  /// What each variable named in those annotations stands for, keyed by where
+  /// it is first named.
+  kinds: HashMap<types::Variable, types::Kind>,
+  /// The declared types in scope, a declaration shadows the earlier ones of
+  /// the same name.
+  types: Vec<types::Constant>,
+  /// The number of types declared so far, which numbers the next one.
+  declarations: usize,
+  /// The structures the declarations being inferred are nested in, the
+  /// outermost first.
+  path: Vec<surface::Identifier>,
+  /// The type of the value each match tests, and each pattern of a parameter,
+  /// by where the match or the pattern starts. The checks of patterns look
+  /// them up once the constraints are solved.
+  scrutinees: HashMap<surface::Location, types::Type>,
+}
+
+/// The names of the built-in types, in scope unless a declaration shadows
+/// them.
+pub const BUILT_IN_TYPES: [&str; 5] =
+  ["Int", "Float", "String", "Boolean", "Unit"];
+
+/// The schemes of the names a structure binds, the types it declares and the
+/// structures nested in it, in order.
+#[derive(Debug, Clone, Default)]
+struct Signature
+{
+  values: Vec<(surface::Identifier, types::Scheme)>,
+  types: Vec<types::Constant>,
+  structures: Vec<(surface::Identifier, Signature)>,
 }
 
 impl Context
 {
+  /// A context where every primitive is bound to its name, in the order of
+  /// `Primitive::ALL`.
+  pub fn with_primitives() -> Self
+  {
+    Self {
+      stack: surface::Primitive::ALL
+        .into_iter()
+        .map(|primitive| {
+          (surface::Identifier::new(primitive.name()), signature(primitive))
+        })
+        .collect(),
+      ..Self::default()
+    }
+  }
+
+  /// Every constructor of `typ` in order, if it is a declared type, with the
+  /// types of their arguments for the arguments of `typ`.
+  pub fn constructors(
+    &self,
+    typ: &types::Type,
+  ) -> Option<Vec<surface::Constructor>>
+  {
+    let types::Type::Concrete(
+      types::Constant {
+        declaration: Some(declaration),
+        ..
+      },
+      arguments,
+    ) = typ
+    else {
+      return None
+    };
+    let (parameters, constructors) = self.constructors.get(declaration)?;
+    let replacements = parameters
+      .iter()
+      .cloned()
+      .zip(arguments.iter().cloned())
+      .collect();
+    Some(
+      constructors
+        .iter()
+        .map(|constructor| surface::Constructor {
+          name: constructor.name.clone(),
+          argument: constructor
+            .argument
+            .as_ref()
+            .map(|argument| argument.substitute(&replacements)),
+        })
+        .collect(),
+    )
+  }
+
+  /// The type of the value the match or the parameter pattern `span` starts
+  /// tests, as far as the solved constraints tell.
+  pub fn scrutinee(
+    &self,
+    span: surface::Span,
+  ) -> Option<types::Type>
+  {
+    self
+      .scrutinees
+      .get(&span.start)
+      .and_then(|typ| typ.resolve(self).ok())
+  }
+
+  /// Forgets the types of the matches and parameter patterns inferred so far,
+  /// once their patterns are checked. Later input may start at the same
+  /// locations.
+  pub fn forget_scrutinees(&mut self)
+  {
+    self.scrutinees.clear();
+  }
+
   fn free_name(&mut self) -> types::Variable
   {
-    let free_name = self.free_name;
-    self.free_name += 1;
-    types::Variable::Unnamed(free_name)
+    self.substitution.free_name()
+  }
+
+  /// A row variable for further fields, other than `fields`.
+  fn row(
+    &mut self,
+    fields: impl IntoIterator<Item = surface::Identifier>,
+  ) -> types::Variable
+  {
+    let row = self.free_name();
+    self.substitution.lack(&row, fields);
+    row
   }
 
   fn lookup(
-    &self,
+    &mut self,
     name: &str,
   ) -> Option<types::Type>
   {
-    self
+    let scheme = self
       .stack
       .iter()
       .rev()
-      .find_map(|(binding, typ)| match binding.name == name {
-        | true => Some(typ.clone().into()),
+      .find_map(|(binding, scheme)| match binding.name == name {
+        | true => Some(scheme.clone()),
         | false => None,
+      })?;
+    Some(self.instantiate(&scheme))
+  }
+
+  /// The signature of the structure `path` names.
+  fn structure(
+    &self,
+    path: &[surface::Identifier],
+  ) -> Result<&Signature, TypeError>
+  {
+    fn find<'a>(
+      structures: &'a [(surface::Identifier, Signature)],
+      name: &surface::Identifier,
+    ) -> Result<&'a Signature, TypeError>
+    {
+      structures
+        .iter()
+        .rev()
+        .find(|(binding, _)| binding.name == name.name)
+        .map(|(_, signature)| signature)
+        .ok_or_else(|| TypeError::UnknownStructure(name.clone()))
+    }
+    let (first, nested) = path
+      .split_first()
+      .expect("a path names at least one structure");
+    nested
+      .iter()
+      .try_fold(find(&self.structures, first)?, |signature, name| {
+        find(&signature.structures, name)
+      })
+  }
+
+  /// A fresh instance of the member `name` of the structure `path` names.
+  fn member(
+    &mut self,
+    path: &[surface::Identifier],
+    name: &surface::Identifier,
+  ) -> Result<types::Type, TypeError>
+  {
+    let scheme = self
+      .structure(path)?
+      .values
+      .iter()
+      .rev()
+      .find(|(binding, _)| binding.name == name.name)
+      .map(|(_, scheme)| scheme.clone())
+      .ok_or_else(|| TypeError::UnboundName(name.clone()))?;
+    Ok(self.instantiate(&scheme))
+  }
+
+  /// Replaces the quantified variables of `scheme` by fresh ones, which lack
+  /// the same fields if they are rows.
+  fn instantiate(
+    &mut self,
+    scheme: &types::Scheme,
+  ) -> types::Type
+  {
+    let replacements = scheme
+      .variables
+      .iter()
+      .map(|variable| {
+        let lacks = self.substitution.lacks(variable);
+        (variable.clone(), self.row(lacks).into())
+      })
+      .collect();
+    scheme.typ.substitute(&replacements)
+  }
+
+  /// Resolves every name of a type within `typ` to the latest declaration of
+  /// it in scope, or in the structure it is qualified by, or else to the
+  /// built-in type of that name.
+  fn declared(
+    &self,
+    typ: &types::Type,
+  ) -> Result<types::Type, TypeError>
+  {
+    let declared = |types: &[types::Type]| {
+      types
+        .iter()
+        .map(|typ| self.declared(typ))
+        .collect::<Result<Vec<_>, _>>()
+    };
+    Ok(match typ {
+      | types::Type::Variable(_) => typ.clone(),
+      | types::Type::Concrete(constant, arguments) => {
+        let find = |types: &[types::Constant]| {
+          types
+            .iter()
+            .rev()
+            .find(|declared| declared.name.name == constant.name.name)
+            .cloned()
+        };
+        let declared_constant = match constant.path.is_empty() {
+          | true => find(&self.types).or_else(|| {
+            BUILT_IN_TYPES
+              .contains(&constant.name.name.as_str())
+              .then(|| constant.name.clone().into())
+          }),
+          | false => find(&self.structure(&constant.path)?.types),
+        }
+        .ok_or_else(|| TypeError::UnknownType(constant.clone()))?;
+        types::Type::Concrete(declared_constant, declared(arguments)?)
+      },
+      | types::Type::Abstraction(abstraction) => types::Type::abstraction(
+        self.declared(&abstraction.parameter_type)?,
+        self.declared(&abstraction.return_type)?,
+      ),
+      | types::Type::Tuple(elements) => types::Type::Tuple(declared(elements)?),
+      | types::Type::List(element) =>
+        types::Type::list(self.declared(element)?),
+      | types::Type::Record(fields, rest) => types::Type::record(
+        fields
+          .iter()
+          .map(|(name, typ)| Ok((name.clone(), self.declared(typ)?)))
+          .collect::<Result<Vec<_>, _>>()?,
+        rest.clone(),
+      ),
+    })
+  }
+
+  /// Turns a written type into one to infer with. A type variable of
+  /// `annotation` becomes a fresh variable where the top-level binding first
+  /// names it, and stands for that same variable everywhere else in it.
+  fn annotated(
+    &mut self,
+    annotation: &types::Type,
+  ) -> Result<types::Type, TypeError>
+  {
+    let annotation = self.declared(annotation)?;
+    for (variable, kind) in annotation.kinds() {
+      match self.kinds.get_key_value(&variable) {
+        | Some((first, first_kind)) if *first_kind != kind =>
+          return Err(TypeError::KindMismatch {
+            variable: Box::new(variable.clone()),
+            kind,
+            span: named_at(&variable),
+            first: named_at(first),
+          }),
+        | Some(_) => (),
+        | None => {
+          self.kinds.insert(variable, kind);
+        },
+      }
+    }
+    for variable in annotation.variables() {
+      let substitution = &mut self.substitution;
+      self
+        .annotations
+        .entry(variable)
+        .or_insert_with(|| substitution.free_name().into());
+    }
+    Ok(annotation.substitute(&self.annotations))
+  }
+
+  /// Runs `inference` on a top-level binding, which the variables named in
+  /// its annotations are scoped to.
+  pub fn binding<T>(
+    &mut self,
+    inference: impl FnOnce(&mut Self) -> T,
+  ) -> T
+  {
+    let enclosing = std::mem::take(&mut self.annotations);
+    let enclosing_kinds = std::mem::take(&mut self.kinds);
+    let result = inference(self);
+    self.annotations = enclosing;
+    self.kinds = enclosing_kinds;
+    result
+  }
+
+  /// Quantifies `typ` over the variables that are not bound anywhere in the
+  /// surrounding scope, expects the constraints to be solved.
+  pub fn generalise(
+    &self,
+    typ: &types::Type,
+  ) -> Result<types::Scheme, TypeError>
+  {
+    let typ = typ.resolve(self)?;
+    let mut bound = vec![];
+    for (_, scheme) in &self.stack {
+      bound.extend(
+        scheme
+          .typ
+          .resolve(self)?
+          .variables()
+          .into_iter()
+          .filter(|variable| !scheme.variables.contains(variable)),
+      );
+    }
+    Ok(types::Scheme {
+      variables: typ
+        .variables()
+        .into_iter()
+        .filter(|variable| !bound.contains(variable))
+        .collect(),
+      typ,
+    })
+  }
+
+  /// Brings `binding` into scope with type `scheme` for everything inferred
+  /// afterwards.
+  pub fn bind(
+    &mut self,
+    binding: surface::Identifier,
+    scheme: types::Scheme,
+  )
+  {
+    self.stack.push((binding, scheme));
+  }
+
+  /// Brings the names of `pattern` into scope, where `typ` is the type of the
+  /// value it matches.
+  fn bind_pattern(
+    &mut self,
+    pattern: &surface::Pattern,
+    typ: types::Type,
+  ) -> Result<(), TypeError>
+  {
+    match pattern {
+      | surface::Pattern::Identifier(identifier) => self
+        .stack
+        .push((identifier.clone(), typ.into())),
+      | surface::Pattern::Wildcard(_) => (),
+      | surface::Pattern::Literal(literal, span) => {
+        let left = literal.infer_type(self)?;
+        self.constraints.push(
+          types::Equivalent {
+            left,
+            right: typ,
+            span: *span,
+          }
+          .into(),
+        );
+      },
+      | surface::Pattern::Tuple(elements, span) => {
+        let element_types = elements
+          .iter()
+          .map(|_| self.free_name().into())
+          .collect::<Vec<types::Type>>();
+        self.constraints.push(
+          types::Equivalent {
+            left: types::Type::Tuple(element_types.clone()),
+            right: typ,
+            span: *span,
+          }
+          .into(),
+        );
+        for (element, typ) in elements.iter().zip(element_types) {
+          self.bind_pattern(element, typ)?;
+        }
+      },
+      | surface::Pattern::Constructor(path, name, argument) => {
+        let constructor = match path.is_empty() {
+          | true => self
+            .lookup(&name.name)
+            .ok_or_else(|| TypeError::UnboundName(name.clone()))?,
+          | false => self.member(path, name)?,
+        };
+        let (left, argument) = match (constructor, argument) {
+          | (types::Type::Abstraction(abstraction), Some(argument)) => {
+            let types::Abstraction {
+              parameter_type,
+              return_type,
+            } = *abstraction;
+            (return_type, Some((argument, parameter_type)))
+          },
+          | (types::Type::Abstraction(_), None) =>
+            return Err(TypeError::MissingArgument(name.clone())),
+          | (_, Some(_)) =>
+            return Err(TypeError::UnexpectedArgument(name.clone())),
+          | (constructor, None) => (constructor, None),
+        };
+        self.constraints.push(
+          types::Equivalent {
+            left,
+            right: typ,
+            span: name.span,
+          }
+          .into(),
+        );
+        if let Some((argument, typ)) = argument {
+          self.bind_pattern(argument, typ)?;
+        }
+      },
+      | surface::Pattern::List(elements, span) => {
+        let element: types::Type = self.free_name().into();
+        self.constraints.push(
+          types::Equivalent {
+            left: types::Type::list(element.clone()),
+            right: typ,
+            span: *span,
+          }
+          .into(),
+        );
+        for pattern in elements {
+          self.bind_pattern(pattern, element.clone())?;
+        }
+      },
+      | surface::Pattern::Cons(head, tail) => {
+        let element: types::Type = self.free_name().into();
+        let list = types::Type::list(element.clone());
+        self.constraints.push(
+          types::Equivalent {
+            left: list.clone(),
+            right: typ,
+            span: pattern.span(),
+          }
+          .into(),
+        );
+        self.bind_pattern(head, element)?;
+        self.bind_pattern(tail, list)?;
+      },
+    }
+    Ok(())
+  }
+
+  /// The types of the values of `fields`, which must have distinct names.
+  fn fields(
+    &mut self,
+    fields: &[surface::Field],
+  ) -> Result<Vec<(surface::Identifier, types::Type)>, TypeError>
+  {
+    let mut types = Vec::<(surface::Identifier, types::Type)>::new();
+    for field in fields {
+      if let Some((first, _)) = types
+        .iter()
+        .find(|(name, _)| name.name == field.name.name)
+      {
+        return Err(TypeError::DuplicateField {
+          field: field.name.clone(),
+          first: first.span,
+        })
+      }
+      types.push((field.name.clone(), field.value.infer_type(self)?));
+    }
+    Ok(types)
+  }
+
+  /// Infers the schemes of a group of mutually recursive `functions`, which
+  /// are monomorphic within the group and generalised afterwards.
+  fn recursive(
+    &mut self,
+    functions: &[surface::Function],
+  ) -> Result<Vec<(surface::Identifier, types::Scheme)>, TypeError>
+  {
+    let assumed = functions
+      .iter()
+      .map(|function| (function.name.clone(), self.free_name().into()))
+      .collect::<Vec<(surface::Identifier, types::Type)>>();
+    self.stack.extend(
+      assumed
+        .iter()
+        .map(|(name, typ)| (name.clone(), typ.clone().into())),
+    );
+
+    let inferred = functions
+      .iter()
+      .zip(assumed.iter())
+      .try_for_each(|(function, (_, typ))| {
+        let actual = function.abstraction.infer_type(self)?;
+        self.constraints.push(
+          types::Equivalent {
+            left: typ.clone(),
+            right: actual,
+            span: function.abstraction.span,
+          }
+          .into(),
+        );
+        Ok(())
       })
+      .and_then(|()| self.solve_constraints());
+    self
+      .stack
+      .truncate(self.stack.len() - functions.len());
+    inferred?;
+
+    assumed
+      .into_iter()
+      .map(|(name, typ)| Ok((name, self.generalise(&typ)?)))
+      .collect()
   }
 
-  fn solve_constraints(&mut self)
+  pub fn solve_constraints(&mut self) -> Result<(), TypeError>
   {
-    while let Some(constraint) = dbg!(self.constraints.pop()) {
-      dbg!(&self);
+    while let Some(constraint) = self.constraints.pop() {
       match constraint {
-        | types::Constraint::Equivalent(equivalent) => equivalent.solve(self),
+        | types::Constraint::Equivalent(equivalent) =>
+          equivalent.solve(self)?,
+      }
+    }
+    Ok(())
+  }
+}
+
+fn signature(primitive: surface::Primitive) -> types::Scheme
+{
+  let concrete =
+    |name: &str| -> types::Type { surface::Identifier::new(name).into() };
+  let binary = |operand: types::Type, result: types::Type| {
+    types::Type::abstraction(
+      operand.clone(),
+      types::Type::abstraction(operand, result),
+    )
+  };
+  match primitive {
+    | surface::Primitive::Add
+    | surface::Primitive::Subtract
+    | surface::Primitive::Multiply
+    | surface::Primitive::Divide
+    | surface::Primitive::Modulo =>
+      binary(concrete("Int"), concrete("Int")).into(),
+    | surface::Primitive::AddFloat
+    | surface::Primitive::SubtractFloat
+    | surface::Primitive::MultiplyFloat
+    | surface::Primitive::DivideFloat =>
+      binary(concrete("Float"), concrete("Float")).into(),
+    | surface::Primitive::Negate =>
+      types::Type::abstraction(concrete("Int"), concrete("Int")).into(),
+    | surface::Primitive::NegateFloat =>
+      types::Type::abstraction(concrete("Float"), concrete("Float")).into(),
+    | surface::Primitive::Float =>
+      types::Type::abstraction(concrete("Int"), concrete("Float")).into(),
+    | surface::Primitive::Truncate =>
+      types::Type::abstraction(concrete("Float"), concrete("Int")).into(),
+    | surface::Primitive::Equal
+    | surface::Primitive::Less
+    | surface::Primitive::LessOrEqual
+    | surface::Primitive::Greater
+    | surface::Primitive::GreaterOrEqual => {
+      // named, so that it can't clash with the variables being inferred
+      let operand = types::Variable::Named(surface::Identifier::new("a"));
+      types::Scheme {
+        variables: vec![operand.clone()],
+        typ: binary(operand.into(), concrete("Boolean")),
+      }
+    },
+    | surface::Primitive::Concatenate =>
+      binary(concrete("String"), concrete("String")).into(),
+    | surface::Primitive::Not =>
+      types::Type::abstraction(concrete("Boolean"), concrete("Boolean")).into(),
+    | surface::Primitive::And | surface::Primitive::Or =>
+      binary(concrete("Boolean"), concrete("Boolean")).into(),
+    | surface::Primitive::Cons => {
+      let element = types::Variable::Named(surface::Identifier::new("a"));
+      let list = types::Type::list(element.clone().into());
+      types::Scheme {
+        variables: vec![element.clone()],
+        typ: types::Type::abstraction(
+          element.into(),
+          types::Type::abstraction(list.clone(), list),
+        ),
+      }
+    },
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum TypeError
+{
+  #[error("mismatched types: {}", mismatch(.expected, .actual))]
+  Mismatch
+  {
+    expected: Box<types::Type>,
+    actual: Box<types::Type>,
+    span: surface::Span,
+  },
+  #[error("`{}` is not a function", printed(.typ))]
+  NotAFunction
+  {
+    typ: Box<types::Type>,
+    span: surface::Span,
+  },
+  #[error("cannot construct the infinite type `{}`", infinite(.variable, .typ))]
+  InfiniteType
+  {
+    variable: Box<types::Variable>,
+    typ: Box<types::Type>,
+    span: surface::Span,
+  },
+  #[error("unbound variable `{}`", .0.name)]
+  UnboundName(surface::Identifier),
+  #[error("unknown structure `{}`", .0.name)]
+  UnknownStructure(surface::Identifier),
+  #[error("unknown type `{0}`")]
+  UnknownType(types::Constant),
+  #[error("unbound type variable `'{}`", .0.name)]
+  UnboundTypeVariable(surface::Identifier),
+  #[error(
+    "the type variable `{variable}` stands for both a type and the fields of \
+     a record"
+  )]
+  KindMismatch
+  {
+    variable: Box<types::Variable>,
+    /// What the variable stands for at `span`, it stands for the other at
+    /// `first`.
+    kind: types::Kind,
+    span: surface::Span,
+    first: surface::Span,
+  },
+  #[error("`{}` cannot stand for the fields of a record", printed(.typ))]
+  NotARow
+  {
+    typ: Box<types::Type>,
+    span: surface::Span,
+  },
+  #[error("`{}` is not a tuple", printed(.typ))]
+  NotATuple
+  {
+    typ: Box<types::Type>,
+    span: surface::Span,
+  },
+  #[error("the tuple `{}` has no element {index}", printed(.typ))]
+  NoElement
+  {
+    typ: Box<types::Type>,
+    index: usize,
+    span: surface::Span,
+  },
+  #[error("the type `{}` must be known to be a tuple", printed(.typ))]
+  UnknownTuple
+  {
+    typ: Box<types::Type>,
+    span: surface::Span,
+  },
+  #[error("the record `{}` has no field `{}`", printed(.typ), .field.name)]
+  NoField
+  {
+    typ: Box<types::Type>,
+    field: surface::Identifier,
+  },
+  #[error(
+    "the record `{}` already has a field `{}`",
+    printed(.typ),
+    .field.name
+  )]
+  FieldPresent
+  {
+    typ: Box<types::Type>,
+    field: surface::Identifier,
+  },
+  #[error("the field `{}` is given more than once", .field.name)]
+  DuplicateField
+  {
+    field: surface::Identifier,
+    first: surface::Span,
+  },
+  #[error("the type parameter `'{}` is declared more than once", .parameter.name)]
+  DuplicateParameter
+  {
+    parameter: surface::Identifier,
+    first: surface::Span,
+  },
+  #[error("the constructor `{}` is declared more than once", .constructor.name)]
+  DuplicateConstructor
+  {
+    constructor: surface::Identifier,
+    first: surface::Span,
+  },
+  #[error("the constructor `{}` expects an argument", .0.name)]
+  MissingArgument(surface::Identifier),
+  #[error("the constructor `{}` takes no argument", .0.name)]
+  UnexpectedArgument(surface::Identifier),
+}
+
+impl ToDiagnostic for TypeError
+{
+  fn to_diagnostic(&self) -> Diagnostic
+  {
+    let diagnostic = Diagnostic::error(self.to_string());
+    match self {
+      | TypeError::Mismatch {
+        expected,
+        actual,
+        span,
+      } => {
+        let (expected, _) = distinguished(expected, actual);
+        diagnostic.with_primary(*span, format!("expected `{}` here", expected))
+      },
+      | TypeError::NotAFunction {
+        span,
+        ..
+      } => diagnostic.with_primary(*span, "cannot be applied to an argument"),
+      | TypeError::InfiniteType {
+        span,
+        ..
+      } =>
+        diagnostic.with_primary(*span, "the type would have to contain itself"),
+      | TypeError::UnboundName(identifier) =>
+        diagnostic.with_primary(identifier.span, "not found in this scope"),
+      | TypeError::UnknownStructure(identifier) => diagnostic
+        .with_primary(identifier.span, "no structure of this name in scope"),
+      | TypeError::UnknownType(constant) => diagnostic
+        .with_primary(constant.name.span, "no type of this name in scope"),
+      | TypeError::UnboundTypeVariable(identifier) => diagnostic
+        .with_primary(identifier.span, "not a parameter of the declared type"),
+      | TypeError::KindMismatch {
+        kind,
+        span,
+        first,
+        ..
+      } => {
+        let other = match kind {
+          | types::Kind::Type => types::Kind::Row,
+          | types::Kind::Row => types::Kind::Type,
+        };
+        diagnostic
+          .with_primary(*span, format!("stands for {} here", kind))
+          .with_secondary(*first, format!("stands for {} here", other))
+      },
+      | TypeError::NotARow {
+        span,
+        ..
+      } => diagnostic.with_primary(*span, "expected the fields of a record"),
+      | TypeError::NotATuple {
+        span,
+        ..
+      } => diagnostic.with_primary(*span, "cannot be projected"),
+      | TypeError::NoElement {
+        span,
+        ..
+      } => diagnostic.with_primary(*span, "index out of bounds"),
+      | TypeError::UnknownTuple {
+        span,
+        ..
+      } => diagnostic
+        .with_primary(*span, "the size of this tuple is not known here")
+        .with_note("annotate it with a tuple type such as `Int * Int`"),
+      | TypeError::NoField {
+        field,
+        ..
+      } => diagnostic.with_primary(field.span, "unknown field"),
+      | TypeError::FieldPresent {
+        field,
+        ..
+      } => diagnostic
+        .with_primary(field.span, "would be given twice")
+        .with_note("use `{ r with ... }` to change the value of a field"),
+      | TypeError::DuplicateField {
+        field,
+        first,
+      } => diagnostic
+        .with_primary(field.span, "already given")
+        .with_secondary(*first, "first given here"),
+      | TypeError::DuplicateParameter {
+        parameter: name,
+        first,
       }
+      | TypeError::DuplicateConstructor {
+        constructor: name,
+        first,
+      } => diagnostic
+        .with_primary(name.span, "declared again here")
+        .with_secondary(*first, "first declared here"),
+      | TypeError::MissingArgument(identifier) => diagnostic
+        .with_primary(identifier.span, "add a pattern for its argument"),
+      | TypeError::UnexpectedArgument(identifier) =>
+        diagnostic.with_primary(identifier.span, "matches a value on its own"),
+    }
+  }
+}
+
+/// `typ` as it is printed in an error, with its variables named `'a`, `'b`
+/// and so on rather than by the numbers inference gave them.
+fn printed(typ: &types::Type) -> types::Type
+{
+  typ.substitute(&types::lettered([typ]))
+}
+
+fn infinite(
+  variable: &types::Variable,
+  typ: &types::Type,
+) -> String
+{
+  let variable = types::Type::Variable(variable.clone());
+  let replacements = types::lettered([&variable, typ]);
+  format!(
+    "{} = {}",
+    variable.substitute(&replacements),
+    typ.substitute(&replacements)
+  )
+}
+
+/// `expected` and `actual` as they are printed, with their variables named
+/// alike in both. The types they name are numbered by their declaration if
+/// they would print the same otherwise.
+fn distinguished(
+  expected: &types::Type,
+  actual: &types::Type,
+) -> (types::Type, types::Type)
+{
+  let replacements = types::lettered([expected, actual]);
+  let expected = expected.substitute(&replacements);
+  let actual = actual.substitute(&replacements);
+  match expected.to_string() == actual.to_string() {
+    | true => (expected.numbered(), actual.numbered()),
+    | false => (expected, actual),
+  }
+}
+
+fn mismatch(
+  expected: &types::Type,
+  actual: &types::Type,
+) -> String
+{
+  let (expected, actual) = distinguished(expected, actual);
+  format!("expected `{}`, found `{}`", expected, actual)
+}
+
+/// The first of `names` that repeats an earlier one, along with where the
+/// earlier one is.
+fn repeated<'a>(
+  names: impl IntoIterator<Item = &'a surface::Identifier>
+) -> Option<(&'a surface::Identifier, surface::Span)>
+{
+  let mut earlier = Vec::<&surface::Identifier>::new();
+  for name in names {
+    if let Some(first) = earlier
+      .iter()
+      .find(|first| first.name == name.name)
+    {
+      return Some((name, first.span))
     }
+    earlier.push(name);
+  }
+  None
+}
+
+/// Where a variable of a written type is named.
+fn named_at(variable: &types::Variable) -> surface::Span
+{
+  match variable {
+    | types::Variable::Named(identifier) => identifier.span,
+    | types::Variable::Unnamed(_) => surface::Span::default(),
   }
 }
 
 pub trait Resolve
+where
+  Self: Sized,
 {
   fn resolve(
     &self,
     context: &Context,
-  ) -> Self;
+  ) -> Result<Self, TypeError>;
 }
 
 impl Resolve for types::Type
@@ -64,23 +916,9 @@ impl Resolve for types::Type
   fn resolve(
     &self,
     context: &Context,
-  ) -> Self
+  ) -> Result<Self, TypeError>
   {
-    match self {
-      | types::Type::Abstraction(abstraction) => types::Abstraction {
-        parameter_type: abstraction
-          .parameter_type
-          .resolve(context),
-        return_type: abstraction.return_type.resolve(context),
-      }
-      .into(),
-      | types::Type::Variable(variable) =>
-        match context.assumptions.get(variable) {
-          | Some(resolved) => resolved.resolve(context),
-          | None => self.clone(),
-        },
-      | concrete @ types::Type::Concrete(_) => concrete.clone(),
-    }
+    context.substitution.resolve(self)
   }
 }
 
@@ -89,252 +927,898 @@ pub trait Solve
   fn solve(
     &self,
     context: &mut Context,
-  );
+  ) -> Result<(), TypeError>;
 }
 
+/// Constraints are oriented: `left` is the type the context expects and
+/// `right` the type actually found there.
 impl Solve for types::Equivalent
 {
   fn solve(
     &self,
     context: &mut Context,
-  )
+  ) -> Result<(), TypeError>
   {
-    match self {
-      | types::Equivalent {
-        left: types::Type::Concrete(left),
-        right: types::Type::Concrete(right),
-      } => match () {
-        | _ if left == right => {
-          panic!("Type mismatch: {} != {}", left.name, right.name);
-        },
-        | _ => (),
-      },
-      | types::Equivalent {
-        left: types::Type::Abstraction(_),
-        right: types::Type::Concrete(right),
-      } => {
-        panic!("Type mismatch: {} is not a function", right.name);
-      },
-      | types::Equivalent {
-        left: types::Type::Concrete(left),
-        right: types::Type::Abstraction(_),
-      } => {
-        panic!("Type mismatch: {} is not a function", left.name);
-      },
-      | types::Equivalent {
-        left: types::Type::Abstraction(left),
-        right: types::Type::Abstraction(right),
-      } => context.constraints.extend([
-        types::Equivalent {
-          left: left.parameter_type.clone(),
-          right: right.parameter_type.clone(),
-        }
-        .into(),
-        types::Equivalent {
-          left: left.return_type.clone(),
-          right: right.return_type.clone(),
-        }
-        .into(),
-      ]),
-      | types::Equivalent {
-        left: types::Type::Variable(left),
-        right,
-      } => match dbg!(context.assumptions.get(left)) {
-        | Some(left) => {
-          context.constraints.push(
-            types::Equivalent {
-              left: left.clone(),
-              right: right.clone(),
-            }
-            .into(),
-          );
-        },
-        | None => {
-          debug_assert_eq!(
-            context
-              .assumptions
-              .insert(left.clone(), right.clone()),
-            None
-          );
-        },
-      },
-      | types::Equivalent {
-        right: right @ types::Type::Variable(_),
-        left,
-      } => context.constraints.push(
-        types::Equivalent {
-          left: right.clone(),
-          right: left.clone(),
-        }
-        .into(),
-      ),
-    }
+    context
+      .substitution
+      .unify(&self.left, &self.right, self.span)
   }
 }
 
-pub trait InferType<'a>
+pub trait InferType<'a, Representation>
 {
   fn infer_type(
     &self,
     context: &'a mut Context,
-  ) -> types::Type;
+  ) -> Result<Representation, TypeError>;
 }
 
-impl<'a, Representation> InferType<'a> for Representation
+impl<'a, SourceRepresentation, TargetRepresentation>
+  InferType<'a, TargetRepresentation> for SourceRepresentation
 where
-  Representation: TransformInto<types::Type, Context<'a> = &'a mut Context>,
+  SourceRepresentation: TransformInto<
+    Result<TargetRepresentation, TypeError>,
+    Context<'a> = &'a mut Context,
+  >,
 {
   fn infer_type(
     &self,
     context: &'a mut Context,
-  ) -> types::Type
+  ) -> Result<TargetRepresentation, TypeError>
   {
     self.transform(context)
   }
 }
 
-impl TransformInto<types::Type> for surface::Expression
+impl TransformInto<Result<types::Type, TypeError>> for surface::Expression
 {
   type Context<'a> = &'a mut Context;
 
   fn transform(
     &self,
     context: Self::Context<'_>,
-  ) -> types::Type
+  ) -> Result<types::Type, TypeError>
   {
     match self {
-      | surface::Expression::Literal(literal) => literal.infer_type(context),
+      | surface::Expression::Literal(literal, _) => literal.infer_type(context),
       | surface::Expression::Identifier(identifier) =>
         identifier.infer_type(context),
+      | surface::Expression::Qualified(qualified) =>
+        qualified.infer_type(context),
       | surface::Expression::Abstraction(abstraction) =>
         abstraction.infer_type(context),
       | surface::Expression::Application(application) =>
         application.infer_type(context),
+      | surface::Expression::Conditional(conditional) =>
+        conditional.infer_type(context),
+      | surface::Expression::Annotation(annotation) =>
+        annotation.infer_type(context),
+      | surface::Expression::Let(let_in) => let_in.infer_type(context),
+      | surface::Expression::LetRec(let_rec) => let_rec.infer_type(context),
+      | surface::Expression::Tuple(tuple) => tuple.infer_type(context),
+      | surface::Expression::List(list) => list.infer_type(context),
+      | surface::Expression::Projection(projection) =>
+        projection.infer_type(context),
+      | surface::Expression::Match(pattern_match) =>
+        pattern_match.infer_type(context),
+      | surface::Expression::Record(record) => record.infer_type(context),
+      | surface::Expression::FieldAccess(access) => access.infer_type(context),
+      | surface::Expression::Update(update) => update.infer_type(context),
+      | surface::Expression::Extension(extension) =>
+        extension.infer_type(context),
+      | surface::Expression::Restriction(restriction) =>
+        restriction.infer_type(context),
     }
   }
 }
 
-impl TransformInto<types::Type> for surface::Application
+impl TransformInto<Result<types::Type, TypeError>> for surface::Application
 {
   type Context<'a> = &'a mut Context;
 
   fn transform(
     &self,
     context: Self::Context<'_>,
-  ) -> types::Type
+  ) -> Result<types::Type, TypeError>
   {
     let arguments = self
       .arguments
       .iter()
-      .map(|argument| argument.infer_type(context))
-      .collect::<Vec<_>>();
+      .map(|argument| Ok((argument.infer_type(context)?, argument.span())))
+      .collect::<Result<Vec<_>, _>>()?;
 
-    let mut actual_abstraction_type = self.abstraction.infer_type(context);
-    for argument_type in arguments {
+    let mut actual_abstraction_type = self.abstraction.infer_type(context)?;
+    for (argument_type, argument_span) in arguments {
       let return_type: types::Type = context.free_name().into();
       let assumed_abstraction_type =
         types::Type::abstraction(argument_type, return_type.clone());
       context.constraints.push(
         types::Equivalent {
-          left: actual_abstraction_type,
-          right: assumed_abstraction_type,
+          left: assumed_abstraction_type,
+          right: actual_abstraction_type,
+          span: self
+            .abstraction
+            .span()
+            .merge(argument_span),
         }
         .into(),
       );
       actual_abstraction_type = return_type;
     }
-    actual_abstraction_type
+    Ok(actual_abstraction_type)
   }
 }
 
-impl TransformInto<types::Type> for surface::Abstraction
+impl TransformInto<Result<types::Type, TypeError>> for surface::Abstraction
 {
   type Context<'a> = &'a mut Context;
 
   fn transform(
     &self,
     context: Self::Context<'_>,
-  ) -> types::Type
+  ) -> Result<types::Type, TypeError>
   {
     let parameters = self
       .parameters
       .iter()
-      .map(|parameter| (parameter.clone(), context.free_name()))
-      .collect::<Vec<_>>();
+      .map(|parameter| match &parameter.annotation {
+        | Some(annotation) => context.annotated(annotation),
+        | None => Ok(context.free_name().into()),
+      })
+      .collect::<Result<Vec<types::Type>, _>>()?;
 
-    context
-      .stack
-      .extend(parameters.iter().cloned());
+    for (parameter, typ) in self
+      .parameters
+      .iter()
+      .zip(parameters.iter())
+    {
+      context
+        .scrutinees
+        .insert(parameter.pattern.span().start, typ.clone());
+    }
+    let depth = context.stack.len();
+    let body = self
+      .parameters
+      .iter()
+      .zip(parameters.iter())
+      .try_for_each(|(parameter, typ)| {
+        context.bind_pattern(&parameter.pattern, typ.clone())
+      })
+      .and_then(|()| self.body.infer_type(context));
+    context.stack.truncate(depth);
 
-    let mut return_type = self.body.infer_type(context);
-    for (_, parameter_type) in parameters.iter().rev() {
+    let mut return_type = body?;
+    for parameter_type in parameters.iter().rev() {
       return_type =
-        types::Type::abstraction(parameter_type.clone().into(), return_type);
-      context.stack.pop();
+        types::Type::abstraction(parameter_type.clone(), return_type);
     }
-    return_type
+    Ok(return_type)
   }
 }
 
-impl TransformInto<types::Type> for surface::Identifier
+impl TransformInto<Result<types::Type, TypeError>> for surface::Match
 {
   type Context<'a> = &'a mut Context;
 
   fn transform(
     &self,
     context: Self::Context<'_>,
-  ) -> types::Type
+  ) -> Result<types::Type, TypeError>
   {
+    let scrutinee = self.scrutinee.infer_type(context)?;
     context
-      .lookup(self.name.as_str())
-      .unwrap_or_else(|| context.free_name().into())
+      .scrutinees
+      .insert(self.span.start, scrutinee.clone());
+    let result: types::Type = context.free_name().into();
+    for arm in self.arms.iter() {
+      let depth = context.stack.len();
+      let body = context
+        .bind_pattern(&arm.pattern, scrutinee.clone())
+        .and_then(|()| arm.body.infer_type(context));
+      context.stack.truncate(depth);
+      context.constraints.push(
+        types::Equivalent {
+          left: result.clone(),
+          right: body?,
+          span: arm.body.span(),
+        }
+        .into(),
+      );
+    }
+    Ok(result)
   }
 }
 
-impl TransformInto<types::Type> for surface::Literal
+impl TransformInto<Result<types::Type, TypeError>> for surface::Conditional
 {
   type Context<'a> = &'a mut Context;
 
   fn transform(
     &self,
-    _context: Self::Context<'_>,
-  ) -> types::Type
+    context: Self::Context<'_>,
+  ) -> Result<types::Type, TypeError>
   {
-    match self {
-      | surface::Literal::String(_) =>
-        surface::Identifier::new("String").into(),
-      | surface::Literal::Numeric(_) =>
-        surface::Identifier::new("Numeric").into(),
-      | surface::Literal::Boolean(_) =>
-        surface::Identifier::new("Boolean").into(),
-    }
+    let condition = self.condition.infer_type(context)?;
+    let consequence = self.consequence.infer_type(context)?;
+    let alternative = self.alternative.infer_type(context)?;
+    context.constraints.push(
+      types::Equivalent {
+        left: surface::Identifier::new("Boolean").into(),
+        right: condition,
+        span: self.condition.span(),
+      }
+      .into(),
+    );
+    context.constraints.push(
+      types::Equivalent {
+        left: consequence.clone(),
+        right: alternative,
+        span: self.alternative.span(),
+      }
+      .into(),
+    );
+    Ok(consequence)
   }
 }
 
-#[cfg(test)]
-mod spec
+impl TransformInto<Result<types::Type, TypeError>> for surface::Annotation
 {
-  use super::*;
+  type Context<'a> = &'a mut Context;
 
-  macro_rules! assert_type {
-    ($name:ident; $input:literal resolves to $expected:expr) => {
-      #[test]
-      fn $name()
-      {
-        use crate::frontend::{
-          ExpressionParser,
-          Lexer,
-          WithBacktracking,
-        };
-        let mut lexer = Lexer::from_str($input).with_backtracking();
-        let expression = lexer.expect_expression().unwrap();
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<types::Type, TypeError>
+  {
+    let typ = self.expression.infer_type(context)?;
+    let annotation = context.annotated(&self.annotation)?;
+    context.constraints.push(
+      types::Equivalent {
+        left: annotation.clone(),
+        right: typ,
+        span: self.expression.span(),
+      }
+      .into(),
+    );
+    Ok(annotation)
+  }
+}
+
+/// Every value is generalised before the body is inferred, so a binding can
+/// be used at different types within the body.
+impl TransformInto<Result<types::Type, TypeError>> for surface::Let
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<types::Type, TypeError>
+  {
+    let values = self
+      .bindings
+      .iter()
+      .map(|binding| binding.value.infer_type(context))
+      .collect::<Result<Vec<types::Type>, _>>()?;
+    context.solve_constraints()?;
+    let schemes = values
+      .iter()
+      .map(|value| context.generalise(value))
+      .collect::<Result<Vec<_>, _>>()?;
+
+    context.stack.extend(
+      self
+        .bindings
+        .iter()
+        .map(|binding| binding.name.clone())
+        .zip(schemes),
+    );
+    let body = self.body.infer_type(context);
+    context
+      .stack
+      .truncate(context.stack.len() - self.bindings.len());
+    body
+  }
+}
+
+impl TransformInto<Result<types::Type, TypeError>> for surface::LetRec
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<types::Type, TypeError>
+  {
+    let schemes = context.recursive(&self.functions)?;
+    context.stack.extend(schemes);
+    let body = self.body.infer_type(context);
+    context
+      .stack
+      .truncate(context.stack.len() - self.functions.len());
+    body
+  }
+}
+
+impl TransformInto<Result<types::Type, TypeError>> for surface::Tuple
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<types::Type, TypeError>
+  {
+    Ok(types::Type::Tuple(
+      self
+        .elements
+        .iter()
+        .map(|element| element.infer_type(context))
+        .collect::<Result<_, _>>()?,
+    ))
+  }
+}
+
+/// The elements must all have the same type, the empty list is a list of any
+/// type.
+impl TransformInto<Result<types::Type, TypeError>> for surface::List
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<types::Type, TypeError>
+  {
+    let element: types::Type = context.free_name().into();
+    for value in self.elements.iter() {
+      let actual = value.infer_type(context)?;
+      context.constraints.push(
+        types::Equivalent {
+          left: element.clone(),
+          right: actual,
+          span: value.span(),
+        }
+        .into(),
+      );
+    }
+    Ok(types::Type::list(element))
+  }
+}
+
+/// The size of the tuple has to be known by the time it is projected, so
+/// the constraints gathered so far are solved first.
+impl TransformInto<Result<types::Type, TypeError>> for surface::Projection
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<types::Type, TypeError>
+  {
+    let tuple = self.tuple.infer_type(context)?;
+    context.solve_constraints()?;
+    match tuple.resolve(context)? {
+      | types::Type::Tuple(mut elements) if self.index < elements.len() =>
+        Ok(elements.swap_remove(self.index)),
+      | typ @ types::Type::Tuple(_) => Err(TypeError::NoElement {
+        typ: Box::new(typ),
+        index: self.index,
+        span: self.span,
+      }),
+      | typ @ types::Type::Variable(_) => Err(TypeError::UnknownTuple {
+        typ: Box::new(typ),
+        span: self.tuple.span(),
+      }),
+      | typ => Err(TypeError::NotATuple {
+        typ: Box::new(typ),
+        span: self.tuple.span(),
+      }),
+    }
+  }
+}
+
+impl TransformInto<Result<types::Type, TypeError>> for surface::Record
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<types::Type, TypeError>
+  {
+    Ok(types::Type::record(context.fields(&self.fields)?, None))
+  }
+}
+
+/// The record can have any fields besides `field`.
+impl TransformInto<Result<types::Type, TypeError>> for surface::FieldAccess
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<types::Type, TypeError>
+  {
+    let record = self.record.infer_type(context)?;
+    let field: types::Type = context.free_name().into();
+    let row = context.row([self.field.clone()]);
+    context.constraints.push(
+      types::Equivalent {
+        left: types::Type::record(
+          vec![(self.field.clone(), field.clone())],
+          Some(row),
+        ),
+        right: record,
+        span: self.record.span(),
+      }
+      .into(),
+    );
+    Ok(field)
+  }
+}
+
+/// The updated fields keep their types, so the record keeps its type.
+impl TransformInto<Result<types::Type, TypeError>> for surface::Update
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<types::Type, TypeError>
+  {
+    let record = self.record.infer_type(context)?;
+    let fields = context.fields(&self.fields)?;
+    let row = context.row(
+      fields
+        .iter()
+        .map(|(name, _)| name.clone()),
+    );
+    context.constraints.push(
+      types::Equivalent {
+        left: record.clone(),
+        right: types::Type::record(fields, Some(row)),
+        span: self.span,
+      }
+      .into(),
+    );
+    Ok(record)
+  }
+}
+
+/// The record must not have any of the new fields already.
+impl TransformInto<Result<types::Type, TypeError>> for surface::Extension
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<types::Type, TypeError>
+  {
+    let fields = context.fields(&self.fields)?;
+    let record = self.record.infer_type(context)?;
+    let row = context.row(
+      fields
+        .iter()
+        .map(|(name, _)| name.clone()),
+    );
+    context.constraints.push(
+      types::Equivalent {
+        left: types::Type::record(vec![], Some(row.clone())),
+        right: record,
+        span: self.record.span(),
+      }
+      .into(),
+    );
+    Ok(types::Type::record(fields, Some(row)))
+  }
+}
+
+impl TransformInto<Result<types::Type, TypeError>> for surface::Restriction
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<types::Type, TypeError>
+  {
+    let record = self.record.infer_type(context)?;
+    let mut fields = Vec::<(surface::Identifier, types::Type)>::new();
+    for field in self.fields.iter() {
+      if let Some((first, _)) = fields
+        .iter()
+        .find(|(name, _)| name.name == field.name)
+      {
+        return Err(TypeError::DuplicateField {
+          field: field.clone(),
+          first: first.span,
+        })
+      }
+      fields.push((field.clone(), context.free_name().into()));
+    }
+    let row = context.row(self.fields.iter().cloned());
+    context.constraints.push(
+      types::Equivalent {
+        left: types::Type::record(fields, Some(row.clone())),
+        right: record,
+        span: self.record.span(),
+      }
+      .into(),
+    );
+    Ok(types::Type::record(vec![], Some(row)))
+  }
+}
+
+impl TransformInto<Result<types::Type, TypeError>> for surface::Identifier
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<types::Type, TypeError>
+  {
+    context
+      .lookup(self.name.as_str())
+      .ok_or_else(|| TypeError::UnboundName(self.clone()))
+  }
+}
+
+impl TransformInto<Result<types::Type, TypeError>> for surface::Qualified
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<types::Type, TypeError>
+  {
+    context.member(&self.path, &self.name)
+  }
+}
+
+impl TransformInto<Result<types::Type, TypeError>> for surface::Literal
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    _context: Self::Context<'_>,
+  ) -> Result<types::Type, TypeError>
+  {
+    Ok(match self {
+      | surface::Literal::String(_) =>
+        surface::Identifier::new("String").into(),
+      | surface::Literal::Integer(_) => surface::Identifier::new("Int").into(),
+      | surface::Literal::Float(_) => surface::Identifier::new("Float").into(),
+      | surface::Literal::Boolean(_) =>
+        surface::Identifier::new("Boolean").into(),
+      | surface::Literal::Unit => surface::Identifier::new("Unit").into(),
+    })
+  }
+}
+
+impl TransformInto<Result<types::Scheme, TypeError>> for surface::ValBinding
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<types::Scheme, TypeError>
+  {
+    let scheme = context.binding(|context| {
+      let typ: types::Type = self.value.infer_type(context)?;
+      context.solve_constraints()?;
+      context.generalise(&typ)
+    })?;
+    context.bind(self.name.clone(), scheme.clone());
+    Ok(scheme)
+  }
+}
+
+impl TransformInto<Result<Vec<types::Scheme>, TypeError>>
+  for surface::DefBinding
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<Vec<types::Scheme>, TypeError>
+  {
+    let schemes =
+      context.binding(|context| context.recursive(&self.functions))?;
+    context
+      .stack
+      .extend(schemes.iter().cloned());
+    Ok(
+      schemes
+        .into_iter()
+        .map(|(_, scheme)| scheme)
+        .collect(),
+    )
+  }
+}
+
+/// Every constructor is polymorphic in the parameters of the type, `Some` is
+/// `'a -> 'a option`.
+impl TransformInto<Result<Vec<types::Scheme>, TypeError>>
+  for surface::TypeDeclaration
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<Vec<types::Scheme>, TypeError>
+  {
+    if let Some((parameter, first)) = repeated(&self.parameters) {
+      return Err(TypeError::DuplicateParameter {
+        parameter: parameter.clone(),
+        first,
+      })
+    }
+    let names = self
+      .constructors
+      .iter()
+      .map(|constructor| &constructor.name);
+    if let Some((constructor, first)) = repeated(names) {
+      return Err(TypeError::DuplicateConstructor {
+        constructor: constructor.clone(),
+        first,
+      })
+    }
+    let variables = self
+      .parameters
+      .iter()
+      .map(|parameter| types::Variable::Named(parameter.clone()))
+      .collect::<Vec<_>>();
+    // the type is in scope of its constructors, which may be recursive
+    context.declarations += 1;
+    let declaration = context.declarations;
+    let constant = types::Constant {
+      path: context.path.clone(),
+      name: self.name.clone(),
+      declaration: Some(declaration),
+    };
+    context.types.push(constant.clone());
+    let constructors = self
+      .constructors
+      .iter()
+      .map(|constructor| {
+        let argument = match &constructor.argument {
+          | Some(argument) => {
+            let unbound = argument
+              .variables()
+              .into_iter()
+              .find(|variable| !variables.contains(variable));
+            if let Some(types::Variable::Named(identifier)) = unbound {
+              return Err(TypeError::UnboundTypeVariable(identifier))
+            }
+            // the parameters stand for types, not for the fields of a record
+            let row = argument
+              .kinds()
+              .into_iter()
+              .find(|(_, kind)| *kind == types::Kind::Row);
+            if let Some((variable, kind)) = row {
+              let parameter = variables
+                .iter()
+                .find(|parameter| **parameter == variable)
+                .map_or_else(Default::default, named_at);
+              return Err(TypeError::KindMismatch {
+                span: named_at(&variable),
+                variable: Box::new(variable),
+                kind,
+                first: parameter,
+              })
+            }
+            Some(context.declared(argument)?)
+          },
+          | None => None,
+        };
+        Ok(surface::Constructor {
+          name: constructor.name.clone(),
+          argument,
+        })
+      })
+      .collect::<Result<Vec<_>, _>>()?;
+    let declared = self.typ(constant);
+    let schemes = constructors
+      .iter()
+      .map(|constructor| types::Scheme {
+        variables: variables.clone(),
+        typ: match &constructor.argument {
+          | Some(argument) =>
+            types::Type::abstraction(argument.clone(), declared.clone()),
+          | None => declared.clone(),
+        },
+      })
+      .collect::<Vec<_>>();
+    for (constructor, scheme) in constructors.iter().zip(&schemes) {
+      context.bind(constructor.name.clone(), scheme.clone());
+    }
+    context
+      .constructors
+      .insert(declaration, (variables, constructors));
+    Ok(schemes)
+  }
+}
+
+/// The schemes of the names of the declarations of the structure, which binds
+/// its signature in place of them.
+impl TransformInto<Result<Vec<types::Scheme>, TypeError>> for surface::Structure
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<Vec<types::Scheme>, TypeError>
+  {
+    let values = context.stack.len();
+    let declared = context.types.len();
+    let structures = context.structures.len();
+    context.path.push(self.name.clone());
+    let schemes = self
+      .declarations
+      .iter()
+      .map(|declaration| declaration.infer_type(&mut *context))
+      .collect::<Result<Vec<Vec<types::Scheme>>, _>>();
+    context.path.pop();
+    let signature = Signature {
+      values: context.stack.split_off(values),
+      types: context.types.split_off(declared),
+      structures: context.structures.split_off(structures),
+    };
+    let schemes = schemes?;
+    context
+      .structures
+      .push((self.name.clone(), signature));
+    Ok(schemes.concat())
+  }
+}
+
+/// Binds the members of the structure, the declaration has no names of its
+/// own.
+impl TransformInto<Result<Vec<types::Scheme>, TypeError>> for surface::Open
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<Vec<types::Scheme>, TypeError>
+  {
+    let signature = context.structure(&self.path)?.clone();
+    context.stack.extend(signature.values);
+    context.types.extend(signature.types);
+    context
+      .structures
+      .extend(signature.structures);
+    Ok(vec![])
+  }
+}
+
+/// The scheme of every name the declaration binds, in order.
+impl TransformInto<Result<Vec<types::Scheme>, TypeError>> for surface::TopLevel
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<Vec<types::Scheme>, TypeError>
+  {
+    match self {
+      | surface::TopLevel::ValBinding(binding) =>
+        Ok(vec![InferType::<types::Scheme>::infer_type(
+          binding.as_ref(),
+          context,
+        )?]),
+      | surface::TopLevel::DefBinding(binding) => binding.infer_type(context),
+      | surface::TopLevel::TypeDeclaration(declaration) =>
+        declaration.infer_type(context),
+      | surface::TopLevel::Structure(structure) =>
+        structure.infer_type(context),
+      | surface::TopLevel::Open(open) => open.infer_type(context),
+    }
+  }
+}
+
+/// The scheme of every top-level name, in the order of declaration.
+impl TransformInto<Result<Vec<(surface::Identifier, types::Scheme)>, TypeError>>
+  for surface::Program
+{
+  type Context<'a> = &'a mut Context;
+
+  fn transform(
+    &self,
+    context: Self::Context<'_>,
+  ) -> Result<Vec<(surface::Identifier, types::Scheme)>, TypeError>
+  {
+    let mut schemes = vec![];
+    for declaration in self.declarations.iter() {
+      let names = declaration.names();
+      let declared: Vec<types::Scheme> = declaration.infer_type(context)?;
+      schemes.extend(names.into_iter().zip(declared));
+    }
+    Ok(schemes)
+  }
+}
+
+#[cfg(test)]
+mod spec
+{
+  use super::*;
+
+  macro_rules! assert_type {
+    ($name:ident; $input:literal resolves to $expected:expr) => {
+      #[test]
+      fn $name()
+      {
+        use crate::frontend::{
+          ExpressionParser,
+          Lexer,
+          WithBacktracking,
+        };
+        let mut lexer = Lexer::from_str($input).with_backtracking();
+        let expression = lexer.expect_expression().unwrap();
+        let mut context = Context::default();
+        let typ = expression
+          .infer_type(&mut context)
+          .unwrap();
+        context.solve_constraints().unwrap();
+        pretty_assertions::assert_eq!(typ.resolve(&context), Ok($expected));
+      }
+    };
+    ($name:ident; $input:literal has type $expected:literal) => {
+      #[test]
+      fn $name()
+      {
+        use crate::frontend::{
+          ExpressionParser,
+          Lexer,
+          WithBacktracking,
+        };
+        let mut lexer = Lexer::from_str($input).with_backtracking();
+        let expression = lexer.expect_expression().unwrap();
         let mut context = Context::default();
-        let typ = dbg!(expression.infer_type(&mut context));
-        dbg!(&context);
-        context.solve_constraints();
-        dbg!(&context);
-        let typ = dbg!(typ.resolve(&context));
-        pretty_assertions::assert_eq!(typ, $expected);
+        let typ = expression
+          .infer_type(&mut context)
+          .unwrap();
+        context.solve_constraints().unwrap();
+        pretty_assertions::assert_eq!(
+          typ
+            .resolve(&context)
+            .unwrap()
+            .to_string(),
+          $expected
+        );
+      }
+    };
+    ($name:ident; $input:literal fails with $message:literal) => {
+      #[test]
+      fn $name()
+      {
+        use crate::frontend::{
+          ExpressionParser,
+          Lexer,
+          WithBacktracking,
+        };
+        let mut lexer = Lexer::from_str($input).with_backtracking();
+        let expression = lexer.expect_expression().unwrap();
+        let mut context = Context::default();
+        let error = expression
+          .infer_type(&mut context)
+          .and_then(|_| context.solve_constraints())
+          .unwrap_err();
+        pretty_assertions::assert_eq!(error.to_string(), $message);
       }
     };
   }
@@ -342,8 +1826,11 @@ mod spec
   assert_type!(string_literal_resolves_to_true;
     "`foo`" resolves to surface::Identifier::new("String").into()
   );
-  assert_type!(numeric_literal_resolves_to_numeric;
-    "10" resolves to surface::Identifier::new("Numeric").into()
+  assert_type!(integer_literal_resolves_to_int;
+    "10" resolves to surface::Identifier::new("Int").into()
+  );
+  assert_type!(float_literal_resolves_to_float;
+    "1.5e3" resolves to surface::Identifier::new("Float").into()
   );
   assert_type!(true_resolves_to_boolean;
     "true" resolves to surface::Identifier::new("Boolean").into()
@@ -376,12 +1863,634 @@ mod spec
     )
   );
   assert_type!(abstraction_one_parameter_fully_applied_returns_first;
-    "(fun x -> x) 10" resolves to surface::Identifier::new("Numeric").into()
+    "(fun x -> x) 10" resolves to surface::Identifier::new("Int").into()
   );
   assert_type!(abstraction_two_parameters_fully_applied_returns_first;
-    "(fun x y -> x) 10 `foo`" resolves to surface::Identifier::new("Numeric").into()
+    "(fun x y -> x) 10 `foo`" resolves to surface::Identifier::new("Int").into()
   );
   assert_type!(abstraction_two_parameters_fully_applied_returns_second;
     "(fun x y -> y) 10 `foo`" resolves to surface::Identifier::new("String").into()
   );
+  assert_type!(applying_a_literal_is_an_error;
+    "true 10" fails with "`Boolean` is not a function"
+  );
+  assert_type!(applying_a_parameter_to_itself_is_an_error;
+    "fun x -> x x" fails with "cannot construct the infinite type `'a = 'a -> 'b`"
+  );
+  assert_type!(using_a_parameter_at_two_types_is_an_error;
+    "fun f -> (fun a b -> a) (f 10) (f `foo`)"
+      fails with "mismatched types: expected `String`, found `Int`"
+  );
+  assert_type!(unbound_names_are_an_error;
+    "foo" fails with "unbound variable `foo`"
+  );
+
+  assert_type!(annotated_parameter;
+    "fun (x : Int) -> x" has type "Int -> Int"
+  );
+  assert_type!(annotation_with_type_variables;
+    "(fun x -> x : 'a -> 'a)" has type "'t1 -> 't1"
+  );
+  assert_type!(annotation_narrows_the_inferred_type;
+    "(fun x y -> x : Int -> 'a -> Int)" has type "Int -> 't2 -> Int"
+  );
+  assert_type!(annotation_disagrees_with_the_inferred_type;
+    "(10 : String)" fails with "mismatched types: expected `String`, found `Int`"
+  );
+  assert_type!(type_variables_stand_for_one_type_throughout_the_binding;
+    "fun (x : 'a) -> fun (y : 'a) -> (x, y)" has type "'t0 -> 't0 -> 't0 * 't0"
+  );
+  assert_type!(type_variables_named_twice_disagree;
+    "(fun (x : 'a) (y : 'a) -> x) 10 `foo`"
+      fails with "mismatched types: expected `Int`, found `String`"
+  );
+  assert_type!(annotated_parameter_disagrees_with_its_use;
+    "fun (f : Int) -> f 10" fails with "`Int` is not a function"
+  );
+  assert_type!(apply;
+    "fun f x -> f x" has type "('t1 -> 't2) -> 't1 -> 't2"
+  );
+  assert_type!(compose;
+    "fun f g x -> f (g x)" has type "('t3 -> 't4) -> ('t2 -> 't3) -> 't2 -> 't4"
+  );
+  assert_type!(identity_applied_to_identity;
+    "(fun x -> x) (fun y -> y)" has type "'t0 -> 't0"
+  );
+  assert_type!(higher_order_argument;
+    "(fun f -> f 10) (fun n -> n)" has type "Int"
+  );
+  assert_type!(same_type_used_twice;
+    "fun x -> (fun a b -> a) x x" has type "'t0 -> 't0"
+  );
+  assert_type!(constant_function_ignores_its_argument;
+    "(fun x y -> x) `foo` true" has type "String"
+  );
+  assert_type!(applying_a_result_too_often_is_an_error;
+    "(fun x -> x) true `foo`" fails with "`Boolean` is not a function"
+  );
+  assert_type!(passing_a_literal_as_a_function_is_an_error;
+    "(fun f -> f 10) 10" fails with "`Int` is not a function"
+  );
+  assert_type!(passing_a_function_as_a_literal_is_an_error;
+    "(fun f -> f true) (fun g -> g 10)"
+      fails with "`Boolean` is not a function"
+  );
+  assert_type!(argument_of_the_wrong_type_is_an_error;
+    "fun f -> (fun a b -> a) (f 10) (f true)"
+      fails with "mismatched types: expected `Boolean`, found `Int`"
+  );
+  assert_type!(self_application_through_an_argument_is_an_error;
+    "fun f -> f (fun x -> f x)"
+      fails with "cannot construct the infinite type `'a = 'a -> 'b`"
+  );
+
+  assert_type!(conditional_has_the_type_of_its_branches;
+    "if true then 1 else 2" has type "Int"
+  );
+  assert_type!(conditional_unifies_its_branches;
+    "fun x -> if x then (fun y -> y) else (fun (z : String) -> z)"
+      has type "Boolean -> String -> String"
+  );
+  assert_type!(conditional_requires_a_boolean;
+    "if 1 then 2 else 3" fails with "mismatched types: expected `Boolean`, found `Int`"
+  );
+  assert_type!(conditional_branches_must_agree;
+    "if true then 1 else `one`" fails with "mismatched types: expected `Int`, found `String`"
+  );
+  assert_type!(let_bound_value;
+    "let x = 10 in x" has type "Int"
+  );
+  assert_type!(let_bound_functions_are_polymorphic;
+    "let id = fun x -> x in (fun a b -> a) (id 10) (id `foo`)" has type "Int"
+  );
+  assert_type!(let_bindings_are_simultaneous;
+    "fun x -> let x = 10 and y = x in y" has type "'t0 -> 't0"
+  );
+  assert_type!(let_does_not_generalise_enclosing_parameters;
+    "fun f -> let g = f in (fun a b -> a) (g 10) (g true)"
+      fails with "mismatched types: expected `Boolean`, found `Int`"
+  );
+  assert_type!(let_rec_function_calls_itself;
+    "let rec loop x = loop x in loop" has type "'t3 -> 't4"
+  );
+  assert_type!(let_rec_functions_are_generalised_in_the_body;
+    "let rec f x = x in (fun a b -> a) (f 10) (f `foo`)" has type "Int"
+  );
+  assert_type!(let_rec_functions_are_monomorphic_within_the_group;
+    "let rec f x = (fun a b -> a) (f 10) (f `foo`) in f"
+      fails with "mismatched types: expected `String`, found `Int`"
+  );
+  assert_type!(let_rec_mutually_recursive_functions;
+    "let rec f (x : Int) = g x and g y = f y in g" has type "Int -> 't5"
+  );
+  assert_type!(let_rec_return_annotation;
+    "let rec f x : Boolean = f x in f" has type "'t3 -> Boolean"
+  );
+  assert_type!(unit_resolves_to_unit;
+    "()" resolves to surface::Identifier::new("Unit").into()
+  );
+  assert_type!(tuple_of_its_element_types;
+    "(1, `one`, (true, ()))" has type "Int * String * (Boolean * Unit)"
+  );
+  assert_type!(tuple_parameters_are_taken_apart;
+    "fun (x, y) -> (y, x)" has type "'t1 * 't2 -> 't2 * 't1"
+  );
+  assert_type!(annotated_tuple_parameter;
+    "fun ((x, y) : Int * String) -> x" has type "Int * String -> Int"
+  );
+  assert_type!(tuple_parameter_needs_a_tuple;
+    "(fun (x, y) -> x) 1" fails with "mismatched types: expected `'a * 'b`, found `Int`"
+  );
+  assert_type!(projection_selects_an_element;
+    "(1, `one`).1" has type "String"
+  );
+  assert_type!(projection_of_an_annotated_parameter;
+    "fun (p : 'a * 'b) -> p.0" has type "'t0 * 't1 -> 't0"
+  );
+  assert_type!(projection_out_of_bounds_is_an_error;
+    "(1, 2).2" fails with "the tuple `Int * Int` has no element 2"
+  );
+  assert_type!(projection_of_a_non_tuple_is_an_error;
+    "true.0" fails with "`Boolean` is not a tuple"
+  );
+  assert_type!(projection_of_an_unknown_tuple_is_an_error;
+    "fun p -> p.0" fails with "the type `'a` must be known to be a tuple"
+  );
+  assert_type!(let_bindings_are_not_visible_afterwards;
+    "(fun a b -> a) (let x = 10 in x) x" fails with "unbound variable `x`"
+  );
+  assert_type!(match_arms_share_their_type;
+    "match (1, true) with (0, b) -> b | (_, _) -> false" has type "Boolean"
+  );
+  assert_type!(match_patterns_have_the_type_of_the_scrutinee;
+    "match 1 with `one` -> 1 | _ -> 2" fails with
+      "mismatched types: expected `String`, found `Int`"
+  );
+  assert_type!(match_arms_must_agree;
+    "match 1 with 0 -> 1 | _ -> true" fails with
+      "mismatched types: expected `Boolean`, found `Int`"
+  );
+  assert_type!(record_of_its_field_types;
+    "{ name = `a`; age = 1 }" has type "{ age : Int; name : String }"
+  );
+  assert_type!(record_fields_are_distinct;
+    "{ age = 1; age = 2 }" fails with "the field `age` is given more than once"
+  );
+  assert_type!(field_access_selects_a_field;
+    "{ name = `a`; age = 1 }.name" has type "String"
+  );
+  assert_type!(field_access_of_an_annotated_parameter;
+    "fun (r : { name : 'a }) -> r.name" has type "{ name : 't0 } -> 't0"
+  );
+  assert_type!(field_access_of_a_missing_field_is_an_error;
+    "{ age = 1 }.name" fails with "the record `{ age : Int }` has no field `name`"
+  );
+  assert_type!(field_access_of_a_non_record_is_an_error;
+    "true.name" fails with
+      "mismatched types: expected `{ name : 'a | 'b }`, found `Boolean`"
+  );
+  assert_type!(update_keeps_the_type_of_the_record;
+    "{ { name = `a`; age = 1 } with age = 2 }" has type "{ age : Int; name : String }"
+  );
+  assert_type!(update_keeps_the_types_of_the_fields;
+    "{ { age = 1 } with age = `two` }"
+      fails with "mismatched types: expected `Int`, found `String`"
+  );
+  assert_type!(update_of_a_missing_field_is_an_error;
+    "{ { age = 1 } with name = `a` }"
+      fails with "the record `{ age : Int }` has no field `name`"
+  );
+  assert_type!(field_access_accepts_any_record_with_the_field;
+    "fun r -> r.name" has type "{ name : 't1 | 't2 } -> 't1"
+  );
+  assert_type!(row_polymorphic_functions_are_generalised;
+    "let name = fun r -> r.name in (name { name = 1 }, name { name = `a`; age = 2 })"
+      has type "Int * String"
+  );
+  assert_type!(field_access_of_a_missing_field_is_an_error_through_a_row;
+    "(fun r -> r.name) { age = 1 }"
+      fails with "the record `{ age : Int }` has no field `name`"
+  );
+  assert_type!(update_accepts_any_record_with_the_fields;
+    "fun r -> { r with age = 1 }" has type "{ age : Int | 't1 } -> { age : Int | 't1 }"
+  );
+  assert_type!(extension_adds_a_field_to_the_row;
+    "fun r -> { id = 1 | r }" has type "{ | 't1 } -> { id : Int | 't1 }"
+  );
+  assert_type!(extension_of_a_record;
+    "{ id = 1 | { name = `a` } }" has type "{ id : Int; name : String }"
+  );
+  assert_type!(extension_of_a_present_field_is_an_error;
+    "{ age = 1 | { age = 2 } }"
+      fails with "the record `{ age : Int }` already has a field `age`"
+  );
+  assert_type!(extension_of_a_present_field_is_an_error_through_a_row;
+    "(fun r -> { age = 1 | r }) { age = 2 }"
+      fails with "the record `{ age : Int }` already has a field `age`"
+  );
+  assert_type!(extension_fields_are_distinct;
+    "fun r -> { age = 1; age = 2 | r }"
+      fails with "the field `age` is given more than once"
+  );
+  assert_type!(restriction_removes_a_field_from_the_row;
+    "fun r -> { r without name }" has type "{ name : 't1 | 't2 } -> { | 't2 }"
+  );
+  assert_type!(restriction_of_a_record;
+    "{ { name = `a`; age = 1 } without name }" has type "{ age : Int }"
+  );
+  assert_type!(restriction_of_a_missing_field_is_an_error;
+    "{ { age = 1 } without name }"
+      fails with "the record `{ age : Int }` has no field `name`"
+  );
+  assert_type!(restriction_then_extension_changes_the_type_of_a_field;
+    "{ age = `one` | { { age = 1 } without age } }" has type "{ age : String }"
+  );
+  assert_type!(open_record_annotations;
+    "fun (r : { age : Int | 'r }) -> { r without age }" has type "{ age : Int | 't0 } -> { | 't0 }"
+  );
+  assert_type!(type_variables_stand_for_types_or_for_fields;
+    "fun (y : 'r) (r : { a : Int | 'r }) -> r.a" fails with "the type variable `'r` stands for both a type and the fields of a record"
+  );
+  assert_type!(list_of_its_element_type;
+    "[1, 2, 3]" has type "List Int"
+  );
+  assert_type!(empty_list_of_any_type;
+    "[]" has type "List 't0"
+  );
+  assert_type!(list_elements_must_agree;
+    "[1, true]" fails with "mismatched types: expected `Boolean`, found `Int`"
+  );
+  assert_type!(lists_of_lists;
+    "[[1], []]" has type "List (List Int)"
+  );
+  assert_type!(list_patterns_bind_elements_and_tails;
+    "fun l -> match l with [] -> [] | [x] -> [x] | x :: y :: rest -> rest"
+      has type "List 't3 -> List 't3"
+  );
+  assert_type!(list_patterns_match_lists;
+    "match (1, 2) with [x] -> x" fails with
+      "mismatched types: expected `List 'a`, found `Int * Int`"
+  );
+  assert_type!(annotated_lists;
+    "fun (l : List 'a) -> l" has type "List 't0 -> List 't0"
+  );
+  assert_type!(match_bindings_are_scoped_to_their_arm;
+    "match 1 with x -> x | _ -> x" fails with "unbound variable `x`"
+  );
+}
+
+#[cfg(test)]
+mod declarations
+{
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+  use crate::frontend::{
+    DeclarationParser,
+    Lexer,
+    WithBacktracking,
+  };
+
+  fn infer_program(input: &str) -> Result<Vec<(String, String)>, TypeError>
+  {
+    let program = Lexer::from_str(input)
+      .with_backtracking()
+      .expect_program()
+      .unwrap();
+    let mut context = Context::default();
+    let schemes: Vec<(surface::Identifier, types::Scheme)> =
+      program.infer_type(&mut context)?;
+    Ok(
+      schemes
+        .into_iter()
+        .map(|(name, scheme)| (name.name, scheme.to_string()))
+        .collect(),
+    )
+  }
+
+  #[test]
+  fn every_name_gets_a_scheme()
+  {
+    assert_eq!(
+      infer_program(
+        "val id = fun x -> x ;\nval const = fun a b -> a ;\nval n = const (id \
+         10) id ;\n"
+      ),
+      Ok(vec![
+        ("id".to_string(), "'a -> 'a".to_string()),
+        ("const".to_string(), "'a -> 'b -> 'a".to_string()),
+        ("n".to_string(), "Int".to_string()),
+      ])
+    );
+  }
+
+  #[test]
+  fn type_errors_reject_the_program()
+  {
+    assert_eq!(
+      infer_program("val x = 10 ;\nval y = x x ;\n")
+        .unwrap_err()
+        .to_string(),
+      "`Int` is not a function"
+    );
+  }
+
+  #[test]
+  fn later_declarations_shadow_earlier_ones()
+  {
+    assert_eq!(
+      infer_program("val x = 10 ;\nval x = `foo` ;\nval y = x ;\n"),
+      Ok(vec![
+        ("x".to_string(), "Int".to_string()),
+        ("x".to_string(), "String".to_string()),
+        ("y".to_string(), "String".to_string()),
+      ])
+    );
+  }
+
+  #[test]
+  fn type_variables_are_scoped_to_their_declaration()
+  {
+    assert_eq!(
+      infer_program(
+        "val x = (10 : 'a) ;\ndef f (y : 'a) = (y : String) and g (z : 'a) = \
+         z ;\n"
+      ),
+      Ok(vec![
+        ("x".to_string(), "Int".to_string()),
+        ("f".to_string(), "String -> String".to_string()),
+        ("g".to_string(), "String -> String".to_string()),
+      ])
+    );
+  }
+
+  #[test]
+  fn constructors_are_polymorphic_in_the_parameters()
+  {
+    assert_eq!(
+      infer_program(
+        "type 'a option = None | Some of 'a ;\nval x = Some (Some 1) ;\n"
+      ),
+      Ok(vec![
+        ("None".to_string(), "'a option".to_string()),
+        ("Some".to_string(), "'a -> 'a option".to_string()),
+        ("x".to_string(), "Int option option".to_string()),
+      ])
+    );
+  }
+
+  #[test]
+  fn recursive_types_with_several_parameters()
+  {
+    assert_eq!(
+      infer_program(
+        "type ('k, 'v) map = Empty | Node of ('k, 'v) map * 'k * 'v * ('k, \
+         'v) map ;\nval m = Node (Empty, 1, `one`, Empty) ;\n"
+      ),
+      Ok(vec![
+        ("Empty".to_string(), "('a, 'b) map".to_string()),
+        (
+          "Node".to_string(),
+          "('a, 'b) map * 'a * 'b * ('a, 'b) map -> ('a, 'b) map".to_string()
+        ),
+        ("m".to_string(), "(Int, String) map".to_string()),
+      ])
+    );
+  }
+
+  #[test]
+  fn constructor_arguments_are_checked()
+  {
+    assert_eq!(
+      infer_program("type t = A of Int ;\nval a = A `one` ;\n")
+        .unwrap_err()
+        .to_string(),
+      "mismatched types: expected `Int`, found `String`"
+    );
+  }
+
+  #[test]
+  fn constructors_only_mention_the_parameters()
+  {
+    assert_eq!(
+      infer_program("type 'a t = A of 'b ;\n")
+        .unwrap_err()
+        .to_string(),
+      "unbound type variable `'b`"
+    );
+  }
+
+  #[test]
+  fn annotations_agree_on_what_a_variable_stands_for()
+  {
+    assert_eq!(
+      infer_program(
+        "val f = fun (y : 'r) (r : { a : Int | 'r }) -> (y + 1, r.a) ;\n"
+      )
+      .unwrap_err()
+      .to_string(),
+      "the type variable `'r` stands for both a type and the fields of a \
+       record"
+    );
+    assert_eq!(
+      infer_program("type 'r t = A of { a : Int | 'r } ;\n")
+        .unwrap_err()
+        .to_string(),
+      "the type variable `'r` stands for both a type and the fields of a \
+       record"
+    );
+  }
+
+  #[test]
+  fn redeclared_types_are_distinct()
+  {
+    let program =
+      "type t = A ;\nval a = A ;\ntype t = B ;\nval f = fun (x : t) -> x ;\n";
+    assert_eq!(
+      infer_program(&format!("{}val b = f B ;\n", program))
+        .unwrap()
+        .last()
+        .unwrap()
+        .1,
+      "t"
+    );
+    assert_eq!(
+      infer_program(&format!("{}val b = f a ;\n", program))
+        .unwrap_err()
+        .to_string(),
+      "mismatched types: expected `t/2`, found `t/1`"
+    );
+  }
+
+  #[test]
+  fn declared_names_are_distinct()
+  {
+    assert_eq!(
+      infer_program("type t = A | A of Int ;\n")
+        .unwrap_err()
+        .to_string(),
+      "the constructor `A` is declared more than once"
+    );
+    assert_eq!(
+      infer_program("type ('a, 'a) t = A of 'a ;\n")
+        .unwrap_err()
+        .to_string(),
+      "the type parameter `'a` is declared more than once"
+    );
+  }
+
+  #[test]
+  fn types_are_named_before_use()
+  {
+    assert_eq!(
+      infer_program("val f = fun (x : Int t) -> x ;\n")
+        .unwrap_err()
+        .to_string(),
+      "unknown type `t`"
+    );
+    assert_eq!(
+      infer_program("type t = A of Int u ;\n")
+        .unwrap_err()
+        .to_string(),
+      "unknown type `u`"
+    );
+    assert_eq!(
+      infer_program("type 'a t = Leaf | Node of 'a t * 'a t ;\n")
+        .unwrap()
+        .last()
+        .unwrap()
+        .1,
+      "'a t * 'a t -> 'a t"
+    );
+  }
+
+  #[test]
+  fn constructor_patterns_instantiate_the_type()
+  {
+    assert_eq!(
+      infer_program(
+        "type 'a option = None | Some of 'a ;
+val get = fun d o -> match o with Some x -> x | None -> d ;
+"
+      ),
+      Ok(vec![
+        ("None".to_string(), "'a option".to_string()),
+        ("Some".to_string(), "'a -> 'a option".to_string()),
+        ("get".to_string(), "'a -> 'a option -> 'a".to_string()),
+      ])
+    );
+  }
+
+  #[test]
+  fn constructor_patterns_match_the_arity()
+  {
+    let program = "type 'a option = None | Some of 'a ;
+";
+    assert_eq!(
+      infer_program(&format!(
+        "{}val x = fun o -> match o with Some -> 1 ;
+",
+        program
+      ))
+      .unwrap_err()
+      .to_string(),
+      "the constructor `Some` expects an argument"
+    );
+    assert_eq!(
+      infer_program(&format!(
+        "{}val x = fun o -> match o with None y -> 1 ;
+",
+        program
+      ))
+      .unwrap_err()
+      .to_string(),
+      "the constructor `None` takes no argument"
+    );
+  }
+
+  #[test]
+  fn members_are_named_after_their_structure()
+  {
+    assert_eq!(
+      infer_program(
+        "structure Option = struct
+  type 'a option = None | Some of 'a ;
+  val get = fun d o -> match o with Some x -> x | None -> d ;
+end ;
+val x = Option.get 0 (Option.Some 1) ;
+"
+      ),
+      Ok(vec![
+        ("Option.None".to_string(), "'a Option.option".to_string()),
+        ("Option.Some".to_string(), "'a -> 'a Option.option".to_string()),
+        ("Option.get".to_string(), "'a -> 'a Option.option -> 'a".to_string()),
+        ("x".to_string(), "Int".to_string()),
+      ])
+    );
+  }
+
+  #[test]
+  fn members_are_qualified_in_patterns_and_types()
+  {
+    let program =
+      "structure M = struct type 'a t = None | Some of 'a ; end ;\n";
+    assert_eq!(
+      infer_program(&format!(
+        "{}val get = fun d (o : 'a M.t) -> match o with M.Some x -> x | \
+         M.None -> d ;\nval x = get 0 (M.Some 1) ;\n",
+        program
+      )),
+      Ok(vec![
+        ("M.None".to_string(), "'a M.t".to_string()),
+        ("M.Some".to_string(), "'a -> 'a M.t".to_string()),
+        ("get".to_string(), "'a -> 'a M.t -> 'a".to_string()),
+        ("x".to_string(), "Int".to_string()),
+      ])
+    );
+    assert_eq!(
+      infer_program(&format!("{}val f = fun (o : Int t) -> o ;\n", program))
+        .unwrap_err()
+        .to_string(),
+      "unknown type `t`"
+    );
+    assert_eq!(
+      infer_program(&format!("{}val f = fun (o : M.u) -> o ;\n", program))
+        .unwrap_err()
+        .to_string(),
+      "unknown type `M.u`"
+    );
+    assert_eq!(
+      infer_program(&format!(
+        "{}open M ;\nval f = fun (o : Int t) -> match o with Some x -> x | \
+         M.None -> 0 ;\n",
+        program
+      ))
+      .unwrap()
+      .last()
+      .unwrap()
+      .1,
+      "Int M.t -> Int"
+    );
+  }
+
+  #[test]
+  fn open_structures_bind_their_members()
+  {
+    assert_eq!(
+      infer_program(
+        "structure A = struct structure B = struct val id = fun x -> x ; end \
+         ; end ;\nopen A ;\nval y = (B.id true, A.B.id 1) ;\n"
+      ),
+      Ok(vec![
+        ("A.B.id".to_string(), "'a -> 'a".to_string()),
+        ("y".to_string(), "Boolean * Int".to_string()),
+      ])
+    );
+    assert_eq!(
+      infer_program("val y = A.x ;\n")
+        .unwrap_err()
+        .to_string(),
+      "unknown structure `A`"
+    );
+  }
 }
@@ -0,0 +1,732 @@
+use std::collections::HashMap;
+
+use super::TypeError;
+use crate::syntax::surface::{
+  types,
+  Identifier,
+  Span,
+};
+
+/// The fields of a record, and the row variable for any further fields.
+type Row = (Vec<(Identifier, types::Type)>, Option<types::Variable>);
+
+/// Union-find over type variables: every variable belongs to a class whose
+/// representative may be bound to a type that is not itself a variable.
+///
+/// Row variables, the rests of open records, are bound to the record of the
+/// fields they stand for.
+#[derive(Debug, Clone, Default)]
+pub struct Substitution
+{
+  parents: HashMap<types::Variable, types::Variable>,
+  bindings: HashMap<types::Variable, types::Type>,
+  /// The fields that the row of each class must not contain, because a
+  /// record it is the rest of has them already.
+  lacks: HashMap<types::Variable, Vec<Identifier>>,
+  free_name: usize,
+}
+
+impl Substitution
+{
+  /// A variable that appears nowhere yet.
+  pub fn free_name(&mut self) -> types::Variable
+  {
+    let free_name = self.free_name;
+    self.free_name += 1;
+    types::Variable::Unnamed(free_name)
+  }
+
+  /// The fields that the row `variable` must not contain.
+  pub fn lacks(
+    &mut self,
+    variable: &types::Variable,
+  ) -> Vec<Identifier>
+  {
+    let root = self.find(variable);
+    self
+      .lacks
+      .get(&root)
+      .cloned()
+      .unwrap_or_default()
+  }
+
+  /// Requires the row `variable` not to contain `fields`.
+  pub fn lack(
+    &mut self,
+    variable: &types::Variable,
+    fields: impl IntoIterator<Item = Identifier>,
+  )
+  {
+    let root = self.find(variable);
+    let lacks = self.lacks.entry(root).or_default();
+    for field in fields {
+      if !lacks
+        .iter()
+        .any(|lacked| lacked.name == field.name)
+      {
+        lacks.push(field);
+      }
+    }
+  }
+
+  /// The representative of the class of `variable`, pointing every variable
+  /// on the way directly at it.
+  pub fn find(
+    &mut self,
+    variable: &types::Variable,
+  ) -> types::Variable
+  {
+    let root = self.root(variable);
+    let mut current = variable.clone();
+    while let Some(parent) = self
+      .parents
+      .insert(current, root.clone())
+    {
+      if parent == root {
+        break
+      }
+      current = parent;
+    }
+    self.parents.remove(&root);
+    root
+  }
+
+  fn root(
+    &self,
+    variable: &types::Variable,
+  ) -> types::Variable
+  {
+    let mut current = variable;
+    while let Some(parent) = self.parents.get(current) {
+      current = parent;
+    }
+    current.clone()
+  }
+
+  fn union(
+    &mut self,
+    variable: types::Variable,
+    other: types::Variable,
+  )
+  {
+    let other = self.find(&other);
+    if variable != other {
+      if let Some(lacks) = self.lacks.remove(&variable) {
+        self.lack(&other, lacks);
+      }
+      self.parents.insert(variable, other);
+    }
+  }
+
+  /// Replaces a variable by what its class is bound to, one level deep.
+  fn shallow(
+    &mut self,
+    typ: &types::Type,
+  ) -> types::Type
+  {
+    match typ {
+      | types::Type::Variable(variable) => {
+        let root = self.find(variable);
+        match self.bindings.get(&root) {
+          | Some(bound) => bound.clone(),
+          | None => root.into(),
+        }
+      },
+      | typ => typ.clone(),
+    }
+  }
+
+  fn occurs(
+    &mut self,
+    variable: &types::Variable,
+    typ: &types::Type,
+  ) -> bool
+  {
+    match self.shallow(typ) {
+      | types::Type::Variable(other) => &other == variable,
+      | types::Type::Abstraction(abstraction) =>
+        self.occurs(variable, &abstraction.parameter_type)
+          || self.occurs(variable, &abstraction.return_type),
+      | types::Type::Concrete(_, elements) | types::Type::Tuple(elements) =>
+        elements
+          .iter()
+          .any(|element| self.occurs(variable, element)),
+      | types::Type::List(element) => self.occurs(variable, &element),
+      | types::Type::Record(fields, rest) =>
+        fields
+          .iter()
+          .any(|(_, typ)| self.occurs(variable, typ))
+          || rest.map_or(false, |rest| self.occurs(variable, &rest.into())),
+    }
+  }
+
+  /// The fields of a record together with those its row is bound to, and
+  /// the row variable that remains.
+  fn row(
+    &mut self,
+    mut fields: Vec<(Identifier, types::Type)>,
+    mut rest: Option<types::Variable>,
+    span: Span,
+  ) -> Result<Row, TypeError>
+  {
+    while let Some(variable) = rest.take() {
+      match self.shallow(&variable.into()) {
+        | types::Type::Record(more, more_rest) => {
+          fields.extend(more);
+          rest = more_rest;
+        },
+        | types::Type::Variable(root) => {
+          rest = Some(root);
+          break
+        },
+        | typ =>
+          return Err(TypeError::NotARow {
+            typ: Box::new(self.resolve(&typ)?),
+            span,
+          }),
+      }
+    }
+    fields.sort_by(|(left, _), (right, _)| left.name.cmp(&right.name));
+    Ok((fields, rest))
+  }
+
+  /// Binds the row `variable` to `fields` followed by `rest`, which then
+  /// lacks everything `variable` lacked as well as `fields`.
+  fn bind_row(
+    &mut self,
+    variable: types::Variable,
+    fields: Vec<(Identifier, types::Type)>,
+    rest: Option<types::Variable>,
+    span: Span,
+  ) -> Result<(), TypeError>
+  {
+    let mut lacks = self.lacks(&variable);
+    let present = lacks.iter().find(|lacked| {
+      fields
+        .iter()
+        .any(|(name, _)| name.name == lacked.name)
+    });
+    let present = present.cloned();
+    lacks.extend(
+      fields
+        .iter()
+        .map(|(name, _)| name.clone()),
+    );
+    let row = types::Type::Record(fields, rest.clone());
+    if let Some(field) = present {
+      return Err(TypeError::FieldPresent {
+        typ: Box::new(self.resolve(&row)?),
+        field,
+      })
+    }
+    if self.occurs(&variable, &row) {
+      return Err(TypeError::InfiniteType {
+        variable: Box::new(variable),
+        typ: Box::new(self.resolve(&row)?),
+        span,
+      })
+    }
+    if let Some(rest) = rest {
+      self.lack(&rest, lacks);
+    }
+    self.bindings.insert(variable, row);
+    Ok(())
+  }
+
+  /// Unifies the fields that both records have, and binds the row of each
+  /// record to the fields only the other one has.
+  fn unify_records(
+    &mut self,
+    expected: types::Type,
+    actual: types::Type,
+    span: Span,
+  ) -> Result<(), TypeError>
+  {
+    let (
+      types::Type::Record(expected_fields, expected_rest),
+      types::Type::Record(actual_fields, actual_rest),
+    ) = (expected.clone(), actual.clone())
+    else {
+      unreachable!()
+    };
+    let (expected_fields, expected_rest) =
+      self.row(expected_fields, expected_rest, span)?;
+    let (actual_fields, actual_rest) =
+      self.row(actual_fields, actual_rest, span)?;
+    for (rest, fields) in
+      [(&expected_rest, &expected_fields), (&actual_rest, &actual_fields)]
+    {
+      if let Some(rest) = rest {
+        self.lack(
+          rest,
+          fields
+            .iter()
+            .map(|(name, _)| name.clone()),
+        );
+      }
+    }
+
+    let mut only_expected = vec![];
+    for (name, typ) in expected_fields.iter() {
+      match actual_fields
+        .iter()
+        .find(|(other, _)| other.name == name.name)
+      {
+        | Some((_, other)) => self.unify(typ, other, span)?,
+        | None => only_expected.push((name.clone(), typ.clone())),
+      }
+    }
+    let only_actual = actual_fields
+      .into_iter()
+      .filter(|(name, _)| {
+        !expected_fields
+          .iter()
+          .any(|(other, _)| other.name == name.name)
+      })
+      .collect::<Vec<_>>();
+
+    let missing = |fields: &[(Identifier, types::Type)],
+                   rest: &Option<types::Variable>| {
+      match (fields.first(), rest) {
+        | (Some((field, _)), None) => Some(field.clone()),
+        | _ => None,
+      }
+    };
+    if let Some(field) = missing(&only_expected, &actual_rest) {
+      return Err(TypeError::NoField {
+        typ: Box::new(self.resolve(&actual)?),
+        field,
+      })
+    }
+    if let Some(field) = missing(&only_actual, &expected_rest) {
+      return Err(TypeError::NoField {
+        typ: Box::new(self.resolve(&expected)?),
+        field,
+      })
+    }
+
+    match (expected_rest, actual_rest) {
+      | (None, None) => Ok(()),
+      | (Some(expected_rest), None) =>
+        self.bind_row(expected_rest, only_actual, None, span),
+      | (None, Some(actual_rest)) =>
+        self.bind_row(actual_rest, only_expected, None, span),
+      | (Some(expected_rest), Some(actual_rest))
+        if expected_rest == actual_rest =>
+        match only_expected.is_empty() && only_actual.is_empty() {
+          | true => Ok(()),
+          | false => Err(TypeError::Mismatch {
+            expected: Box::new(self.resolve(&expected)?),
+            actual: Box::new(self.resolve(&actual)?),
+            span,
+          }),
+        },
+      | (Some(expected_rest), Some(actual_rest)) =>
+        match (only_expected.is_empty(), only_actual.is_empty()) {
+          | (true, true) => {
+            self.union(expected_rest, actual_rest);
+            Ok(())
+          },
+          | (true, false) =>
+            self.bind_row(expected_rest, only_actual, Some(actual_rest), span),
+          | (false, true) =>
+            self.bind_row(actual_rest, only_expected, Some(expected_rest), span),
+          | (false, false) => {
+            let rest = self.free_name();
+            self.bind_row(
+              expected_rest,
+              only_actual,
+              Some(rest.clone()),
+              span,
+            )?;
+            self.bind_row(actual_rest, only_expected, Some(rest), span)
+          },
+        },
+    }
+  }
+
+  /// Makes `expected` and `actual` equal, where `expected` is the type the
+  /// context requires and `actual` the type found there.
+  pub fn unify(
+    &mut self,
+    expected: &types::Type,
+    actual: &types::Type,
+    span: Span,
+  ) -> Result<(), TypeError>
+  {
+    match (self.shallow(expected), self.shallow(actual)) {
+      | (types::Type::Variable(left), types::Type::Variable(right)) => {
+        self.union(left, right);
+        Ok(())
+      },
+      | (types::Type::Variable(variable), typ)
+      | (typ, types::Type::Variable(variable)) =>
+        match self.occurs(&variable, &typ) {
+          | true => Err(TypeError::InfiniteType {
+            variable: Box::new(variable),
+            typ: Box::new(self.resolve(&typ)?),
+            span,
+          }),
+          | false => {
+            self.bindings.insert(variable, typ);
+            Ok(())
+          },
+        },
+      | (
+        types::Type::Concrete(left, left_arguments),
+        types::Type::Concrete(right, right_arguments),
+      ) if left == right && left_arguments.len() == right_arguments.len() =>
+        left_arguments
+          .iter()
+          .zip(right_arguments.iter())
+          .try_for_each(|(left, right)| self.unify(left, right, span)),
+      | (
+        types::Type::Abstraction(_),
+        actual @ (types::Type::Concrete(..)
+        | types::Type::Tuple(_)
+        | types::Type::List(_)
+        | types::Type::Record(..)),
+      ) => Err(TypeError::NotAFunction {
+        typ: Box::new(actual),
+        span,
+      }),
+      | (types::Type::Abstraction(left), types::Type::Abstraction(right)) => {
+        // parameters are contravariant, the expected function's parameter is
+        // what is actually passed to the found one
+        self.unify(&right.parameter_type, &left.parameter_type, span)?;
+        self.unify(&left.return_type, &right.return_type, span)
+      },
+      | (types::Type::Tuple(left), types::Type::Tuple(right))
+        if left.len() == right.len() =>
+        left
+          .iter()
+          .zip(right.iter())
+          .try_for_each(|(left, right)| self.unify(left, right, span)),
+      | (types::Type::List(left), types::Type::List(right)) =>
+        self.unify(&left, &right, span),
+      | (
+        expected @ types::Type::Record(..),
+        actual @ types::Type::Record(..),
+      ) => self.unify_records(expected, actual, span),
+      | (expected, actual) => Err(TypeError::Mismatch {
+        expected: Box::new(self.resolve(&expected)?),
+        actual: Box::new(self.resolve(&actual)?),
+        span,
+      }),
+    }
+  }
+
+  /// Applies the substitution to every variable within `typ`, which fails if
+  /// a row turns out to be bound to a type other than a record.
+  pub fn resolve(
+    &self,
+    typ: &types::Type,
+  ) -> Result<types::Type, TypeError>
+  {
+    let resolve = |types: &[types::Type]| {
+      types
+        .iter()
+        .map(|typ| self.resolve(typ))
+        .collect::<Result<Vec<_>, _>>()
+    };
+    Ok(match typ {
+      | types::Type::Variable(variable) => {
+        let root = self.root(variable);
+        match self.bindings.get(&root) {
+          | Some(bound) => self.resolve(bound)?,
+          | None => root.into(),
+        }
+      },
+      | types::Type::Concrete(constant, arguments) =>
+        types::Type::Concrete(constant.clone(), resolve(arguments)?),
+      | types::Type::Abstraction(abstraction) => types::Type::abstraction(
+        self.resolve(&abstraction.parameter_type)?,
+        self.resolve(&abstraction.return_type)?,
+      ),
+      | types::Type::Tuple(elements) => types::Type::Tuple(resolve(elements)?),
+      | types::Type::List(element) => types::Type::list(self.resolve(element)?),
+      | types::Type::Record(fields, rest) => {
+        let mut fields = fields
+          .iter()
+          .map(|(name, typ)| Ok((name.clone(), self.resolve(typ)?)))
+          .collect::<Result<Vec<_>, TypeError>>()?;
+        let rest = match rest {
+          | None => None,
+          | Some(rest) => match self.resolve(&rest.clone().into())? {
+            | types::Type::Record(more, rest) => {
+              fields.extend(more);
+              rest
+            },
+            | types::Type::Variable(root) => Some(root),
+            | typ =>
+              return Err(TypeError::NotARow {
+                typ: Box::new(typ),
+                span: Span::default(),
+              }),
+          },
+        };
+        types::Type::record(fields, rest)
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod spec
+{
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+  use crate::syntax::surface::Identifier;
+
+  fn variable(name: usize) -> types::Variable
+  {
+    types::Variable::Unnamed(name)
+  }
+
+  fn numeric() -> types::Type
+  {
+    Identifier::new("Int").into()
+  }
+
+  #[test]
+  fn find_compresses_paths()
+  {
+    let mut substitution = Substitution::default();
+    for name in 0 .. 3 {
+      substitution
+        .unify(
+          &variable(name).into(),
+          &variable(name + 1).into(),
+          Span::default(),
+        )
+        .unwrap();
+    }
+    assert_eq!(substitution.find(&variable(0)), variable(3));
+    assert_eq!(substitution.parents.get(&variable(0)), Some(&variable(3)));
+    assert_eq!(substitution.parents.get(&variable(1)), Some(&variable(3)));
+    assert_eq!(substitution.parents.get(&variable(3)), None);
+  }
+
+  #[test]
+  fn bindings_are_shared_by_the_class()
+  {
+    let mut substitution = Substitution::default();
+    substitution
+      .unify(&variable(0).into(), &variable(1).into(), Span::default())
+      .unwrap();
+    substitution
+      .unify(&variable(1).into(), &numeric(), Span::default())
+      .unwrap();
+    assert_eq!(
+      substitution
+        .resolve(&variable(0).into())
+        .unwrap(),
+      numeric()
+    );
+  }
+
+  #[test]
+  fn unifying_a_variable_with_itself_is_a_no_op()
+  {
+    let mut substitution = Substitution::default();
+    substitution
+      .unify(&variable(0).into(), &variable(0).into(), Span::default())
+      .unwrap();
+    assert_eq!(substitution.find(&variable(0)), variable(0));
+    assert_eq!(
+      substitution
+        .resolve(&variable(0).into())
+        .unwrap(),
+      variable(0).into()
+    );
+  }
+
+  #[test]
+  fn occurs_check_looks_through_bindings()
+  {
+    let mut substitution = Substitution::default();
+    substitution
+      .unify(
+        &variable(1).into(),
+        &types::Type::abstraction(variable(0).into(), numeric()),
+        Span::default(),
+      )
+      .unwrap();
+    assert!(matches!(
+      substitution.unify(
+        &variable(0).into(),
+        &types::Type::abstraction(variable(1).into(), numeric()),
+        Span::default(),
+      ),
+      Err(TypeError::InfiniteType { .. })
+    ));
+  }
+
+  #[test]
+  fn tuples_unify_element_wise()
+  {
+    let mut substitution = Substitution::default();
+    substitution
+      .unify(
+        &types::Type::Tuple(vec![variable(0).into(), numeric()]),
+        &types::Type::Tuple(vec![numeric(), variable(1).into()]),
+        Span::default(),
+      )
+      .unwrap();
+    assert_eq!(
+      substitution
+        .resolve(&variable(0).into())
+        .unwrap(),
+      numeric()
+    );
+    assert_eq!(
+      substitution
+        .resolve(&variable(1).into())
+        .unwrap(),
+      numeric()
+    );
+  }
+
+  #[test]
+  fn tuples_of_different_sizes_do_not_unify()
+  {
+    let mut substitution = Substitution::default();
+    assert!(matches!(
+      substitution.unify(
+        &types::Type::Tuple(vec![numeric(), numeric()]),
+        &types::Type::Tuple(vec![numeric(), numeric(), numeric()]),
+        Span::default(),
+      ),
+      Err(TypeError::Mismatch { .. })
+    ));
+  }
+
+  #[test]
+  fn type_arguments_unify_when_the_names_agree()
+  {
+    let option = |argument| {
+      types::Type::Concrete(Identifier::new("option").into(), vec![argument])
+    };
+    let mut substitution = Substitution::default();
+    substitution
+      .unify(&option(variable(0).into()), &option(numeric()), Span::default())
+      .unwrap();
+    assert_eq!(
+      substitution
+        .resolve(&variable(0).into())
+        .unwrap(),
+      numeric()
+    );
+    assert!(matches!(
+      substitution.unify(&option(numeric()), &numeric(), Span::default()),
+      Err(TypeError::Mismatch { .. })
+    ));
+  }
+
+  fn record(
+    fields: Vec<(&str, types::Type)>,
+    rest: Option<types::Variable>,
+  ) -> types::Type
+  {
+    types::Type::record(
+      fields
+        .into_iter()
+        .map(|(name, typ)| (Identifier::new(name), typ))
+        .collect(),
+      rest,
+    )
+  }
+
+  #[test]
+  fn records_unify_field_by_field()
+  {
+    let mut substitution = Substitution::default();
+    substitution
+      .unify(
+        &record(vec![("b", variable(0).into()), ("a", numeric())], None),
+        &record(vec![("a", variable(1).into()), ("b", numeric())], None),
+        Span::default(),
+      )
+      .unwrap();
+    assert_eq!(
+      substitution
+        .resolve(&variable(0).into())
+        .unwrap(),
+      numeric()
+    );
+    assert_eq!(
+      substitution
+        .resolve(&variable(1).into())
+        .unwrap(),
+      numeric()
+    );
+    assert!(matches!(
+      substitution.unify(
+        &record(vec![("a", numeric())], None),
+        &record(vec![("b", numeric())], None),
+        Span::default(),
+      ),
+      Err(TypeError::NoField { field, .. }) if field.name == "a"
+    ));
+  }
+
+  #[test]
+  fn rows_take_the_fields_of_the_other_record()
+  {
+    let mut substitution = Substitution::default();
+    let (left, right) = (substitution.free_name(), substitution.free_name());
+    substitution
+      .unify(
+        &record(vec![("a", numeric())], Some(left.clone())),
+        &record(vec![("b", numeric())], Some(right.clone())),
+        Span::default(),
+      )
+      .unwrap();
+    assert_eq!(
+      substitution
+        .resolve(&record(vec![], Some(left)))
+        .unwrap()
+        .to_string(),
+      "{ b : Int | 't2 }"
+    );
+    assert_eq!(
+      substitution
+        .resolve(&record(vec![], Some(right)))
+        .unwrap()
+        .to_string(),
+      "{ a : Int | 't2 }"
+    );
+  }
+
+  #[test]
+  fn rows_do_not_repeat_fields()
+  {
+    let mut substitution = Substitution::default();
+    let rest = substitution.free_name();
+    substitution.lack(&rest, [Identifier::new("a")]);
+    assert!(matches!(
+      substitution.unify(
+        &record(vec![], Some(rest)),
+        &record(vec![("a", numeric())], None),
+        Span::default(),
+      ),
+      Err(TypeError::FieldPresent { field, .. }) if field.name == "a"
+    ));
+  }
+
+  #[test]
+  fn rows_cannot_contain_themselves()
+  {
+    let mut substitution = Substitution::default();
+    let rest = substitution.free_name();
+    assert!(matches!(
+      substitution.unify(
+        &record(vec![("a", numeric())], Some(rest.clone())),
+        &record(vec![("b", numeric())], Some(rest)),
+        Span::default(),
+      ),
+      Err(TypeError::Mismatch { .. })
+    ));
+  }
+}
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use super::*;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -15,6 +17,66 @@ impl From<Identifier> for Variable
   }
 }
 
+/// The name of a type along with the declaration that introduced it, every
+/// declaration is a type of its own even when it reuses a name. The built-in
+/// types, and types as written until their names are resolved, have none.
+/// The `path` of `Option.t` holds the structures the type is a member of.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Constant
+{
+  pub path: Vec<Identifier>,
+  pub name: Identifier,
+  pub declaration: Option<usize>,
+}
+
+impl From<Identifier> for Constant
+{
+  fn from(name: Identifier) -> Self
+  {
+    Self {
+      path: vec![],
+      name,
+      declaration: None,
+    }
+  }
+}
+
+impl std::fmt::Display for Constant
+{
+  fn fmt(
+    &self,
+    f: &mut std::fmt::Formatter<'_>,
+  ) -> std::fmt::Result
+  {
+    for structure in &self.path {
+      write!(f, "{}.", structure.name)?;
+    }
+    write!(f, "{}", self.name.name)
+  }
+}
+
+/// What a type variable stands for, a type or the further fields of a record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind
+{
+  Type,
+  Row,
+}
+
+impl std::fmt::Display for Kind
+{
+  fn fmt(
+    &self,
+    f: &mut std::fmt::Formatter<'_>,
+  ) -> std::fmt::Result
+  {
+    match self {
+      | Kind::Type => write!(f, "a type"),
+      | Kind::Row => write!(f, "the fields of a record"),
+    }
+  }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Abstraction
 {
@@ -26,8 +88,17 @@ pub struct Abstraction
 pub enum Type
 {
   Variable(Variable),
-  Concrete(Identifier),
+  /// A named type applied to its arguments, `Int` or `'a option`.
+  Concrete(Constant, Vec<Type>),
   Abstraction(Box<Abstraction>),
+  /// The product of the element types, `Int * String`.
+  Tuple(Vec<Type>),
+  /// `List Int`, the built-in type of lists of elements of the same type.
+  List(Box<Type>),
+  /// `{ age : Int; name : String | 'r }`, the fields are sorted by name. The
+  /// row variable `'r` stands for any further fields, the record is closed
+  /// without it.
+  Record(Vec<(Identifier, Type)>, Option<Variable>),
 }
 
 impl Type
@@ -43,13 +114,335 @@ impl Type
     }
     .into()
   }
+
+  pub fn list(element: Type) -> Type
+  {
+    Type::List(Box::new(element))
+  }
+
+  /// The record type of `fields`, in any order, followed by the fields of
+  /// `rest`.
+  pub fn record(
+    mut fields: Vec<(Identifier, Type)>,
+    rest: Option<Variable>,
+  ) -> Type
+  {
+    fields.sort_by(|(left, _), (right, _)| left.name.cmp(&right.name));
+    Type::Record(fields, rest)
+  }
+
+  /// The variables within the type, in order of first appearance.
+  pub fn variables(&self) -> Vec<Variable>
+  {
+    let mut variables = vec![];
+    for (variable, _) in self.kinds() {
+      if !variables.contains(&variable) {
+        variables.push(variable);
+      }
+    }
+    variables
+  }
+
+  /// The variables within the type along with what they stand for, in order
+  /// of first appearance. A variable that stands for both appears twice.
+  pub fn kinds(&self) -> Vec<(Variable, Kind)>
+  {
+    let mut kinds = vec![];
+    self.collect_kinds(&mut kinds);
+    kinds
+  }
+
+  fn collect_kinds(
+    &self,
+    kinds: &mut Vec<(Variable, Kind)>,
+  )
+  {
+    match self {
+      | Type::Variable(variable) =>
+        if !kinds.contains(&(variable.clone(), Kind::Type)) {
+          kinds.push((variable.clone(), Kind::Type));
+        },
+      | Type::Abstraction(abstraction) => {
+        abstraction
+          .parameter_type
+          .collect_kinds(kinds);
+        abstraction
+          .return_type
+          .collect_kinds(kinds);
+      },
+      | Type::Concrete(_, elements) | Type::Tuple(elements) =>
+        for element in elements {
+          element.collect_kinds(kinds);
+        },
+      | Type::List(element) => element.collect_kinds(kinds),
+      | Type::Record(fields, rest) => {
+        for (_, typ) in fields {
+          typ.collect_kinds(kinds);
+        }
+        match rest {
+          | Some(rest) if !kinds.contains(&(rest.clone(), Kind::Row)) =>
+            kinds.push((rest.clone(), Kind::Row)),
+          | _ => (),
+        }
+      },
+    }
+  }
+
+  /// The type with every declared type it names followed by the number of its
+  /// declaration, `t/1`, which tells apart types that shadow one another.
+  pub fn numbered(&self) -> Type
+  {
+    match self {
+      | Type::Variable(_) => self.clone(),
+      | Type::Concrete(constant, arguments) => {
+        let mut constant = constant.clone();
+        if let Some(declaration) = constant.declaration {
+          constant.name.name =
+            format!("{}/{}", constant.name.name, declaration);
+        }
+        Type::Concrete(
+          constant,
+          arguments
+            .iter()
+            .map(Type::numbered)
+            .collect(),
+        )
+      },
+      | Type::Abstraction(abstraction) => Type::abstraction(
+        abstraction.parameter_type.numbered(),
+        abstraction.return_type.numbered(),
+      ),
+      | Type::Tuple(elements) => Type::Tuple(
+        elements
+          .iter()
+          .map(Type::numbered)
+          .collect(),
+      ),
+      | Type::List(element) => Type::list(element.numbered()),
+      | Type::Record(fields, rest) => Type::Record(
+        fields
+          .iter()
+          .map(|(name, typ)| (name.clone(), typ.numbered()))
+          .collect(),
+        rest.clone(),
+      ),
+    }
+  }
+
+  /// Replaces the variables in `replacements` by their replacement.
+  pub fn substitute(
+    &self,
+    replacements: &HashMap<Variable, Type>,
+  ) -> Type
+  {
+    match self {
+      | Type::Variable(variable) => replacements
+        .get(variable)
+        .cloned()
+        .unwrap_or_else(|| self.clone()),
+      | Type::Concrete(constant, arguments) => Type::Concrete(
+        constant.clone(),
+        arguments
+          .iter()
+          .map(|argument| argument.substitute(replacements))
+          .collect(),
+      ),
+      | Type::Abstraction(abstraction) => Type::abstraction(
+        abstraction
+          .parameter_type
+          .substitute(replacements),
+        abstraction
+          .return_type
+          .substitute(replacements),
+      ),
+      | Type::Tuple(elements) => Type::Tuple(
+        elements
+          .iter()
+          .map(|element| element.substitute(replacements))
+          .collect(),
+      ),
+      | Type::List(element) => Type::list(element.substitute(replacements)),
+      | Type::Record(fields, rest) => {
+        let mut fields = fields
+          .iter()
+          .map(|(name, typ)| (name.clone(), typ.substitute(replacements)))
+          .collect::<Vec<_>>();
+        // a row variable is replaced by a variable or by further fields
+        let rest = match rest
+          .as_ref()
+          .and_then(|rest| replacements.get(rest))
+        {
+          | Some(Type::Variable(variable)) => Some(variable.clone()),
+          | Some(Type::Record(more, rest)) => {
+            fields.extend(more.iter().cloned());
+            rest.clone()
+          },
+          | _ => rest.clone(),
+        };
+        Type::record(fields, rest)
+      },
+    }
+  }
+}
+
+/// A type that is polymorphic in `variables`, `forall a. a -> a` is the scheme
+/// of the identity function.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scheme
+{
+  pub variables: Vec<Variable>,
+  pub typ: Type,
+}
+
+impl From<Type> for Scheme
+{
+  fn from(typ: Type) -> Self
+  {
+    Self {
+      variables: vec![],
+      typ,
+    }
+  }
+}
+
+/// Names quantified variables `'a`, `'b`, ... and the remaining ones `'_a`,
+/// `'_b`, ... in order of appearance.
+impl std::fmt::Display for Scheme
+{
+  fn fmt(
+    &self,
+    f: &mut std::fmt::Formatter<'_>,
+  ) -> std::fmt::Result
+  {
+    let mut quantified = 0;
+    let mut free = 0;
+    let mut replacements = HashMap::new();
+    for variable in self.typ.variables() {
+      let name = match self.variables.contains(&variable) {
+        | true => letters(&mut quantified),
+        | false => format!("_{}", letters(&mut free)),
+      };
+      replacements
+        .insert(variable, Variable::Named(Identifier::new(name)).into());
+    }
+    write!(f, "{}", self.typ.substitute(&replacements))
+  }
+}
+
+/// Replacements that name the variables within `types` `'a`, `'b` and so on,
+/// in order of first appearance across all of them, the way the variables of
+/// a scheme are named.
+pub fn lettered<'a>(
+  types: impl IntoIterator<Item = &'a Type>
+) -> HashMap<Variable, Type>
+{
+  let mut counter = 0;
+  let mut replacements = HashMap::new();
+  for typ in types {
+    for variable in typ.variables() {
+      replacements
+        .entry(variable)
+        .or_insert_with(|| {
+          Variable::Named(Identifier::new(letters(&mut counter))).into()
+        });
+    }
+  }
+  replacements
+}
+
+fn letters(counter: &mut usize) -> String
+{
+  let index = *counter;
+  *counter += 1;
+  let letter = (b'a' + (index % 26) as u8) as char;
+  match index / 26 {
+    | 0 => letter.to_string(),
+    | round => format!("{}{}", letter, round),
+  }
+}
+
+impl std::fmt::Display for Variable
+{
+  fn fmt(
+    &self,
+    f: &mut std::fmt::Formatter<'_>,
+  ) -> std::fmt::Result
+  {
+    match self {
+      | Variable::Named(identifier) => write!(f, "'{}", identifier.name),
+      | Variable::Unnamed(name) => write!(f, "'t{}", name),
+    }
+  }
+}
+
+impl std::fmt::Display for Type
+{
+  fn fmt(
+    &self,
+    f: &mut std::fmt::Formatter<'_>,
+  ) -> std::fmt::Result
+  {
+    match self {
+      | Type::Variable(variable) => write!(f, "{}", variable),
+      | Type::Concrete(constant, arguments) => match arguments.as_slice() {
+        | [] => write!(f, "{}", constant),
+        | [argument
+        @ (Type::Abstraction(_) | Type::Tuple(_) | Type::List(_))] =>
+          write!(f, "({}) {}", argument, constant),
+        | [argument] => write!(f, "{} {}", argument, constant),
+        | arguments => {
+          let arguments = arguments
+            .iter()
+            .map(Type::to_string)
+            .collect::<Vec<_>>();
+          write!(f, "({}) {}", arguments.join(", "), constant)
+        },
+      },
+      | Type::Abstraction(abstraction) => match &abstraction.parameter_type {
+        | parameter_type @ Type::Abstraction(_) =>
+          write!(f, "({}) -> {}", parameter_type, abstraction.return_type),
+        | parameter_type =>
+          write!(f, "{} -> {}", parameter_type, abstraction.return_type),
+      },
+      | Type::Tuple(elements) => {
+        let elements = elements
+          .iter()
+          .map(|element| match element {
+            | Type::Abstraction(_) | Type::Tuple(_) => format!("({})", element),
+            | element => element.to_string(),
+          })
+          .collect::<Vec<_>>();
+        write!(f, "{}", elements.join(" * "))
+      },
+      | Type::List(element) => match element.as_ref() {
+        | Type::Concrete(_, arguments) if !arguments.is_empty() =>
+          write!(f, "List ({})", element),
+        | Type::Abstraction(_) | Type::Tuple(_) | Type::List(_) =>
+          write!(f, "List ({})", element),
+        | _ => write!(f, "List {}", element),
+      },
+      | Type::Record(fields, rest) => {
+        let fields = fields
+          .iter()
+          .map(|(name, typ)| format!("{} : {}", name.name, typ))
+          .collect::<Vec<_>>();
+        match (fields.as_slice(), rest) {
+          | ([], None) => write!(f, "{{}}"),
+          | ([], Some(rest)) => write!(f, "{{ | {} }}", rest),
+          | (_, None) => write!(f, "{{ {} }}", fields.join("; ")),
+          | (_, Some(rest)) =>
+            write!(f, "{{ {} | {} }}", fields.join("; "), rest),
+        }
+      },
+    }
+  }
 }
 
 impl From<Identifier> for Type
 {
   fn from(identifier: Identifier) -> Self
   {
-    Self::Concrete(identifier)
+    Self::Concrete(identifier.into(), vec![])
   }
 }
 
@@ -88,4 +481,181 @@ pub struct Equivalent
 {
   pub left: Type,
   pub right: Type,
+  /// The expression that gave rise to the constraint.
+  pub span: Span,
+}
+
+#[cfg(test)]
+mod spec
+{
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn display_concrete()
+  {
+    let typ: Type = Identifier::new("Int").into();
+    assert_eq!(typ.to_string(), "Int");
+  }
+
+  #[test]
+  fn display_variables()
+  {
+    let named: Type = Variable::Named(Identifier::new("a")).into();
+    let unnamed: Type = Variable::Unnamed(3).into();
+    assert_eq!(named.to_string(), "'a");
+    assert_eq!(unnamed.to_string(), "'t3");
+  }
+
+  #[test]
+  fn display_abstractions_associate_to_the_right()
+  {
+    let numeric: Type = Identifier::new("Int").into();
+    let string: Type = Identifier::new("String").into();
+    let curried = Type::abstraction(
+      numeric.clone(),
+      Type::abstraction(string.clone(), numeric.clone()),
+    );
+    let higher_order =
+      Type::abstraction(Type::abstraction(numeric.clone(), string), numeric);
+    assert_eq!(curried.to_string(), "Int -> String -> Int");
+    assert_eq!(higher_order.to_string(), "(Int -> String) -> Int");
+  }
+
+  #[test]
+  fn display_tuples_as_products()
+  {
+    let numeric: Type = Identifier::new("Int").into();
+    let string: Type = Identifier::new("String").into();
+    let pair = Type::Tuple(vec![numeric.clone(), string.clone()]);
+    let nested = Type::Tuple(vec![
+      pair.clone(),
+      Type::abstraction(numeric.clone(), string),
+    ]);
+    assert_eq!(pair.to_string(), "Int * String");
+    assert_eq!(nested.to_string(), "(Int * String) * (Int -> String)");
+    assert_eq!(
+      Type::abstraction(pair, numeric).to_string(),
+      "Int * String -> Int"
+    );
+  }
+
+  #[test]
+  fn display_type_arguments_before_the_constructor()
+  {
+    let numeric: Type = Identifier::new("Int").into();
+    let option = |argument| {
+      Type::Concrete(Identifier::new("option").into(), vec![argument])
+    };
+    let either = Type::Concrete(Identifier::new("either").into(), vec![
+      numeric.clone(),
+      option(numeric.clone()),
+    ]);
+    assert_eq!(
+      option(option(numeric.clone())).to_string(),
+      "Int option option"
+    );
+    assert_eq!(
+      option(Type::Tuple(vec![numeric.clone(), numeric])).to_string(),
+      "(Int * Int) option"
+    );
+    assert_eq!(either.to_string(), "(Int, Int option) either");
+  }
+
+  #[test]
+  fn display_lists_before_their_element()
+  {
+    let numeric: Type = Identifier::new("Int").into();
+    let option = |argument| {
+      Type::Concrete(Identifier::new("option").into(), vec![argument])
+    };
+    assert_eq!(Type::list(numeric.clone()).to_string(), "List Int");
+    assert_eq!(
+      Type::list(Type::list(numeric.clone())).to_string(),
+      "List (List Int)"
+    );
+    assert_eq!(
+      Type::list(option(numeric.clone())).to_string(),
+      "List (Int option)"
+    );
+    assert_eq!(
+      option(Type::list(numeric.clone())).to_string(),
+      "(List Int) option"
+    );
+    assert_eq!(
+      Type::abstraction(Type::list(numeric.clone()), numeric).to_string(),
+      "List Int -> Int"
+    );
+  }
+
+  #[test]
+  fn display_schemes_with_letters()
+  {
+    let scheme = Scheme {
+      variables: vec![Variable::Unnamed(7), Variable::Unnamed(3)],
+      typ: Type::abstraction(
+        Variable::Unnamed(3).into(),
+        Type::abstraction(
+          Variable::Unnamed(5).into(),
+          Variable::Unnamed(7).into(),
+        ),
+      ),
+    };
+    assert_eq!(scheme.to_string(), "'a -> '_a -> 'b");
+  }
+
+  #[test]
+  fn display_monomorphic_schemes()
+  {
+    let scheme: Scheme = Type::from(Identifier::new("Int")).into();
+    assert_eq!(scheme.to_string(), "Int");
+  }
+
+  #[test]
+  fn display_records_with_sorted_fields()
+  {
+    let numeric: Type = Identifier::new("Int").into();
+    let record = Type::record(
+      vec![
+        (Identifier::new("name"), Identifier::new("String").into()),
+        (Identifier::new("age"), Type::Tuple(vec![numeric.clone(), numeric])),
+      ],
+      None,
+    );
+    assert_eq!(record.to_string(), "{ age : Int * Int; name : String }");
+  }
+
+  #[test]
+  fn display_open_records_with_their_row()
+  {
+    let rest = Variable::Named(Identifier::new("r"));
+    let numeric: Type = Identifier::new("Int").into();
+    let open =
+      Type::record(vec![(Identifier::new("age"), numeric)], Some(rest.clone()));
+    assert_eq!(open.to_string(), "{ age : Int | 'r }");
+    assert_eq!(Type::record(vec![], Some(rest)).to_string(), "{ | 'r }");
+    assert_eq!(Type::record(vec![], None).to_string(), "{}");
+  }
+
+  #[test]
+  fn substituting_a_row_extends_the_record()
+  {
+    let rest = Variable::Named(Identifier::new("r"));
+    let numeric: Type = Identifier::new("Int").into();
+    let open = Type::record(
+      vec![(Identifier::new("b"), numeric.clone())],
+      Some(rest.clone()),
+    );
+    let replacements = HashMap::from([(
+      rest,
+      Type::record(vec![(Identifier::new("a"), numeric)], None),
+    )]);
+    assert_eq!(
+      open
+        .substitute(&replacements)
+        .to_string(),
+      "{ a : Int; b : Int }"
+    );
+  }
 }